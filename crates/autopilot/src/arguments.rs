@@ -109,6 +109,25 @@ pub struct Arguments {
     )]
     pub min_order_validity_period: Duration,
 
+    /// Maximum number of chunks of balance queries fetched concurrently when
+    /// updating the solvable orders.
+    #[clap(long, env, default_value = "20")]
+    pub balance_fetch_parallelism: NonZeroUsize,
+
+    /// Maximum number of balance queries fetched together in one chunk.
+    #[clap(long, env, default_value = "1000")]
+    pub balance_fetch_chunk_size: NonZeroUsize,
+
+    /// How long balances keep being cached and refreshed on new blocks after
+    /// they were last requested.
+    #[clap(
+        long,
+        env,
+        default_value = "1m",
+        value_parser = humantime::parse_duration,
+    )]
+    pub balance_cache_ttl: Duration,
+
     /// List of account addresses to be denied from order creation
     #[clap(long, env, use_value_delimiter = true)]
     pub banned_users: Vec<H160>,
@@ -191,6 +210,22 @@ pub struct Arguments {
     )]
     pub solve_deadline: Duration,
 
+    /// How long to accumulate order changes before cutting a new auction. A
+    /// shorter window lowers the latency until orders are solved, a longer
+    /// one batches more orders together and creates more CoW opportunities.
+    #[clap(
+        long,
+        env,
+        default_value = "0s",
+        value_parser = humantime::parse_duration,
+    )]
+    pub auction_batching_window: Duration,
+
+    /// Maximum number of solvers participating in a single auction. If fewer
+    /// than all solvers may participate, they take turns across auctions.
+    #[clap(long, env)]
+    pub max_solvers_per_auction: Option<usize>,
+
     /// Describes how the protocol fees should be calculated.
     #[clap(long, env, use_value_delimiter = true)]
     pub fee_policies: Vec<FeePolicy>,
@@ -204,6 +239,13 @@ pub struct Arguments {
     #[clap(long, env, default_value = "0.01")]
     pub fee_policy_max_partner_fee: FeeFactor,
 
+    /// Pay out partner fees to the recipients specified in the orders' app
+    /// data as part of the settlement. The transfers are paid for by the
+    /// winning solver and the paid out fees are no longer accounted as fees
+    /// kept by the protocol.
+    #[clap(long, env, action = clap::ArgAction::Set, default_value = "false")]
+    pub pay_out_partner_fees: bool,
+
     /// Arguments for uploading information to S3.
     #[clap(flatten)]
     pub s3: infra::persistence::cli::S3,
@@ -248,6 +290,9 @@ impl std::fmt::Display for Arguments {
             pool_cache_lru_size,
             native_price_estimators,
             min_order_validity_period,
+            balance_fetch_parallelism,
+            balance_fetch_chunk_size,
+            balance_cache_ttl,
             banned_users,
             max_auction_age,
             limit_order_price_factor,
@@ -258,9 +303,12 @@ impl std::fmt::Display for Arguments {
             submission_deadline,
             shadow,
             solve_deadline,
+            auction_batching_window,
+            max_solvers_per_auction,
             fee_policies,
             enable_multiple_fees,
             fee_policy_max_partner_fee,
+            pay_out_partner_fees,
             order_events_cleanup_interval,
             order_events_cleanup_threshold,
             db_url,
@@ -298,6 +346,13 @@ impl std::fmt::Display for Arguments {
             "min_order_validity_period: {:?}",
             min_order_validity_period
         )?;
+        writeln!(
+            f,
+            "balance_fetch_parallelism: {}",
+            balance_fetch_parallelism
+        )?;
+        writeln!(f, "balance_fetch_chunk_size: {}", balance_fetch_chunk_size)?;
+        writeln!(f, "balance_cache_ttl: {:?}", balance_cache_ttl)?;
         writeln!(f, "banned_users: {:?}", banned_users)?;
         writeln!(f, "max_auction_age: {:?}", max_auction_age)?;
         writeln!(
@@ -316,6 +371,8 @@ impl std::fmt::Display for Arguments {
         writeln!(f, "submission_deadline: {}", submission_deadline)?;
         display_option(f, "shadow", shadow)?;
         writeln!(f, "solve_deadline: {:?}", solve_deadline)?;
+        writeln!(f, "auction_batching_window: {:?}", auction_batching_window)?;
+        display_option(f, "max_solvers_per_auction", max_solvers_per_auction)?;
         writeln!(f, "fee_policies: {:?}", fee_policies)?;
         writeln!(f, "enable_multiple_fees: {:?}", enable_multiple_fees)?;
         writeln!(
@@ -323,6 +380,7 @@ impl std::fmt::Display for Arguments {
             "fee_policy_max_partner_fee: {:?}",
             fee_policy_max_partner_fee
         )?;
+        writeln!(f, "pay_out_partner_fees: {:?}", pay_out_partner_fees)?;
         writeln!(
             f,
             "order_events_cleanup_interval: {:?}",
@@ -14,6 +14,7 @@ pub mod onchain_order_events;
 pub mod order_events;
 mod quotes;
 pub mod recent_settlements;
+pub mod snapshot;
 
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -65,6 +66,20 @@ impl Postgres {
         Ok(())
     }
 
+    pub async fn update_open_orders_metric(&self) -> sqlx::Result<()> {
+        let metrics = Metrics::get();
+        let _timer = metrics
+            .database_queries
+            .with_label_values(&["count_open_orders"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let min_valid_to = model::time::now_in_epoch_seconds().into();
+        let count = database::orders::count_open_orders(&mut ex, min_valid_to).await?;
+        metrics.open_orders.set(count);
+        Ok(())
+    }
+
     pub async fn update_large_tables_stats(&self) -> sqlx::Result<()> {
         for &table in database::LARGE_TABLES {
             let mut ex = self.pool.acquire().await?;
@@ -116,6 +131,9 @@ struct Metrics {
     /// corresponding order in the `orders` table.
     unused_app_data: prometheus::IntGauge,
 
+    /// Number of currently open orders.
+    open_orders: prometheus::IntGauge,
+
     /// Timing of db queries.
     #[metric(name = "autopilot_database_queries", labels("type"))]
     database_queries: prometheus::HistogramVec,
@@ -145,6 +163,9 @@ async fn database_metrics(db: Postgres) -> ! {
         if let Err(err) = db.update_database_metrics().await {
             tracing::error!(?err, "failed to update table rows metric");
         }
+        if let Err(err) = db.update_open_orders_metric().await {
+            tracing::error!(?err, "failed to update open orders metric");
+        }
     }
 }
 
@@ -7,6 +7,7 @@ use {
         byte_array::ByteArray,
         settlement_call_data::SettlementCallData,
         settlement_scores::Score,
+        solver_scores::SolverScore,
         surplus_capturing_jit_order_owners,
         Address,
     },
@@ -28,6 +29,8 @@ pub struct Competition {
     /// Addresses to which the CIP20 participation rewards will be payed out.
     /// Usually the same as the solver addresses.
     pub participants: HashSet<H160>,
+    /// The best score of every participating solver.
+    pub scores: BTreeMap<H160, U256>,
     /// External prices for auction.
     pub prices: BTreeMap<H160, U256>,
     /// Winner receives performance rewards if a settlement is finalized on
@@ -93,6 +96,22 @@ impl super::Postgres {
         .await
         .context("auction_participants::insert")?;
 
+        database::solver_scores::insert(
+            &mut ex,
+            competition
+                .scores
+                .iter()
+                .map(|(solver, score)| SolverScore {
+                    auction_id: competition.auction_id,
+                    solver: ByteArray(solver.0),
+                    score: u256_to_big_decimal(score),
+                })
+                .collect::<Vec<_>>()
+                .as_slice(),
+        )
+        .await
+        .context("solver_scores::insert")?;
+
         database::auction_prices::insert(
             &mut ex,
             competition
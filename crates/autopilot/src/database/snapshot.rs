@@ -0,0 +1,102 @@
+use {
+    super::Postgres,
+    anyhow::Result,
+    chrono::{DateTime, Utc},
+    futures::{StreamExt, TryStreamExt},
+    model::order::Order,
+    serde::{Deserialize, Serialize},
+    shared::db_order_conversions::full_order_into_model_order,
+    std::ops::DerefMut,
+};
+
+/// The orders which were open at a point in time, e.g. to replay historical
+/// states of the order book through solvers for backtesting.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    pub at: DateTime<Utc>,
+    pub latest_settlement_block: u64,
+    /// The open orders including the amounts they executed so far.
+    pub orders: Vec<Order>,
+}
+
+impl Postgres {
+    /// Exports the orders which were created at or before `at` and are still
+    /// open as of `at`.
+    pub async fn export_snapshot(&self, at: DateTime<Utc>) -> Result<Snapshot> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["export_snapshot"])
+            .start_timer();
+
+        let mut ex = self.pool.begin().await?;
+        // Read the orders and the latest settlement block from the same database
+        // snapshot.
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+            .execute(ex.deref_mut())
+            .await?;
+        let orders = database::orders::open_orders_at(&mut ex, at)
+            .map(|result| match result {
+                Ok(order) => full_order_into_model_order(order),
+                Err(err) => Err(anyhow::Error::from(err)),
+            })
+            .try_collect()
+            .await?;
+        let latest_settlement_block =
+            database::orders::latest_settlement_block(&mut ex).await? as u64;
+        Ok(Snapshot {
+            at,
+            latest_settlement_block,
+            orders,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        chrono::TimeZone,
+        database::{
+            byte_array::ByteArray,
+            orders::{OrderKind, SigningScheme},
+        },
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_snapshot_roundtrip() {
+        let db = Postgres::with_defaults().await.unwrap();
+        let mut ex = db.pool.begin().await.unwrap();
+        database::clear_DANGER_(&mut ex).await.unwrap();
+
+        for uid in [1, 2] {
+            database::orders::insert_order(
+                &mut ex,
+                &database::orders::Order {
+                    uid: ByteArray([uid; 56]),
+                    kind: OrderKind::Sell,
+                    sell_amount: 10.into(),
+                    buy_amount: 100.into(),
+                    valid_to: 2_000,
+                    signing_scheme: SigningScheme::Eip1271,
+                    creation_timestamp: Utc.timestamp_opt(900, 0).unwrap(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        }
+        ex.commit().await.unwrap();
+
+        let snapshot = db
+            .export_snapshot(Utc.timestamp_opt(1_000, 0).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(snapshot.orders.len(), 2);
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let deserialized: Snapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, snapshot);
+    }
+}
@@ -13,6 +13,8 @@ pub struct Auction {
     pub orders: Vec<Order>,
     pub prices: Prices,
     pub surplus_capturing_jit_order_owners: Vec<eth::Address>,
+    /// When the auction was cut.
+    pub created: chrono::DateTime<chrono::Utc>,
 }
 
 pub type Id = i64;
@@ -57,6 +57,7 @@ pub type ProtocolFeeExemptAddresses = HashSet<H160>;
 pub struct ProtocolFees {
     fee_policies: Vec<ProtocolFee>,
     max_partner_fee: FeeFactor,
+    pay_out_partner_fees: bool,
     enable_protocol_fees: bool,
 }
 
@@ -64,6 +65,7 @@ impl ProtocolFees {
     pub fn new(
         fee_policies: &[arguments::FeePolicy],
         fee_policy_max_partner_fee: FeeFactor,
+        pay_out_partner_fees: bool,
         enable_protocol_fees: bool,
     ) -> Self {
         Self {
@@ -73,6 +75,7 @@ impl ProtocolFees {
                 .map(ProtocolFee::from)
                 .collect(),
             max_partner_fee: fee_policy_max_partner_fee,
+            pay_out_partner_fees,
             enable_protocol_fees,
         }
     }
@@ -101,6 +104,9 @@ impl ProtocolFees {
                             self.max_partner_fee.into(),
                         )
                         .unwrap(),
+                        recipient: self
+                            .pay_out_partner_fees
+                            .then(|| partner_fee.recipient.into()),
                     })
             })
             .into_iter()
@@ -242,9 +248,23 @@ pub enum Policy {
         /// Percentage of the order's volume should be taken as a protocol
         /// fee.
         factor: FeeFactor,
+        /// The integrator the fee is paid out to as part of the settlement,
+        /// for partner fees specified in the order's app data if paying them
+        /// out is enabled.
+        recipient: Option<eth::Address>,
     },
 }
 
+impl Policy {
+    /// The recipient the fee is paid out to as part of the settlement, if any.
+    pub fn recipient(&self) -> Option<eth::Address> {
+        match self {
+            Self::Volume { recipient, .. } => *recipient,
+            Self::Surplus { .. } | Self::PriceImprovement { .. } => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Into)]
 pub struct FeeFactor(f64);
 
@@ -87,6 +87,7 @@ impl Volume {
             boundary::OrderClass::Liquidity => None,
             boundary::OrderClass::Limit => Some(domain::fee::Policy::Volume {
                 factor: self.factor,
+                recipient: None,
             }),
         }
     }
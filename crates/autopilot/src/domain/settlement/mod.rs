@@ -99,13 +99,13 @@ impl Settlement {
 
     /// Total fee expressed in native token.
     pub fn native_fee(&self) -> eth::Ether {
-        self.solution.native_fee(&self.auction.prices)
+        self.solution.native_fee(&self.auction)
     }
 
     /// Per order fees denominated in sell token. Contains all orders from the
     /// settlement
     pub fn order_fees(&self) -> HashMap<domain::OrderUid, Option<eth::SellTokenAmount>> {
-        self.solution.fees(&self.auction.prices)
+        self.solution.fees(&self.auction)
     }
 }
 
@@ -69,11 +69,11 @@ impl Solution {
     ///
     /// Always returns a value, even if some trades have incomplete fee
     /// calculation.
-    pub fn native_fee(&self, prices: &auction::Prices) -> eth::Ether {
+    pub fn native_fee(&self, auction: &super::Auction) -> eth::Ether {
         self.trades
             .iter()
             .map(|trade| {
-                trade.native_fee(prices).unwrap_or_else(|err| {
+                trade.native_fee(auction).unwrap_or_else(|err| {
                     tracing::warn!(
                         ?err,
                         "possible incomplete fee calculation for trade {}",
@@ -88,11 +88,11 @@ impl Solution {
     /// Returns fees denominated in sell token for each order in the solution.
     pub fn fees(
         &self,
-        prices: &auction::Prices,
+        auction: &super::Auction,
     ) -> HashMap<domain::OrderUid, Option<eth::SellTokenAmount>> {
         self.trades
             .iter()
-            .map(|trade| (*trade.order_uid(), trade.fee_in_sell_token(prices).ok()))
+            .map(|trade| (*trade.order_uid(), trade.fee_in_sell_token(auction).ok()))
             .collect()
     }
 
@@ -321,12 +321,12 @@ mod tests {
         // fee read from "executedSurplusFee" https://api.cow.fi/mainnet/api/v1/orders/0x10dab31217bb6cc2ace0fe601c15d342f7626a1ee5ef0495449800e73156998740a50cf069e992aa4536211b23f286ef88752187ffffffff
         // "executedSurplusFee" and native fee are equal because the sell token is ETH
         assert_eq!(
-            solution.native_fee(&auction.prices).0,
+            solution.native_fee(&auction).0,
             eth::U256::from(6752697350740628u128)
         );
         // fee read from "executedSurplusFee" https://api.cow.fi/mainnet/api/v1/orders/0x10dab31217bb6cc2ace0fe601c15d342f7626a1ee5ef0495449800e73156998740a50cf069e992aa4536211b23f286ef88752187ffffffff
         assert_eq!(
-            solution.fees(&auction.prices),
+            solution.fees(&auction),
             HashMap::from([(domain::OrderUid(hex!("10dab31217bb6cc2ace0fe601c15d342f7626a1ee5ef0495449800e73156998740a50cf069e992aa4536211b23f286ef88752187ffffffff")), Some(eth::SellTokenAmount(eth::U256::from(6752697350740628u128))))])
         );
     }
@@ -1,13 +1,7 @@
 pub use error::Error;
 use {
     crate::{
-        domain::{
-            self,
-            auction::{self, order},
-            eth,
-            fee,
-            settlement,
-        },
+        domain::{self, auction::order, eth, fee, settlement},
         util::conv::U256Ext,
     },
     bigdecimal::Zero,
@@ -134,34 +128,39 @@ impl Trade {
         Ok(price.in_eth(surplus.amount))
     }
 
-    /// Total fee (protocol fee + network fee). Equal to a surplus difference
-    /// before and after applying the fees.
+    /// Total fee (protocol fee + network fee) kept by the protocol. Equal to a
+    /// surplus difference before and after applying the fees, excluding
+    /// partner fees paid out to their recipients.
     ///
     /// Denominated in NATIVE token
-    pub fn native_fee(&self, prices: &auction::Prices) -> Result<eth::Ether, Error> {
-        let fee = self.fee()?;
-        let price = prices
+    pub fn native_fee(&self, auction: &settlement::Auction) -> Result<eth::Ether, Error> {
+        let fee = self.fee(auction)?;
+        let price = auction
+            .prices
             .get(&fee.token)
             .ok_or(Error::MissingPrice(fee.token))?;
         Ok(price.in_eth(fee.amount))
     }
 
-    /// Total fee (protocol fee + network fee). Equal to a surplus difference
-    /// before and after applying the fees.
+    /// Total fee (protocol fee + network fee) kept by the protocol. Equal to a
+    /// surplus difference before and after applying the fees, excluding
+    /// partner fees paid out to their recipients.
     ///
     /// Denominated in SELL token
     pub fn fee_in_sell_token(
         &self,
-        prices: &auction::Prices,
+        auction: &settlement::Auction,
     ) -> Result<eth::SellTokenAmount, Error> {
-        let fee = self.fee()?;
+        let fee = self.fee(auction)?;
         let fee_in_sell_token = match self.side {
             order::Side::Buy => fee.amount,
             order::Side::Sell => {
-                let buy_price = prices
+                let buy_price = auction
+                    .prices
                     .get(&self.buy.token)
                     .ok_or(Error::MissingPrice(self.buy.token))?;
-                let sell_price = prices
+                let sell_price = auction
+                    .prices
                     .get(&self.sell.token)
                     .ok_or(Error::MissingPrice(self.sell.token))?;
                 fee.amount
@@ -175,15 +174,26 @@ impl Trade {
         Ok(fee_in_sell_token)
     }
 
-    /// Total fee (protocol fee + network fee). Equal to a surplus difference
-    /// before and after applying the fees.
+    /// Total fee (protocol fee + network fee) kept by the protocol. Equal to a
+    /// surplus difference before and after applying the fees, excluding
+    /// partner fees paid out to their recipients.
     ///
     /// Denominated in SURPLUS token
-    fn fee(&self) -> Result<eth::Asset, Error> {
+    fn fee(&self, auction: &settlement::Auction) -> Result<eth::Asset, Error> {
+        let paid_out = self
+            .protocol_fee_per_policy(self.policies(auction))?
+            .into_iter()
+            .filter(|(policy, _)| policy.recipient().is_some())
+            .fold(eth::TokenAmount::default(), |mut amount, (_, fee)| {
+                amount += fee;
+                amount
+            });
         let fee = self
             .surplus_over_limit_price_before_fee()?
             .amount
             .checked_sub(&self.surplus_over_limit_price()?.amount)
+            .ok_or(error::Math::Negative)?
+            .checked_sub(&paid_out)
             .ok_or(error::Math::Negative)?;
         Ok(eth::Asset {
             token: self.surplus_token(),
@@ -195,22 +205,50 @@ impl Trade {
     ///
     /// Denominated in SURPLUS token
     fn protocol_fees(&self, policies: &[fee::Policy]) -> Result<eth::Asset, Error> {
+        let amount = self.protocol_fee_per_policy(policies)?.into_iter().fold(
+            eth::TokenAmount::default(),
+            |mut amount, (_, fee)| {
+                amount += fee;
+                amount
+            },
+        );
+        Ok(eth::Asset {
+            token: self.surplus_token(),
+            amount,
+        })
+    }
+
+    /// The protocol fee charged by each of the fee policies attached to the
+    /// order, in reverse order of application.
+    ///
+    /// Denominated in SURPLUS token
+    fn protocol_fee_per_policy<'a>(
+        &self,
+        policies: &'a [fee::Policy],
+    ) -> Result<Vec<(&'a fee::Policy, eth::TokenAmount)>, Error> {
         let mut current_trade = self.clone();
         let mut amount = eth::TokenAmount::default();
+        let mut fees = Vec::with_capacity(policies.len());
         for (i, protocol_fee) in policies.iter().enumerate().rev() {
             let fee = current_trade.protocol_fee(protocol_fee)?;
             // Do not need to calculate the last custom prices because in the last iteration
             // the prices are not used anymore to calculate the protocol fee
             amount += fee;
+            fees.push((protocol_fee, fee));
             if !i.is_zero() {
                 current_trade.prices.custom = self.calculate_custom_prices(amount)?;
             }
         }
+        Ok(fees)
+    }
 
-        Ok(eth::Asset {
-            token: self.surplus_token(),
-            amount,
-        })
+    /// The fee policies attached to the order in the auction.
+    fn policies<'a>(&self, auction: &'a settlement::Auction) -> &'a [fee::Policy] {
+        auction
+            .orders
+            .get(&self.order_uid)
+            .map(|value| value.as_slice())
+            .unwrap_or_default()
     }
 
     /// The effective amount that left the user's wallet including all fees.
@@ -306,7 +344,7 @@ impl Trade {
                 );
                 Ok(fee)
             }
-            fee::Policy::Volume { factor } => Ok(self.volume_fee((*factor).into())?.amount),
+            fee::Policy::Volume { factor, .. } => Ok(self.volume_fee((*factor).into())?.amount),
         }
     }
 
@@ -455,13 +493,7 @@ impl Trade {
     ///
     /// Denominated in NATIVE token
     fn native_protocol_fee(&self, auction: &settlement::Auction) -> Result<eth::Ether, Error> {
-        let protocol_fee = self.protocol_fees(
-            auction
-                .orders
-                .get(&self.order_uid)
-                .map(|value| value.as_slice())
-                .unwrap_or_default(),
-        )?;
+        let protocol_fee = self.protocol_fees(self.policies(auction))?;
         let price = auction
             .prices
             .get(&protocol_fee.token)
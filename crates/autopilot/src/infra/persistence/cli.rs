@@ -1,6 +1,6 @@
 //! Command line arguments for persistence.
 
-use anyhow::Result;
+use {super::local, anyhow::Result, std::path::PathBuf};
 
 #[derive(clap::Parser, Debug, Clone)]
 pub struct S3 {
@@ -14,9 +14,24 @@ pub struct S3 {
     /// Something like "staging/mainnet/"
     #[clap(long, env)]
     pub s3_instance_upload_filename_prefix: Option<String>,
+
+    /// Additionally write every auction instance as JSON into this local
+    /// directory, named the same way as the S3 upload. Can be used with or
+    /// without uploading to S3.
+    #[clap(long, env)]
+    pub instance_export_dir: Option<PathBuf>,
 }
 
 impl S3 {
+    pub fn local_export(&self) -> Option<local::Exporter> {
+        let dir = self.instance_export_dir.clone()?;
+        let prefix = self
+            .s3_instance_upload_filename_prefix
+            .clone()
+            .unwrap_or_default();
+        Some(local::Exporter::new(dir, prefix))
+    }
+
     pub fn into(self) -> Result<Option<s3::Config>> {
         let s3_args = &[
             &self.s3_instance_upload_bucket,
@@ -4,6 +4,7 @@ use {
         domain,
         domain::{auction::Price, eth},
     },
+    chrono::{DateTime, Utc},
     number::serialization::HexOrDecimalU256,
     primitive_types::{H160, U256},
     serde::{Deserialize, Serialize},
@@ -30,6 +31,7 @@ pub fn from_domain(auction: domain::Auction) -> Auction {
             .into_iter()
             .map(Into::into)
             .collect(),
+        created: auction.created,
     }
 }
 
@@ -54,6 +56,7 @@ pub fn try_to_domain(auction: Auction) -> anyhow::Result<domain::Auction> {
             .into_iter()
             .map(Into::into)
             .collect(),
+        created: auction.created,
     })
 }
 
@@ -68,6 +71,7 @@ pub struct Auction {
     pub prices: BTreeMap<H160, U256>,
     #[serde(default)]
     pub surplus_capturing_jit_order_owners: Vec<H160>,
+    pub created: DateTime<Utc>,
 }
 
 pub type AuctionId = i64;
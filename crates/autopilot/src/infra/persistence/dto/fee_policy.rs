@@ -1,5 +1,8 @@
 use {
-    crate::{boundary, domain},
+    crate::{
+        boundary,
+        domain::{self, eth},
+    },
     anyhow::Context,
     database::fee_policies::{FeePolicy, FeePolicyKind},
 };
@@ -22,8 +25,9 @@ pub fn from_domain(
             volume_factor: None,
             price_improvement_factor: None,
             price_improvement_max_volume_factor: None,
+            volume_fee_recipient: None,
         },
-        domain::fee::Policy::Volume { factor } => FeePolicy {
+        domain::fee::Policy::Volume { factor, recipient } => FeePolicy {
             auction_id,
             order_uid: boundary::database::byte_array::ByteArray(order_uid.0),
             kind: FeePolicyKind::Volume,
@@ -32,6 +36,8 @@ pub fn from_domain(
             volume_factor: Some(factor.into()),
             price_improvement_factor: None,
             price_improvement_max_volume_factor: None,
+            volume_fee_recipient: recipient
+                .map(|recipient| boundary::database::byte_array::ByteArray(recipient.0 .0)),
         },
         domain::fee::Policy::PriceImprovement {
             factor,
@@ -46,6 +52,7 @@ pub fn from_domain(
             volume_factor: None,
             price_improvement_factor: Some(factor.into()),
             price_improvement_max_volume_factor: Some(max_volume_factor.into()),
+            volume_fee_recipient: None,
         },
     }
 }
@@ -70,6 +77,9 @@ pub fn try_into_domain(
                 .volume_factor
                 .context("missing volume_factor")?
                 .try_into()?,
+            recipient: policy
+                .volume_fee_recipient
+                .map(|recipient| eth::H160(recipient.0).into()),
         },
         FeePolicyKind::PriceImprovement => domain::fee::Policy::PriceImprovement {
             factor: policy
@@ -274,7 +274,11 @@ pub enum FeePolicy {
         quote: Quote,
     },
     #[serde(rename_all = "camelCase")]
-    Volume { factor: f64 },
+    Volume {
+        factor: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        recipient: Option<H160>,
+    },
 }
 
 #[serde_as]
@@ -312,8 +316,9 @@ impl From<domain::fee::Policy> for FeePolicy {
                     fee: quote.fee,
                 },
             },
-            domain::fee::Policy::Volume { factor } => Self::Volume {
+            domain::fee::Policy::Volume { factor, recipient } => Self::Volume {
                 factor: factor.into(),
+                recipient: recipient.map(Into::into),
             },
         }
     }
@@ -342,8 +347,9 @@ impl From<FeePolicy> for domain::fee::Policy {
                     fee: quote.fee,
                 },
             },
-            FeePolicy::Volume { factor } => Self::Volume {
+            FeePolicy::Volume { factor, recipient } => Self::Volume {
                 factor: FeeFactor::try_from(factor).unwrap(),
+                recipient: recipient.map(Into::into),
             },
         }
     }
@@ -0,0 +1,65 @@
+//! Writes auction instances to a local directory, which makes them available
+//! for offline debugging without access to S3.
+
+use {
+    anyhow::{Context, Result},
+    serde::Serialize,
+    std::path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone)]
+pub struct Exporter {
+    dir: PathBuf,
+    /// Prepended to the auction id like the S3 filename prefix, so local
+    /// files are named the same way as their S3 keys.
+    filename_prefix: String,
+}
+
+impl Exporter {
+    pub fn new(dir: PathBuf, filename_prefix: String) -> Self {
+        Self {
+            dir,
+            filename_prefix,
+        }
+    }
+
+    /// Writes the content JSON encoded to `<dir>/<prefix><id>.json`. Returns
+    /// the path of the written file.
+    pub async fn export(&self, id: String, content: impl Serialize) -> Result<PathBuf> {
+        let bytes = serde_json::to_vec(&content)?;
+        let path = self.path(&id);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("creating {}", parent.display()))?;
+        }
+        tokio::fs::write(&path, bytes)
+            .await
+            .with_context(|| format!("writing {}", path.display()))?;
+        Ok(path)
+    }
+
+    fn path(&self, id: &str) -> PathBuf {
+        self.dir
+            .join(Path::new(&self.filename_prefix).join(format!("{id}.json")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[tokio::test]
+    async fn writes_instance_named_like_s3_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let exporter = Exporter::new(dir.path().to_owned(), "staging/mainnet/".to_owned());
+
+        let instance = json!({ "id": 42, "orders": [] });
+        let path = exporter.export("42".to_owned(), &instance).await.unwrap();
+
+        assert_eq!(path, dir.path().join("staging/mainnet/42.json"));
+        let written: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(written, instance);
+    }
+}
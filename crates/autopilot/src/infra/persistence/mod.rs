@@ -19,20 +19,27 @@ use {
 
 pub mod cli;
 pub mod dto;
+pub mod local;
 
 #[derive(Clone)]
 pub struct Persistence {
     s3: Option<s3::Uploader>,
+    local: Option<local::Exporter>,
     postgres: Arc<Postgres>,
 }
 
 impl Persistence {
-    pub async fn new(config: Option<s3::Config>, postgres: Arc<Postgres>) -> Self {
+    pub async fn new(
+        config: Option<s3::Config>,
+        local: Option<local::Exporter>,
+        postgres: Arc<Postgres>,
+    ) -> Self {
         Self {
             s3: match config {
                 Some(config) => Some(s3::Uploader::new(config).await),
                 None => None,
             },
+            local,
             postgres,
         }
     }
@@ -71,17 +78,31 @@ impl Persistence {
     ///
     /// There is no intention to retrieve this data programmatically.
     fn archive_auction(&self, id: domain::auction::Id, instance: dto::auction::Auction) {
-        let Some(uploader) = self.s3.clone() else {
+        if self.s3.is_none() && self.local.is_none() {
             return;
-        };
+        }
+        let uploader = self.s3.clone();
+        let exporter = self.local.clone();
         tokio::spawn(
             async move {
-                match uploader.upload(id.to_string(), &instance).await {
-                    Ok(key) => {
-                        tracing::info!(?key, "uploaded auction to s3");
+                if let Some(uploader) = uploader {
+                    match uploader.upload(id.to_string(), &instance).await {
+                        Ok(key) => {
+                            tracing::info!(?key, "uploaded auction to s3");
+                        }
+                        Err(err) => {
+                            tracing::warn!(?err, "failed to upload auction to s3");
+                        }
                     }
-                    Err(err) => {
-                        tracing::warn!(?err, "failed to upload auction to s3");
+                }
+                if let Some(exporter) = exporter {
+                    match exporter.export(id.to_string(), &instance).await {
+                        Ok(path) => {
+                            tracing::debug!(?path, "exported auction to local file");
+                        }
+                        Err(err) => {
+                            tracing::warn!(?err, "failed to export auction to local file");
+                        }
                     }
                 }
             }
@@ -47,6 +47,7 @@ impl Request {
                 .unique_by(|token| token.address)
                 .collect(),
             deadline: Utc::now() + chrono::Duration::from_std(time_limit).unwrap(),
+            created: auction.created,
             surplus_capturing_jit_order_owners: auction
                 .surplus_capturing_jit_order_owners
                 .iter()
@@ -76,6 +77,9 @@ pub struct Request {
     pub tokens: Vec<Token>,
     pub orders: Vec<Order>,
     pub deadline: DateTime<Utc>,
+    /// When the auction was cut. Drivers may refuse to solve auctions which
+    /// are too old.
+    pub created: DateTime<Utc>,
     pub surplus_capturing_jit_order_owners: Vec<H160>,
 }
 
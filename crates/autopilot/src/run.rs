@@ -14,7 +14,7 @@ use {
         domain,
         event_updater::EventUpdater,
         infra::{self, blockchain::ChainId},
-        run_loop::RunLoop,
+        run_loop::{Rotation, RunLoop},
         shadow,
         solvable_orders::SolvableOrdersCache,
     },
@@ -100,6 +100,7 @@ pub async fn start(args: impl Iterator<Item = String>) {
     observe::tracing::initialize(
         args.shared.logging.log_filter.as_str(),
         args.shared.logging.log_stderr_threshold,
+        args.shared.logging.enable_task_console,
     );
     observe::panic_hook::install();
     tracing::info!("running autopilot with validated arguments:\n{}", args);
@@ -211,6 +212,11 @@ pub async fn run(args: Arguments) {
             vault: vault.as_ref().map(|contract| contract.address()),
         },
         eth.current_block().clone(),
+        args.balance_cache_ttl,
+        account_balances::ConcurrencyConfig {
+            parallelism: args.balance_fetch_parallelism,
+            chunk_size: args.balance_fetch_chunk_size,
+        },
     );
 
     let gas_price_estimator = Arc::new(
@@ -347,8 +353,13 @@ pub async fn run(args: Arguments) {
         None
     };
 
-    let persistence =
-        infra::persistence::Persistence::new(args.s3.into().unwrap(), Arc::new(db.clone())).await;
+    let local_export = args.s3.local_export();
+    let persistence = infra::persistence::Persistence::new(
+        args.s3.into().unwrap(),
+        local_export,
+        Arc::new(db.clone()),
+    )
+    .await;
     let on_settlement_event_updater =
         crate::on_settlement_event_updater::OnSettlementEventUpdater::new(
             eth.clone(),
@@ -468,6 +479,7 @@ pub async fn run(args: Arguments) {
         domain::ProtocolFees::new(
             &args.fee_policies,
             args.fee_policy_max_partner_fee,
+            args.pay_out_partner_fees,
             args.enable_multiple_fees,
         ),
         cow_amm_registry.clone(),
@@ -512,6 +524,8 @@ pub async fn run(args: Arguments) {
         submission_deadline: args.submission_deadline as u64,
         max_settlement_transaction_wait: args.max_settlement_transaction_wait,
         solve_deadline: args.solve_deadline,
+        batching_window: args.auction_batching_window,
+        rotation: std::sync::Mutex::new(Rotation::new(args.max_solvers_per_auction)),
         in_flight_orders: Default::default(),
         persistence: persistence.clone(),
         liveness: liveness.clone(),
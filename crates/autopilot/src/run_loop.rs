@@ -28,7 +28,7 @@ use {
         SolverCompetitionDB,
         SolverSettlement,
     },
-    primitive_types::H256,
+    primitive_types::{H256, U256},
     rand::seq::SliceRandom,
     shared::token_list::AutoUpdatingTokenList,
     std::{
@@ -50,6 +50,9 @@ pub struct RunLoop {
     pub submission_deadline: u64,
     pub max_settlement_transaction_wait: Duration,
     pub solve_deadline: Duration,
+    pub batching_window: Duration,
+    /// Selects the drivers participating in each auction.
+    pub rotation: std::sync::Mutex<Rotation>,
     pub in_flight_orders: Arc<Mutex<Option<InFlightOrders>>>,
     pub liveness: Arc<Liveness>,
 }
@@ -58,14 +61,17 @@ impl RunLoop {
     pub async fn run_forever(self) -> ! {
         let mut last_auction = None;
         let mut last_block = None;
+        let mut batch = Batch::new(self.batching_window);
         loop {
             if let Some(domain::AuctionWithId { id, auction }) = self.next_auction().await {
                 let current_block = self.eth.current_block().borrow().hash;
-                // Only run the solvers if the auction or block has changed.
-                let previous = last_auction.replace(auction.clone());
-                if previous.as_ref() != Some(&auction)
-                    || last_block.replace(current_block) != Some(current_block)
+                // Only run the solvers if the auction or block has changed and
+                // the changes were accumulated for long enough.
+                if (last_auction.as_ref() != Some(&auction) || last_block != Some(current_block))
+                    && batch.ready(Instant::now())
                 {
+                    let previous = last_auction.replace(auction.clone());
+                    last_block = Some(current_block);
                     observe::log_auction_delta(id, &previous, &auction);
                     self.liveness.auction();
 
@@ -166,6 +172,13 @@ impl RunLoop {
                 .iter()
                 .map(|participant| participant.solution.solver().into())
                 .collect::<HashSet<_>>();
+            let mut scores = BTreeMap::<_, U256>::new();
+            for participant in &solutions {
+                let score = scores
+                    .entry(participant.solution.solver().into())
+                    .or_default();
+                *score = (*score).max(participant.solution.score().get().0);
+            }
 
             let mut prices = BTreeMap::new();
             let mut fee_policies = Vec::new();
@@ -263,6 +276,7 @@ impl RunLoop {
                 winning_score,
                 reference_score,
                 participants,
+                scores,
                 prices: prices
                     .into_iter()
                     .map(|(key, value)| (key.into(), value.get().into()))
@@ -330,7 +344,8 @@ impl RunLoop {
         }
     }
 
-    /// Runs the solver competition, making all configured drivers participate.
+    /// Runs the solver competition, making the drivers whose turn it is
+    /// participate.
     async fn competition(
         &self,
         id: domain::auction::Id,
@@ -348,8 +363,9 @@ impl RunLoop {
         self.persistence
             .store_order_events(order_uids, OrderEventLabel::Ready);
 
+        let drivers = self.rotation.lock().unwrap().next(&self.drivers);
         let start = Instant::now();
-        futures::future::join_all(self.drivers.iter().map(|driver| async move {
+        futures::future::join_all(drivers.into_iter().map(|driver| async move {
             let result = self.solve(driver, request).await;
             let solutions = match result {
                 Ok(solutions) => {
@@ -574,6 +590,61 @@ impl RunLoop {
     }
 }
 
+/// Accumulates changes to the solvable orders for a configurable window
+/// before a new auction gets cut, trading latency for bigger batches.
+struct Batch {
+    window: Duration,
+    /// When the first change not yet included in an auction was observed.
+    opened: Option<Instant>,
+}
+
+impl Batch {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            opened: None,
+        }
+    }
+
+    /// Registers a change at `now` and returns whether the window of the
+    /// current batch has elapsed, in which case the next change opens a new
+    /// batch.
+    fn ready(&mut self, now: Instant) -> bool {
+        let opened = *self.opened.get_or_insert(now);
+        let ready = now.duration_since(opened) >= self.window;
+        if ready {
+            self.opened = None;
+        }
+        ready
+    }
+}
+
+/// Bounds the number of drivers participating in an auction. Drivers take turns
+/// in a round-robin fashion so that every one of them gets to participate
+/// equally often across auctions.
+pub struct Rotation {
+    max: Option<usize>,
+    /// Index of the driver participating first in the next auction.
+    next: usize,
+}
+
+impl Rotation {
+    pub fn new(max: Option<usize>) -> Self {
+        Self { max, next: 0 }
+    }
+
+    /// Returns the drivers participating in the next auction.
+    fn next<'a, T>(&mut self, drivers: &'a [T]) -> Vec<&'a T> {
+        let count = match self.max {
+            Some(max) if max < drivers.len() => max,
+            _ => return drivers.iter().collect(),
+        };
+        let participants = drivers.iter().cycle().skip(self.next).take(count).collect();
+        self.next = (self.next + count) % drivers.len();
+        participants
+    }
+}
+
 /// Orders settled in the previous auction that might still be in-flight.
 #[derive(Default)]
 pub struct InFlightOrders {
@@ -783,3 +854,68 @@ pub mod observe {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_orders_arriving_within_window() {
+        let start = Instant::now();
+        let mut batch = Batch::new(Duration::from_secs(3));
+        // Seconds at which orders arrive, the run loop polls once per second.
+        let arrivals = [(0, "a"), (1, "b"), (2, "c"), (4, "d"), (6, "e")];
+
+        let mut pending = vec![];
+        let mut auctions = vec![];
+        for second in 0..10 {
+            pending.extend(
+                arrivals
+                    .iter()
+                    .filter(|(arrival, _)| *arrival == second)
+                    .map(|(_, order)| *order),
+            );
+            if !pending.is_empty() && batch.ready(start + Duration::from_secs(second)) {
+                auctions.push(std::mem::take(&mut pending));
+            }
+        }
+
+        assert_eq!(auctions, vec![vec!["a", "b", "c"], vec!["d", "e"]]);
+    }
+
+    #[test]
+    fn rotates_participating_drivers() {
+        let drivers = ["a", "b", "c", "d", "e"];
+        let mut rotation = Rotation::new(Some(2));
+        let rounds = (0..5)
+            .map(|_| rotation.next(&drivers).into_iter().copied().collect_vec())
+            .collect_vec();
+
+        // Exactly two drivers participate per auction and every driver gets to
+        // participate twice over five auctions.
+        assert_eq!(
+            rounds,
+            vec![
+                vec!["a", "b"],
+                vec!["c", "d"],
+                vec!["e", "a"],
+                vec!["b", "c"],
+                vec!["d", "e"],
+            ]
+        );
+
+        // Without a limit, or with one above the number of drivers, all of them
+        // participate.
+        for max in [None, Some(5), Some(6)] {
+            let mut rotation = Rotation::new(max);
+            assert_eq!(rotation.next(&drivers), drivers.iter().collect_vec());
+        }
+    }
+
+    #[test]
+    fn cuts_auctions_immediately_without_window() {
+        let mut batch = Batch::new(Duration::ZERO);
+        assert!(batch.ready(Instant::now()));
+        assert!(batch.ready(Instant::now()));
+    }
+}
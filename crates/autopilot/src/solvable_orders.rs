@@ -283,6 +283,7 @@ impl SolvableOrdersCache {
                 })
                 .collect::<Result<_, _>>()?,
             surplus_capturing_jit_order_owners,
+            created: chrono::Utc::now(),
         };
         *self.cache.lock().unwrap() = Inner {
             auction: Some(auction),
@@ -1,5 +1,5 @@
 use {
-    crate::{auction::AuctionId, OrderUid},
+    crate::{auction::AuctionId, Address, OrderUid},
     sqlx::{PgConnection, QueryBuilder},
     std::collections::HashMap,
 };
@@ -14,6 +14,7 @@ pub struct FeePolicy {
     pub volume_factor: Option<f64>,
     pub price_improvement_factor: Option<f64>,
     pub price_improvement_max_volume_factor: Option<f64>,
+    pub volume_fee_recipient: Option<Address>,
 }
 
 #[derive(Debug, Clone, PartialEq, sqlx::Type)]
@@ -36,7 +37,7 @@ pub async fn insert_batch(
     let mut query_builder = QueryBuilder::new(
         "INSERT INTO fee_policies (auction_id, order_uid, kind, surplus_factor, \
          surplus_max_volume_factor, volume_factor, price_improvement_factor, \
-         price_improvement_max_volume_factor)",
+         price_improvement_max_volume_factor, volume_fee_recipient)",
     );
 
     query_builder.push_values(fee_policies, |mut b, fee_policy| {
@@ -47,7 +48,8 @@ pub async fn insert_batch(
             .push_bind(fee_policy.surplus_max_volume_factor)
             .push_bind(fee_policy.volume_factor)
             .push_bind(fee_policy.price_improvement_factor)
-            .push_bind(fee_policy.price_improvement_max_volume_factor);
+            .push_bind(fee_policy.price_improvement_max_volume_factor)
+            .push_bind(fee_policy.volume_fee_recipient);
     });
 
     query_builder.build().execute(ex).await.map(|_| ())
@@ -122,6 +124,7 @@ mod tests {
             volume_factor: None,
             price_improvement_factor: None,
             price_improvement_max_volume_factor: None,
+            volume_fee_recipient: None,
         };
         // surplus fee policy with caps
         let fee_policy_2 = FeePolicy {
@@ -133,6 +136,7 @@ mod tests {
             volume_factor: None,
             price_improvement_factor: None,
             price_improvement_max_volume_factor: None,
+            volume_fee_recipient: None,
         };
         // volume based fee policy
         let fee_policy_3 = FeePolicy {
@@ -144,6 +148,7 @@ mod tests {
             volume_factor: Some(0.06),
             price_improvement_factor: None,
             price_improvement_max_volume_factor: None,
+            volume_fee_recipient: Some(ByteArray([3; 20])),
         };
         // price improvement fee policy
         let fee_policy_4 = FeePolicy {
@@ -155,6 +160,7 @@ mod tests {
             volume_factor: None,
             price_improvement_factor: Some(0.1),
             price_improvement_max_volume_factor: Some(0.99999),
+            volume_fee_recipient: None,
         };
 
         let fee_policies = vec![
@@ -18,6 +18,7 @@ pub mod settlement_observations;
 pub mod settlement_scores;
 pub mod settlements;
 pub mod solver_competition;
+pub mod solver_scores;
 pub mod surplus_capturing_jit_order_owners;
 pub mod trades;
 
@@ -66,6 +67,7 @@ pub const TABLES: &[&str] = &[
     "settlement_observations",
     "auction_prices",
     "auction_participants",
+    "solver_scores",
     "app_data",
 ];
 
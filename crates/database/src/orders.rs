@@ -716,6 +716,32 @@ pub async fn count_limit_orders_by_owner(
         .await
 }
 
+/// Counts the number of orders with the conditions of OPEN_ORDERS.
+pub async fn count_open_orders(
+    ex: &mut PgConnection,
+    min_valid_to: i64,
+) -> Result<i64, sqlx::Error> {
+    const QUERY: &str =
+        const_format::concatcp!("SELECT COUNT (*) FROM (", OPEN_ORDERS, " ) AS subquery");
+    sqlx::query_scalar(QUERY)
+        .bind(min_valid_to)
+        .fetch_one(ex)
+        .await
+}
+
+/// Orders which were created at or before `at` and, using the conditions from
+/// OPEN_ORDERS, are still open at `at`.
+pub fn open_orders_at(
+    ex: &mut PgConnection,
+    at: DateTime<Utc>,
+) -> BoxStream<'_, Result<FullOrder, sqlx::Error>> {
+    const QUERY: &str = const_format::concatcp!(OPEN_ORDERS, " AND creation_timestamp <= $2");
+    sqlx::query_as(QUERY)
+        .bind(at.timestamp())
+        .bind(at)
+        .fetch(ex)
+}
+
 #[derive(Debug, sqlx::FromRow)]
 pub struct OrderWithQuote {
     pub order_buy_amount: BigDecimal,
@@ -1535,6 +1561,37 @@ mod tests {
         assert!(get_order(&mut db, 2).await.is_none());
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_count_open_orders() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        assert_eq!(count_open_orders(&mut db, 0).await.unwrap(), 0);
+
+        for (i, valid_to) in [3, 5].into_iter().enumerate() {
+            let order = Order {
+                uid: ByteArray([i as u8; 56]),
+                kind: OrderKind::Sell,
+                sell_amount: 10.into(),
+                buy_amount: 100.into(),
+                valid_to,
+                ..Default::default()
+            };
+            insert_order(&mut db, &order).await.unwrap();
+        }
+        assert_eq!(count_open_orders(&mut db, 0).await.unwrap(), 2);
+        // expired orders don't count
+        assert_eq!(count_open_orders(&mut db, 4).await.unwrap(), 1);
+
+        // invalidated orders don't count
+        insert_onchain_invalidation(&mut db, &EventIndex::default(), &ByteArray([1; 56]))
+            .await
+            .unwrap();
+        assert_eq!(count_open_orders(&mut db, 4).await.unwrap(), 0);
+    }
+
     type Data = ([u8; 56], Address, DateTime<Utc>);
     async fn user_orders(
         ex: &mut PgConnection,
@@ -1891,4 +1948,37 @@ mod tests {
             .unwrap();
         assert_eq!(full_order.full_app_data, Some(full_app_data));
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_open_orders_at() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let at = Utc.timestamp_opt(1_000, 0).unwrap();
+        let order = |uid: u8, created: i64, valid_to: i64| Order {
+            uid: ByteArray([uid; 56]),
+            kind: OrderKind::Sell,
+            sell_amount: 10.into(),
+            buy_amount: 100.into(),
+            creation_timestamp: Utc.timestamp_opt(created, 0).unwrap(),
+            valid_to,
+            ..Default::default()
+        };
+        let open = order(1, 900, 2_000);
+        // Created after the snapshot time.
+        let later = order(2, 1_100, 2_000);
+        // Expired before the snapshot time.
+        let expired = order(3, 900, 999);
+        for order in [&open, &later, &expired] {
+            insert_order(&mut db, order).await.unwrap();
+        }
+
+        let orders: Vec<FullOrder> = open_orders_at(&mut db, at).try_collect().await.unwrap();
+        assert_eq!(
+            orders.iter().map(|order| order.uid).collect::<Vec<_>>(),
+            vec![open.uid]
+        );
+    }
 }
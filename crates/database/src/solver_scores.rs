@@ -0,0 +1,76 @@
+use {
+    crate::{auction::AuctionId, Address, PgTransaction},
+    bigdecimal::BigDecimal,
+    sqlx::PgConnection,
+    std::ops::DerefMut,
+};
+
+/// The score a solver achieved in the competition for a given auction.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct SolverScore {
+    pub auction_id: AuctionId,
+    pub solver: Address,
+    pub score: BigDecimal,
+}
+
+pub async fn insert(ex: &mut PgTransaction<'_>, scores: &[SolverScore]) -> Result<(), sqlx::Error> {
+    const QUERY: &str =
+        r#"INSERT INTO solver_scores (auction_id, solver, score) VALUES ($1, $2, $3);"#;
+    for score in scores {
+        sqlx::query(QUERY)
+            .bind(score.auction_id)
+            .bind(score.solver)
+            .bind(&score.score)
+            .execute(ex.deref_mut())
+            .await?;
+    }
+    Ok(())
+}
+
+pub async fn fetch(
+    ex: &mut PgConnection,
+    auction_id: AuctionId,
+) -> Result<Vec<SolverScore>, sqlx::Error> {
+    const QUERY: &str = r#"SELECT * FROM solver_scores WHERE auction_id = $1 ORDER BY solver"#;
+    sqlx::query_as(QUERY).bind(auction_id).fetch_all(ex).await
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::byte_array::ByteArray, sqlx::Connection};
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let input = vec![
+            SolverScore {
+                auction_id: 1,
+                solver: ByteArray([2; 20]),
+                score: 10.into(),
+            },
+            SolverScore {
+                auction_id: 1,
+                solver: ByteArray([3; 20]),
+                score: 9.into(),
+            },
+        ];
+        insert(&mut db, &input).await.unwrap();
+        insert(
+            &mut db,
+            &[SolverScore {
+                auction_id: 2,
+                solver: ByteArray([2; 20]),
+                score: 1.into(),
+            }],
+        )
+        .await
+        .unwrap();
+
+        let output = fetch(&mut db, 1).await.unwrap();
+        assert_eq!(input, output);
+    }
+}
@@ -42,6 +42,7 @@ pub fn to_domain(id: liquidity::Id, pool: StablePoolOrder) -> Result<liquidity::
             )?,
             fee: balancer::v2::Fee::from_raw(pool.fee.as_uint256()),
         }),
+        fetched_at: None,
     })
 }
 
@@ -46,6 +46,7 @@ pub fn to_domain(id: liquidity::Id, pool: WeightedProductOrder) -> Result<liquid
                 WeightedPoolVersion::V3Plus => balancer::v2::weighted::Version::V3Plus,
             },
         }),
+        fetched_at: None,
     })
 }
 
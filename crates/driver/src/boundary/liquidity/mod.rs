@@ -16,7 +16,7 @@ use {
     },
     solver::{
         liquidity::Liquidity,
-        liquidity_collector::{LiquidityCollecting, LiquidityCollector},
+        liquidity_collector::{LiquidityCollecting, LiquidityCollector, TimeoutLiquiditySource},
     },
     std::{
         collections::HashSet,
@@ -118,13 +118,29 @@ impl Fetcher {
                 .collect::<Vec<_>>(),
         );
 
+        let liquidity_sources = [
+            ("uniswap-v2", uni_v2),
+            ("swapr", swapr),
+            ("balancer-v2", bal_v2),
+            ("uniswap-v3", uni_v3),
+            ("zeroex", zeroex),
+        ]
+        .into_iter()
+        .flat_map(|(label, sources)| {
+            sources
+                .into_iter()
+                .map(move |source| match config.source_timeout {
+                    Some(timeout) => Box::new(TimeoutLiquiditySource::new(label, source, timeout))
+                        as Box<dyn LiquidityCollecting>,
+                    None => source,
+                })
+        })
+        .collect();
+
         Ok(Self {
             blocks: block_stream.clone(),
             inner: LiquidityCollector {
-                liquidity_sources: [uni_v2, swapr, bal_v2, uni_v3, zeroex]
-                    .into_iter()
-                    .flatten()
-                    .collect(),
+                liquidity_sources,
                 base_tokens: Arc::new(base_tokens),
             },
             swapr_routers,
@@ -145,11 +161,14 @@ impl Fetcher {
             })
             .collect();
 
-        let block = match block {
-            infra::liquidity::AtBlock::Recent => recent_block_cache::Block::Recent,
+        let (block, fetched_at) = match block {
+            infra::liquidity::AtBlock::Recent => (recent_block_cache::Block::Recent, None),
             infra::liquidity::AtBlock::Latest => {
                 let block_number = self.blocks.borrow().number;
-                recent_block_cache::Block::Number(block_number)
+                (
+                    recent_block_cache::Block::Number(block_number),
+                    Some(eth::BlockNo(block_number)),
+                )
             }
         };
         let liquidity = self.inner.get_liquidity(pairs, block).await?;
@@ -175,6 +194,10 @@ impl Fetcher {
                 // Ignore "bad" liquidity - this allows the driver to continue
                 // solving with the other good stuff.
                 .ok()
+                .map(|liquidity| liquidity::Liquidity {
+                    fetched_at,
+                    ..liquidity
+                })
             })
             .collect();
         Ok(liquidity)
@@ -35,6 +35,7 @@ pub fn to_domain(id: liquidity::Id, pool: ConstantProductOrder) -> Result<liquid
             base: boundary::liquidity::uniswap::v2::to_domain_pool(pool)?,
             fee,
         }),
+        fetched_at: None,
     })
 }
 
@@ -51,6 +52,7 @@ pub async fn collector(
             router: config.router,
             pool_code: config.pool_code,
             missing_pool_cache_time: config.missing_pool_cache_time,
+            max_concurrent_fetches: None,
         },
         |web3, pair_provider| SwaprPoolReader(DefaultPoolReader::new(web3, pair_provider)),
     )
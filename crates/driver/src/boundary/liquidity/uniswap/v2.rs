@@ -43,6 +43,7 @@ pub fn to_domain(id: liquidity::Id, pool: ConstantProductOrder) -> Result<liquid
         id,
         gas: GAS_PER_SWAP.into(),
         kind: liquidity::Kind::UniswapV2(to_domain_pool(pool)?),
+        fetched_at: None,
     })
 }
 
@@ -143,7 +144,8 @@ where
             reader(web3.clone(), pair_provider),
             web3.clone(),
             config.missing_pool_cache_time,
-        );
+        )
+        .with_max_concurrent_reads(config.max_concurrent_fetches);
 
         Arc::new(PoolCache::new(
             boundary::liquidity::cache_config(),
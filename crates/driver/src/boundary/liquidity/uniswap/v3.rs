@@ -69,6 +69,7 @@ pub fn to_domain(id: liquidity::Id, pool: ConcentratedLiquidity) -> Result<liqui
                 .collect::<Result<BTreeMap<_, _>>>()?,
             fee: Fee(pool.pool.state.fee),
         }),
+        fetched_at: None,
     })
 }
 
@@ -74,6 +74,7 @@ pub fn to_domain(
         id,
         gas: GAS_PER_ZEROEX_ORDER.into(),
         kind: liquidity::Kind::ZeroEx(domain),
+        fetched_at: None,
     })
 }
 
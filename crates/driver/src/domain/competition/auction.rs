@@ -23,7 +23,7 @@ use {
 /// An auction is a set of orders that can be solved. The solvers calculate
 /// [`super::solution::Solution`]s by picking subsets of these orders and
 /// solving them.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Auction {
     /// See the [`Self::id`] method.
     id: Option<Id>,
@@ -33,6 +33,8 @@ pub struct Auction {
     tokens: Tokens,
     gas_price: eth::GasPrice,
     deadline: time::Deadline,
+    /// See the [`Self::created`] method.
+    created: chrono::DateTime<chrono::Utc>,
     surplus_capturing_jit_order_owners: HashSet<eth::Address>,
 }
 
@@ -42,18 +44,16 @@ impl Auction {
         orders: Vec<competition::Order>,
         tokens: impl Iterator<Item = Token>,
         deadline: time::Deadline,
+        created: chrono::DateTime<chrono::Utc>,
         eth: &Ethereum,
         surplus_capturing_jit_order_owners: HashSet<eth::Address>,
     ) -> Result<Self, Error> {
         let tokens = Tokens(tokens.map(|token| (token.address, token)).collect());
 
         // Ensure that tokens are included for each order.
-        let weth = eth.contracts().weth_address();
-        if !orders.iter().all(|order| {
-            tokens.0.contains_key(&order.buy.token.wrap(weth))
-                && tokens.0.contains_key(&order.sell.token)
-        }) {
-            return Err(Error::InvalidTokens);
+        let missing = missing_tokens(&orders, &tokens, eth.contracts().weth_address());
+        if !missing.is_empty() {
+            return Err(Error::InvalidTokens(missing));
         }
 
         // Ensure that there are no orders with 0 amounts.
@@ -67,6 +67,7 @@ impl Auction {
             tokens,
             gas_price: eth.gas_price().await?,
             deadline,
+            created,
             surplus_capturing_jit_order_owners,
         })
     }
@@ -110,6 +111,11 @@ impl Auction {
         self.deadline
     }
 
+    /// When the data of this auction was snapshotted.
+    pub fn created(&self) -> chrono::DateTime<chrono::Utc> {
+        self.created
+    }
+
     pub fn prices(&self) -> Prices {
         self.tokens
             .0
@@ -125,6 +131,55 @@ impl Auction {
     pub fn surplus_capturing_jit_order_owners(&self) -> &HashSet<eth::Address> {
         &self.surplus_capturing_jit_order_owners
     }
+
+    /// Fills in the decimals of tokens for which they are unknown, adding
+    /// tokens which are not part of the auction yet.
+    pub fn with_decimals(mut self, decimals: &HashMap<eth::TokenAddress, u8>) -> Self {
+        self.tokens.fill_decimals(decimals);
+        self
+    }
+
+    /// Removes the orders buying or selling any of the denied tokens. Returns
+    /// the remaining auction together with the removed orders.
+    pub fn without_tokens(
+        self,
+        denied: &HashSet<eth::TokenAddress>,
+    ) -> (Self, Vec<competition::Order>) {
+        let (denied, orders) = self.orders.into_iter().partition(|order| {
+            denied.contains(&order.sell.token) || denied.contains(&order.buy.token)
+        });
+        (Self { orders, ..self }, denied)
+    }
+
+    /// Removes the orders whose limit price asks for more than `max_deviation`
+    /// times the value of the sold tokens according to the reference prices
+    /// (e.g. 1000 for selling at 1000x market), as they are practically never
+    /// fillable. Orders trading tokens without a reference price are kept.
+    /// Returns the remaining auction together with the removed orders.
+    pub fn without_off_market_orders(self, max_deviation: f64) -> (Self, Vec<competition::Order>) {
+        let (off_market, orders) = self.orders.into_iter().partition(|order| {
+            let (Some(sell_price), Some(buy_price)) = (
+                self.tokens.get(order.sell.token).price,
+                self.tokens.get(order.buy.token).price,
+            ) else {
+                return false;
+            };
+            let sell = sell_price.in_eth(order.sell.amount).0.to_f64_lossy();
+            let buy = buy_price.in_eth(order.buy.amount).0.to_f64_lossy();
+            buy > sell * max_deviation
+        });
+        (Self { orders, ..self }, off_market)
+    }
+
+    /// Removes the orders which are no longer valid at the given time. Returns
+    /// the remaining auction together with the removed orders.
+    pub fn without_expired_orders(self, now: util::Timestamp) -> (Self, Vec<competition::Order>) {
+        let (expired, orders) = self
+            .orders
+            .into_iter()
+            .partition(|order| order.is_expired(now));
+        (Self { orders, ..self }, expired)
+    }
 }
 
 #[derive(Clone)]
@@ -177,6 +232,8 @@ impl AuctionProcessor {
             let start = std::time::Instant::now();
             orders.extend(rt.block_on(Self::cow_amm_orders(&eth, &tokens, &cow_amms)));
             Self::sort(&mut orders, &tokens);
+            let pre_signed = rt.block_on(Self::fetch_pre_signatures(&eth, &orders));
+            Self::filter_missing_pre_signatures(&pre_signed, &mut orders);
             let mut balances =
                 rt.block_on(async { Self::fetch_balances(&eth, &orders).await });
             Self::filter_orders(&mut balances, &mut orders);
@@ -221,6 +278,12 @@ impl AuctionProcessor {
     }
 
     /// Removes orders that cannot be filled due to missing funds of the owner.
+    ///
+    /// Partially fillable orders are scaled down to the balance of the owner
+    /// instead. This is how orders selling the owner's entire balance are
+    /// expressed: as partially fillable orders with a sell amount exceeding
+    /// any balance, e.g. [`eth::U256::MAX`], which resolve to the current
+    /// balance when the auction is solved.
     fn filter_orders(balances: &mut Balances, orders: &mut Vec<order::Order>) {
         // The auction that we receive from the `autopilot` assumes that there
         // is sufficient balance to completely cover all the orders. **This is
@@ -290,6 +353,55 @@ impl AuctionProcessor {
         });
     }
 
+    /// Removes orders using the `PreSign` scheme whose pre-signature isn't
+    /// registered in the settlement contract, as they can't be settled.
+    fn filter_missing_pre_signatures(
+        pre_signed: &HashSet<order::Uid>,
+        orders: &mut Vec<order::Order>,
+    ) {
+        orders.retain(|order| {
+            let missing = matches!(order.signature.scheme, order::signature::Scheme::PreSign)
+                && !pre_signed.contains(&order.uid);
+            if missing {
+                observe::order_excluded_from_auction(
+                    order,
+                    observe::OrderExcludedFromAuctionReason::MissingPreSignature,
+                );
+            }
+            !missing
+        });
+    }
+
+    /// Fetches which of the `PreSign` orders have their pre-signature
+    /// registered in the settlement contract. Orders whose registration can't
+    /// be fetched are treated as not registered.
+    async fn fetch_pre_signatures(
+        ethereum: &infra::Ethereum,
+        orders: &[order::Order],
+    ) -> HashSet<order::Uid> {
+        let ethereum = ethereum.with_metric_label("preSignatures".into());
+        let settlement = ethereum.contracts().settlement();
+        join_all(
+            orders
+                .iter()
+                .filter(|order| matches!(order.signature.scheme, order::signature::Scheme::PreSign))
+                .map(|order| async move {
+                    let uid = ethcontract::Bytes(order.uid.0 .0.to_vec());
+                    match settlement.pre_signature(uid).call().await {
+                        Ok(signed) => (!signed.is_zero()).then_some(order.uid),
+                        Err(err) => {
+                            tracing::warn!(uid = ?order.uid, ?err, "failed to fetch pre-signature");
+                            None
+                        }
+                    }
+                }),
+        )
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
     /// Fetches the tradable balance for every order owner.
     async fn fetch_balances(ethereum: &infra::Ethereum, orders: &[order::Order]) -> Balances {
         let ethereum = ethereum.with_metric_label("orderBalances".into());
@@ -395,7 +507,7 @@ impl AuctionProcessor {
                         amount: template.order.sell_amount.into(),
                         token: template.order.sell_token.into(),
                     },
-                    kind: order::Kind::Limit,
+                    kind: order::Kind::Limit { oracle: None },
                     side: template.order.kind.into(),
                     app_data: order::AppData(Bytes(template.order.app_data.0)),
                     buy_token_balance: template.order.buy_token_balance.into(),
@@ -460,6 +572,22 @@ impl AuctionProcessor {
     }
 }
 
+/// Returns the sorted, deduplicated list of sell and buy tokens of the orders
+/// that are missing from the auction tokens.
+fn missing_tokens(
+    orders: &[competition::Order],
+    tokens: &Tokens,
+    weth: eth::WethAddress,
+) -> Vec<eth::TokenAddress> {
+    orders
+        .iter()
+        .flat_map(|order| [order.sell.token, order.buy.token.wrap(weth)])
+        .filter(|token| !tokens.0.contains_key(token))
+        .sorted()
+        .dedup()
+        .collect()
+}
+
 /// The tokens that are used in an auction.
 #[derive(Debug, Default, Clone)]
 pub struct Tokens(HashMap<eth::TokenAddress, Token>);
@@ -479,6 +607,30 @@ impl Tokens {
     pub fn iter(&self) -> impl Iterator<Item = &Token> {
         self.0.values()
     }
+
+    /// Returns the sorted, deduplicated list of the given tokens whose decimals
+    /// are unknown.
+    pub fn missing_decimals(
+        &self,
+        tokens: impl IntoIterator<Item = eth::TokenAddress>,
+    ) -> Vec<eth::TokenAddress> {
+        tokens
+            .into_iter()
+            .filter(|token| self.get(*token).decimals.is_none())
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
+    fn fill_decimals(&mut self, decimals: &HashMap<eth::TokenAddress, u8>) {
+        for (address, decimals) in decimals {
+            let token = self
+                .0
+                .entry(*address)
+                .or_insert_with(|| Self::default().get(*address));
+            token.decimals.get_or_insert(*decimals);
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -598,10 +750,255 @@ pub struct InvalidPrice;
 
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("invalid auction tokens")]
-    InvalidTokens,
+    /// The sell or buy tokens of some orders are missing from the auction.
+    /// Only order tokens are checked: the tokens of liquidity pools aren't
+    /// part of the auction, they are fetched after it has been validated.
+    #[error("auction orders reference tokens missing from the auction: {0:?}")]
+    InvalidTokens(Vec<eth::TokenAddress>),
     #[error("invalid order amounts")]
     InvalidAmounts,
     #[error("blockchain error: {0:?}")]
     Blockchain(#[from] blockchain::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(address: u64) -> eth::TokenAddress {
+        eth::H160::from_low_u64_be(address).into()
+    }
+
+    fn order(sell: u64, buy: u64) -> competition::Order {
+        competition::Order {
+            uid: Default::default(),
+            receiver: None,
+            valid_to: util::Timestamp(u32::MAX),
+            buy: eth::Asset {
+                token: token(buy),
+                amount: eth::U256::from(100).into(),
+            },
+            sell: eth::Asset {
+                token: token(sell),
+                amount: eth::U256::from(100).into(),
+            },
+            side: order::Side::Sell,
+            kind: order::Kind::Market,
+            app_data: Default::default(),
+            partial: order::Partial::No,
+            pre_interactions: Default::default(),
+            post_interactions: Default::default(),
+            sell_token_balance: order::SellTokenBalance::Erc20,
+            buy_token_balance: order::BuyTokenBalance::Erc20,
+            signature: order::Signature {
+                scheme: order::signature::Scheme::PreSign,
+                data: Default::default(),
+                signer: Default::default(),
+            },
+            protocol_fees: Default::default(),
+        }
+    }
+
+    #[test]
+    fn orders_referencing_absent_tokens_are_rejected() {
+        let tokens = Tokens(
+            [token(1), token(2)]
+                .into_iter()
+                .map(|address| {
+                    (
+                        address,
+                        Token {
+                            decimals: Some(18),
+                            symbol: None,
+                            address,
+                            price: None,
+                            available_balance: Default::default(),
+                            trusted: false,
+                        },
+                    )
+                })
+                .collect(),
+        );
+        let weth = eth::WethAddress(token(0xeeee));
+
+        assert!(missing_tokens(&[order(1, 2), order(2, 1)], &tokens, weth).is_empty());
+        assert_eq!(
+            missing_tokens(&[order(1, 3), order(3, 2), order(4, 1)], &tokens, weth),
+            vec![token(3), token(4)],
+        );
+    }
+
+    #[test]
+    fn orders_without_registered_pre_signature_are_excluded() {
+        let uid = |byte| order::Uid([byte; order::UID_LEN].into());
+        let registered = competition::Order {
+            uid: uid(1),
+            ..order(1, 2)
+        };
+        let unregistered = competition::Order {
+            uid: uid(2),
+            ..order(1, 2)
+        };
+        let eip712 = competition::Order {
+            uid: uid(3),
+            signature: order::Signature {
+                scheme: order::signature::Scheme::Eip712,
+                data: Default::default(),
+                signer: Default::default(),
+            },
+            ..order(1, 2)
+        };
+
+        let mut orders = vec![registered, unregistered, eip712];
+        AuctionProcessor::filter_missing_pre_signatures(&HashSet::from([uid(1)]), &mut orders);
+
+        assert_eq!(
+            orders.iter().map(|order| order.uid).collect::<Vec<_>>(),
+            vec![uid(1), uid(3)]
+        );
+    }
+
+    #[test]
+    fn sell_max_orders_resolve_to_the_current_balance() {
+        let sell_max = competition::Order {
+            sell: eth::Asset {
+                token: token(1),
+                amount: eth::U256::MAX.into(),
+            },
+            buy: eth::Asset {
+                token: token(2),
+                amount: eth::U256::from(1).into(),
+            },
+            partial: order::Partial::Yes {
+                available: eth::U256::MAX.into(),
+            },
+            ..order(1, 2)
+        };
+        let mut balances = Balances::from([(
+            (
+                sell_max.trader(),
+                sell_max.sell.token,
+                sell_max.sell_token_balance,
+            ),
+            order::SellAmount(500.into()),
+        )]);
+
+        let mut orders = vec![sell_max];
+        AuctionProcessor::filter_orders(&mut balances, &mut orders);
+
+        assert_eq!(
+            orders[0].available().sell.amount,
+            eth::U256::from(500).into()
+        );
+    }
+
+    #[test]
+    fn off_market_orders_are_excluded() {
+        let price = |wei: u64| Token {
+            decimals: Some(18),
+            symbol: None,
+            address: Default::default(),
+            price: Some(Price::new(eth::U256::from(wei).into()).unwrap()),
+            available_balance: Default::default(),
+            trusted: false,
+        };
+        let uid = |byte| order::Uid([byte; order::UID_LEN].into());
+        let asking = |byte, buy: u64| competition::Order {
+            uid: uid(byte),
+            buy: eth::Asset {
+                token: token(2),
+                amount: eth::U256::from(buy).into(),
+            },
+            ..order(1, 2)
+        };
+        // Token 1 is worth twice as much as token 2.
+        let auction = Auction {
+            id: None,
+            orders: vec![asking(1, 190), asking(2, 200_000)],
+            tokens: Tokens(HashMap::from([
+                (token(1), price(2_000)),
+                (token(2), price(1_000)),
+            ])),
+            gas_price: eth::GasPrice::new(
+                eth::U256::one().into(),
+                eth::U256::one().into(),
+                eth::U256::one().into(),
+            ),
+            deadline: Default::default(),
+            surplus_capturing_jit_order_owners: Default::default(),
+            created: Default::default(),
+        };
+
+        // The second order asks for 1000x the value of its sell tokens.
+        let (remaining, off_market) = auction.without_off_market_orders(100.);
+        assert_eq!(
+            remaining
+                .orders()
+                .iter()
+                .map(|order| order.uid)
+                .collect::<Vec<_>>(),
+            vec![uid(1)]
+        );
+        assert_eq!(
+            off_market.iter().map(|order| order.uid).collect::<Vec<_>>(),
+            vec![uid(2)]
+        );
+    }
+
+    #[test]
+    fn expired_orders_are_excluded_and_expiring_ones_flagged() {
+        let now = util::Timestamp(1_000);
+        let grace = std::time::Duration::from_secs(60);
+        let valid_to = |valid_to| competition::Order {
+            valid_to: util::Timestamp(valid_to),
+            ..order(1, 2)
+        };
+
+        // Within the grace window: still solved, but flagged.
+        let expiring = valid_to(1_030);
+        assert!(!expiring.is_expired(now));
+        assert!(expiring.expires_within(now, grace));
+
+        // Past expiry: excluded.
+        let expired = valid_to(1_000);
+        assert!(expired.is_expired(now));
+        assert!(!expired.expires_within(now, grace));
+
+        // Outside the grace window: solved as usual.
+        let valid = valid_to(2_000);
+        assert!(!valid.is_expired(now));
+        assert!(!valid.expires_within(now, grace));
+    }
+
+    #[test]
+    fn fills_in_missing_decimals() {
+        let usdc = token(2);
+        let mut tokens = Tokens(
+            [(token(1), Some(18)), (usdc, None)]
+                .into_iter()
+                .map(|(address, decimals)| {
+                    (
+                        address,
+                        Token {
+                            decimals,
+                            ..Tokens::default().get(address)
+                        },
+                    )
+                })
+                .collect(),
+        );
+
+        let missing = tokens.missing_decimals([token(1), usdc, token(3), usdc]);
+        assert_eq!(missing, vec![usdc, token(3)]);
+
+        // Decimals as fetched from the token contracts.
+        tokens.fill_decimals(&HashMap::from([(token(1), 9), (usdc, 6), (token(3), 8)]));
+        // Known decimals are never overwritten.
+        assert_eq!(tokens.get(token(1)).decimals, Some(18));
+        assert_eq!(tokens.get(usdc).decimals, Some(6));
+        assert_eq!(tokens.get(token(3)).decimals, Some(8));
+        assert!(tokens
+            .missing_decimals([token(1), usdc, token(3)])
+            .is_empty());
+    }
+}
@@ -5,7 +5,7 @@ use {
         Mempools,
     },
     crate::{
-        domain::{competition::solution::Settlement, eth},
+        domain::{competition::solution::Settlement, eth, liquidity},
         infra::{
             self,
             blockchain::Ethereum,
@@ -15,20 +15,24 @@ use {
             solver::{self, SolutionMerging, Solver},
             Simulator,
         },
-        util::Bytes,
+        util::{self, Bytes},
     },
     futures::{stream::FuturesUnordered, StreamExt},
     itertools::Itertools,
     std::{
         cmp::Reverse,
-        collections::{HashMap, HashSet},
-        sync::Mutex,
+        collections::{BTreeMap, HashMap, HashSet},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Mutex,
+        },
     },
     tap::TapFallible,
 };
 
 pub mod auction;
 pub mod order;
+pub mod replay;
 pub mod solution;
 
 pub use {
@@ -47,25 +51,133 @@ pub struct Competition {
     pub solver: Solver,
     pub eth: Ethereum,
     pub liquidity: infra::liquidity::Fetcher,
+    pub tokens: infra::tokens::Fetcher,
     pub simulator: Simulator,
     pub mempools: Mempools,
     pub settlement: Mutex<Option<Settlement>>,
+    /// The most recent auction passed to [`Competition::solve`].
+    pub auction: Mutex<Option<Summary>>,
+    /// Report of the most recent call to [`Competition::solve`].
+    pub report: Mutex<Option<SolveReport>>,
+    /// Whether the solver is paused, see [`Competition::pause`].
+    pub paused: AtomicBool,
 }
 
 impl Competition {
     /// Solve an auction as part of this competition.
     pub async fn solve(&self, auction: &Auction) -> Result<Option<Solved>, Error> {
-        let liquidity = match self.solver.liquidity() {
+        if self.is_paused() {
+            return Err(Error::SolverPaused);
+        }
+        if is_too_old(
+            auction.created(),
+            infra::time::now(),
+            self.solver.max_auction_age(),
+        ) {
+            return Err(Error::AuctionTooOld);
+        }
+        let started = std::time::Instant::now();
+        let mut phases = Stopwatch::new(std::time::Instant::now);
+
+        // Drop the orders trading tokens the solver must not touch.
+        let allowed;
+        let mut skipped_orders = BTreeMap::new();
+        let auction = if self.solver.denied_tokens().is_empty() {
+            auction
+        } else {
+            let (remaining, denied) = auction.clone().without_tokens(self.solver.denied_tokens());
+            for order in &denied {
+                observe::order_denied(self.solver.name(), order.uid);
+            }
+            if !denied.is_empty() {
+                skipped_orders.insert("TokenDenied", denied.len());
+            }
+            allowed = remaining;
+            &allowed
+        };
+
+        // Drop the orders which already expired, they can't be settled anymore.
+        let (valid, expired) = auction.clone().without_expired_orders(self.now());
+        for order in &expired {
+            observe::order_expired(self.solver.name(), &order.uid);
+        }
+        if !expired.is_empty() {
+            skipped_orders.insert("Expired", expired.len());
+        }
+        let auction = &valid;
+
+        // Drop the orders whose limit prices are too far off the market to
+        // ever be filled.
+        let on_market;
+        let auction = match self.solver.max_limit_price_deviation() {
+            Some(max_deviation) => {
+                let (remaining, off_market) =
+                    auction.clone().without_off_market_orders(max_deviation);
+                for order in &off_market {
+                    observe::order_off_market(self.solver.name(), order.uid);
+                }
+                if !off_market.is_empty() {
+                    skipped_orders.insert("OffMarket", off_market.len());
+                }
+                on_market = remaining;
+                &on_market
+            }
+            None => auction,
+        };
+
+        *self.auction.lock().unwrap() = Some(Summary {
+            id: auction.id(),
+            orders: auction.orders().len(),
+            deadline: auction.deadline().driver(),
+        });
+
+        let mut liquidity = match self.solver.liquidity() {
             solver::Liquidity::Fetch => {
-                self.liquidity
-                    .fetch(
+                let (liquidity, coverage) = self
+                    .liquidity
+                    .fetch_with_coverage(
                         &auction.liquidity_pairs(),
                         infra::liquidity::AtBlock::Latest,
                     )
-                    .await
+                    .await;
+                observe::liquidity_coverage(self.solver.name(), &coverage);
+                liquidity
             }
             solver::Liquidity::Skip => Default::default(),
         };
+        liquidity.retain(|liquidity| {
+            !liquidity
+                .tokens()
+                .iter()
+                .any(|token| self.solver.denied_tokens().contains(token))
+        });
+        phases.lap("liquidity");
+
+        // Solvers need to know the decimals of every token to scale amounts
+        // correctly, so fetch the ones which are unknown instead of letting
+        // solvers guess them.
+        let missing = auction.tokens().missing_decimals(
+            auction
+                .tokens()
+                .iter()
+                .map(|token| token.address)
+                .chain(liquidity.iter().flat_map(liquidity::Liquidity::tokens)),
+        );
+        let completed;
+        let auction = if missing.is_empty() {
+            auction
+        } else {
+            let decimals = self
+                .tokens
+                .get(&missing)
+                .await
+                .into_iter()
+                .filter_map(|(token, metadata)| Some((token, metadata.decimals?)))
+                .collect();
+            completed = auction.clone().with_decimals(&decimals);
+            &completed
+        };
+        phases.lap("tokens");
 
         // Fetch the solutions from the solver.
         let solutions = self
@@ -77,8 +189,14 @@ impl Competition {
                     notify::solver_timeout(&self.solver, auction.id());
                 }
             })?;
+        phases.lap("solver");
 
         observe::postprocessing(&solutions, auction.deadline().driver());
+        let found = solutions.len();
+
+        // Count the discarded solutions by reason for the solve report.
+        let skipped = Mutex::new(BTreeMap::<&'static str, usize>::new());
+        let skip = |reason, count| *skipped.lock().unwrap().entry(reason).or_default() += count;
 
         // Discard solutions that don't have unique ID.
         let mut ids = HashSet::new();
@@ -86,6 +204,7 @@ impl Competition {
             if !ids.insert(solution.id().clone()) {
                 observe::duplicated_solution_id(self.solver.name(), solution.id());
                 notify::duplicated_solution_id(&self.solver, auction.id(), solution.id());
+                skip("DuplicateId", 1);
                 false
             } else {
                 true
@@ -97,16 +216,30 @@ impl Competition {
             if solution.is_empty(auction.surplus_capturing_jit_order_owners()) {
                 observe::empty_solution(self.solver.name(), solution.id());
                 notify::empty_solution(&self.solver, auction.id(), solution.id().clone());
+                skip("EmptySolution", 1);
                 false
             } else {
                 true
             }
         });
 
-        let all_solutions = match self.solver.solution_merging() {
-            SolutionMerging::Allowed => merge(solutions, auction),
-            SolutionMerging::Forbidden => solutions.collect(),
+        // Discard solutions settling more JIT orders than the solver may.
+        let solutions = solutions.filter(|solution| {
+            if solution.exceeds_max_jit_orders() {
+                observe::too_many_jit_orders(self.solver.name(), solution.id());
+                skip("TooManyJitOrders", 1);
+                false
+            } else {
+                true
+            }
+        });
+
+        let (all_solutions, merges) = match self.solver.solution_merging() {
+            SolutionMerging::Allowed => merge(solutions, auction, &self.solver),
+            SolutionMerging::Forbidden => (solutions.collect(), 0),
         };
+        let to_encode = all_solutions.len();
+        phases.lap("merge");
 
         // Encode solutions into settlements (streamed).
         let encoded = all_solutions
@@ -130,6 +263,7 @@ impl Competition {
                     .tap_err(|err| {
                         observe::encoding_failed(self.solver.name(), &id, err);
                         notify::encoding_failed(&self.solver, auction.id(), &id, err);
+                        skip("SettlementEncoding", 1);
                     })
                     .ok()
             });
@@ -151,8 +285,19 @@ impl Competition {
         .is_err()
         {
             observe::postprocessing_timed_out(&settlements);
-            notify::postprocessing_timed_out(&self.solver, auction.id())
+            notify::postprocessing_timed_out(&self.solver, auction.id());
+            let failed = skipped
+                .lock()
+                .unwrap()
+                .get("SettlementEncoding")
+                .copied()
+                .unwrap_or_default();
+            skip(
+                "PostprocessingTimeout",
+                to_encode.saturating_sub(settlements.len() + failed),
+            );
         }
+        phases.lap("encode");
 
         // Score the settlements.
         let scores = settlements
@@ -182,6 +327,7 @@ impl Competition {
                             settlement.solution(),
                             err,
                         );
+                        skip("Scoring", 1);
                     })
                     .ok()
                     .map(|score| (score, settlement))
@@ -191,12 +337,50 @@ impl Competition {
         // Observe the scores.
         for (score, settlement) in scores.iter() {
             observe::score(settlement, score);
+            if let Some(profitability) = settlement.profitability(*score) {
+                observe::profitability(self.solver.name(), settlement, &profitability);
+            }
+        }
+
+        // Discard settlements which don't generate enough surplus per unit of gas.
+        let scores = efficient(
+            scores,
+            |settlement| settlement.gas.estimate,
+            self.solver.min_surplus_per_gas(),
+            |score, settlement| {
+                observe::inefficient_settlement(self.solver.name(), settlement, score);
+                skip("InsufficientSurplusPerGas", 1);
+            },
+        );
+
+        // Shadow solvers are scored like any other solver, but their settlements
+        // never compete for winning the auction.
+        if self.solver.shadow() {
+            for (score, settlement) in scores.iter() {
+                observe::shadow_settlement(self.solver.name(), settlement, score);
+            }
+            skip("Shadow", scores.len());
+            phases.lap("score");
+            observe::solve_phases(self.solver.name(), auction.id(), &phases.into_laps());
+            let report = SolveReport {
+                auction_id: auction.id(),
+                orders: auction.orders().len(),
+                solved_orders: 0,
+                solutions: found,
+                skipped_orders,
+                skipped_solutions: skipped.into_inner().unwrap(),
+                merges,
+                winner_score: None,
+                duration: started.elapsed(),
+            };
+            observe::solve_report(self.solver.name(), &report);
+            *self.report.lock().unwrap() = Some(report);
+            return Ok(None);
         }
 
         // Pick the best-scoring settlement.
-        let (mut score, settlement) = scores
-            .into_iter()
-            .max_by_key(|(score, _)| score.to_owned())
+        let (score, settlement) = best(&scores, |score, settlement| Score::new(*score, settlement))
+            .cloned()
             .map(|(score, settlement)| {
                 (
                     Solved {
@@ -210,8 +394,89 @@ impl Competition {
             })
             .unzip();
 
+        // Keep the settlement of a previous auction which is still pending if it
+        // scores better than the new one, depending on the solver's
+        // configuration. It only competes in this auction if all orders it
+        // settles are still part of it, in which case it gets encoded and
+        // simulated again for this auction and scored at its prices.
+        let pending = match self.pending_settlement().filter(|pending| {
+            settles_only(
+                pending.orders().into_keys(),
+                auction.orders().iter().map(|order| order.uid),
+            )
+        }) {
+            Some(pending) => pending
+                .for_auction(auction, &self.eth, &self.simulator)
+                .await
+                .ok()
+                .and_then(|pending| {
+                    let score = pending
+                        .score(
+                            &auction.prices(),
+                            auction.surplus_capturing_jit_order_owners(),
+                        )
+                        .ok()?;
+                    Some((score, pending))
+                }),
+            None => None,
+        };
+        let (mut score, settlement, pending) = match pending {
+            Some((pending_score, pending))
+                if keeps_pending(
+                    self.solver.pending_settlement(),
+                    score.as_ref().map(|solved| solved.score),
+                    pending_score,
+                ) =>
+            {
+                observe::kept_pending_settlement(self.solver.name(), &pending, &pending_score);
+                let solved = Solved {
+                    score: pending_score,
+                    trades: pending.orders(),
+                    prices: pending.prices(),
+                    gas: Some(pending.gas.estimate),
+                };
+                (
+                    Some(solved),
+                    Some(pending.clone()),
+                    Some((pending_score, pending)),
+                )
+            }
+            _ => (score, settlement, None),
+        };
+        phases.lap("score");
+        observe::solve_phases(self.solver.name(), auction.id(), &phases.into_laps());
+
+        self.solver.report_results(&Results {
+            auction_id: auction.id(),
+            participants: scores
+                .iter()
+                .chain(pending.as_ref())
+                .map(|(score, settlement)| (settlement.solution().clone(), *score))
+                .collect(),
+            winner: settlement
+                .as_ref()
+                .map(|settlement| settlement.solution().clone()),
+        });
+
         self.settlement.lock().unwrap().clone_from(&settlement);
 
+        let report = SolveReport {
+            auction_id: auction.id(),
+            orders: auction.orders().len(),
+            solved_orders: score
+                .as_ref()
+                .map(|solved| solved.trades.len())
+                .unwrap_or_default(),
+            solutions: found,
+            skipped_orders,
+            skipped_solutions: skipped.into_inner().unwrap(),
+            merges,
+            winner_score: score.as_ref().map(|solved| solved.score),
+            duration: started.elapsed(),
+        };
+        observe::solve_report(self.solver.name(), &report);
+        *self.report.lock().unwrap() = Some(report);
+
         let settlement = match settlement {
             Some(settlement) => settlement,
             // Don't wait for the deadline because we can't produce a solution anyway.
@@ -250,6 +515,25 @@ impl Competition {
         Ok(score)
     }
 
+    /// Stops the solver from participating in competitions until
+    /// [`Competition::resume`] is called.
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// The stored settlement, if it hasn't been settled yet.
+    fn pending_settlement(&self) -> Option<Settlement> {
+        self.settlement.lock().unwrap().clone()
+    }
+
     pub async fn reveal(&self) -> Result<Revealed, Error> {
         let settlement = self
             .settlement
@@ -267,6 +551,8 @@ impl Competition {
                 .transaction(settlement::Internalization::Disable)
                 .input
                 .clone(),
+            orders: settlement.orders().into_keys().collect(),
+            surpluses: settlement.surpluses(),
         })
     }
 
@@ -279,6 +565,16 @@ impl Competition {
             .unwrap()
             .take()
             .ok_or(Error::SolutionNotAvailable)?;
+        self.ensure_no_expired_orders(&settlement)?;
+        // Flag the orders expiring within the grace period so that their
+        // submission can be told apart.
+        let settlement = match self.solver.expiry_grace_period() {
+            Some(grace) => settlement.flag_expiring_orders(self.now(), grace),
+            None => settlement,
+        };
+        if !settlement.expiring_orders().is_empty() {
+            observe::orders_expiring_soon(self.solver.name(), settlement.expiring_orders());
+        }
 
         let executed = self
             .mempools
@@ -307,6 +603,44 @@ impl Competition {
         }
     }
 
+    /// Makes sure that all user orders of the settlement are still valid when
+    /// it gets submitted. The settlement is declined otherwise, since settling
+    /// anything but the revealed settlement could get the solver slashed.
+    fn ensure_no_expired_orders(&self, settlement: &Settlement) -> Result<(), Error> {
+        let expired = settlement.expired_orders(self.now());
+        if expired.is_empty() {
+            return Ok(());
+        }
+        for uid in &expired {
+            observe::order_expired(self.solver.name(), uid);
+        }
+        Err(Error::OrderExpired)
+    }
+
+    /// The time orders need to be valid at to be settled. The settlement can't
+    /// be included before the next block, so orders which are valid only up
+    /// to the current block are already too old.
+    fn now(&self) -> util::Timestamp {
+        util::Timestamp(
+            self.eth
+                .current_block()
+                .borrow()
+                .timestamp
+                .try_into()
+                .unwrap_or(u32::MAX),
+        )
+    }
+
+    /// Summary of the auction currently (or most recently) being solved.
+    pub fn latest_auction(&self) -> Option<Summary> {
+        self.auction.lock().unwrap().clone()
+    }
+
+    /// Report of the most recently solved auction.
+    pub fn latest_report(&self) -> Option<SolveReport> {
+        self.report.lock().unwrap().clone()
+    }
+
     /// The ID of the auction being competed on.
     pub fn auction_id(&self) -> Option<auction::Id> {
         self.settlement
@@ -328,6 +662,7 @@ impl Competition {
                 err: SimulatorError::GasExceeded(gas_needed_for_tx, settlement.gas.limit),
                 tx: tx.clone(),
                 block: self.eth.current_block().borrow().number.into(),
+                trace: None,
             }));
         }
         Ok(())
@@ -338,27 +673,39 @@ const MAX_SOLUTIONS_TO_MERGE: usize = 10;
 
 /// Creates a vector with all possible combinations of the given solutions.
 /// The result is sorted descending by score.
-fn merge(solutions: impl Iterator<Item = Solution>, auction: &Auction) -> Vec<Solution> {
-    let mut merged: Vec<Solution> = Vec::new();
+fn merge(
+    solutions: impl Iterator<Item = Solution>,
+    auction: &Auction,
+    solver: &Solver,
+) -> (Vec<Solution>, usize) {
+    // Stop merging once the configured share of the remaining time is used up
+    // to leave enough time for scoring and settling the solutions.
+    let deadline = solver.merge_time_share().map(|share| {
+        let remaining = auction.deadline().driver().remaining().unwrap_or_default();
+        std::time::Instant::now() + remaining.mul_f64(share.get())
+    });
     // Limit the number of solutions to merge to avoid combinatorial explosion
     // (2^MAX_SOLUTIONS).
-    for solution in solutions.take(MAX_SOLUTIONS_TO_MERGE) {
-        let mut extension = vec![];
-        for already_merged in merged.iter() {
-            match solution.merge(already_merged) {
-                Ok(merged) => {
-                    observe::merged(&solution, already_merged, &merged);
-                    extension.push(merged);
-                }
-                Err(err) => {
-                    observe::not_merged(&solution, already_merged, err);
-                }
+    let mut merges = 0;
+    let (mut merged, attempts) = combine(
+        solutions.take(MAX_SOLUTIONS_TO_MERGE),
+        || deadline.is_some_and(|deadline| std::time::Instant::now() >= deadline),
+        |solution, already_merged| match solution.merge(already_merged) {
+            Ok(merged) => {
+                observe::merged(solution, already_merged, &merged);
+                merges += 1;
+                Some(merged)
             }
-        }
-        // At least insert the current solution
-        extension.push(solution);
-        merged.extend(extension);
+            Err(err) => {
+                observe::not_merged(solution, already_merged, err);
+                None
+            }
+        },
+    );
+    if deadline.is_some_and(|deadline| std::time::Instant::now() >= deadline) {
+        observe::merging_deadline_reached(solver.name());
     }
+    observe::merging(solver.name(), attempts, merged.len());
 
     // Sort merged solutions descending by score.
     merged.sort_by_key(|solution| {
@@ -372,7 +719,153 @@ fn merge(solutions: impl Iterator<Item = Solution>, auction: &Auction) -> Vec<So
                 .unwrap_or_default(),
         )
     });
-    merged
+    (merged, merges)
+}
+
+/// Tries to merge every item with all items and combinations preceding it.
+/// Returns all items and successful combinations, together with the number of
+/// attempted merges. Once `deadline_reached` returns true no more merges are
+/// attempted and the remaining items are returned as they are.
+fn combine<T>(
+    items: impl Iterator<Item = T>,
+    deadline_reached: impl Fn() -> bool,
+    mut merge: impl FnMut(&T, &T) -> Option<T>,
+) -> (Vec<T>, usize) {
+    let mut merged: Vec<T> = Vec::new();
+    let mut attempts = 0;
+    for item in items {
+        let mut extension = vec![];
+        for already_merged in merged.iter() {
+            if deadline_reached() {
+                break;
+            }
+            attempts += 1;
+            extension.extend(merge(&item, already_merged));
+        }
+        // At least insert the current item
+        extension.push(item);
+        merged.extend(extension);
+    }
+    (merged, attempts)
+}
+
+/// Whether a still pending settlement with the score `pending` is kept instead
+/// of the best settlement of a new auction, scoring `new` if there is one.
+fn keeps_pending(
+    policy: solver::PendingSettlement,
+    new: Option<eth::Ether>,
+    pending: eth::Ether,
+) -> bool {
+    match policy {
+        solver::PendingSettlement::Replace => false,
+        solver::PendingSettlement::KeepBetter => new.map_or(true, |new| new < pending),
+    }
+}
+
+/// Whether all `settled` orders are part of the `available` ones.
+fn settles_only(
+    settled: impl IntoIterator<Item = order::Uid>,
+    available: impl IntoIterator<Item = order::Uid>,
+) -> bool {
+    let available = available.into_iter().collect::<HashSet<_>>();
+    settled.into_iter().all(|uid| available.contains(&uid))
+}
+
+/// Whether an auction created at `created` is older than `max_age` at `now`.
+/// Never true if no maximum age is configured.
+fn is_too_old(
+    created: chrono::DateTime<chrono::Utc>,
+    now: chrono::DateTime<chrono::Utc>,
+    max_age: Option<std::time::Duration>,
+) -> bool {
+    match max_age.and_then(|max_age| chrono::Duration::from_std(max_age).ok()) {
+        Some(max_age) => now - created > max_age,
+        None => false,
+    }
+}
+
+/// Whether a settlement with the given score and gas estimate generates at
+/// least `min_surplus_per_gas` wei of score per unit of gas. Always true if no
+/// minimum is configured.
+fn is_efficient(score: eth::Ether, gas: eth::Gas, min_surplus_per_gas: Option<eth::U256>) -> bool {
+    match min_surplus_per_gas {
+        Some(min) => score.0 >= min.saturating_mul(gas.0),
+        None => true,
+    }
+}
+
+/// Discards the candidates which don't generate enough score per unit of gas,
+/// see [`is_efficient`], passing each of them to `discarded`.
+fn efficient<T>(
+    candidates: Vec<(eth::Ether, T)>,
+    gas: impl Fn(&T) -> eth::Gas,
+    min_surplus_per_gas: Option<eth::U256>,
+    mut discarded: impl FnMut(&eth::Ether, &T),
+) -> Vec<(eth::Ether, T)> {
+    candidates
+        .into_iter()
+        .filter(|(score, candidate)| {
+            let efficient = is_efficient(*score, gas(candidate), min_surplus_per_gas);
+            if !efficient {
+                discarded(score, candidate);
+            }
+            efficient
+        })
+        .collect()
+}
+
+/// Picks the best candidate according to its [`Score`].
+fn best<T>(
+    candidates: &[(eth::Ether, T)],
+    score: impl Fn(&eth::Ether, &T) -> Score,
+) -> Option<&(eth::Ether, T)> {
+    candidates
+        .iter()
+        .max_by_key(|(value, candidate)| score(value, candidate))
+}
+
+/// The score of a settlement, ordered such that ties between equally scored
+/// settlements are broken deterministically. Among those, the settlement with
+/// the fewest interactions wins, then the one settling the fewest orders and
+/// finally the one with the lowest hash of its calldata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Score {
+    score: eth::Ether,
+    interactions: usize,
+    orders: usize,
+    hash: eth::H256,
+}
+
+impl Score {
+    fn new(score: eth::Ether, settlement: &Settlement) -> Self {
+        Self {
+            score,
+            interactions: settlement.interaction_count(),
+            orders: settlement.orders().len(),
+            hash: eth::H256(web3::signing::keccak256(
+                &settlement
+                    .transaction(settlement::Internalization::Enable)
+                    .input
+                    .0,
+            )),
+        }
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.score
+            .cmp(&other.score)
+            .then_with(|| other.interactions.cmp(&self.interactions))
+            .then_with(|| other.orders.cmp(&self.orders))
+            .then_with(|| other.hash.cmp(&self.hash))
+    }
+}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 /// Solution information sent to the protocol by the driver before the solution
@@ -385,6 +878,80 @@ pub struct Solved {
     pub gas: Option<eth::Gas>,
 }
 
+/// The outcome of a competition, i.e. the scored settlements of the solver and
+/// the winning one among them.
+#[derive(Debug, Clone)]
+pub struct Results {
+    pub auction_id: Option<auction::Id>,
+    pub participants: Vec<(solution::Id, eth::Ether)>,
+    pub winner: Option<solution::Id>,
+}
+
+/// Summary of an auction passed to the competition, used for introspection.
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub id: Option<auction::Id>,
+    /// Number of orders in the auction after pre-processing.
+    pub orders: usize,
+    /// Deadline by which the driver has to respond to the autopilot.
+    pub deadline: chrono::DateTime<chrono::Utc>,
+}
+
+/// What happened to an auction during [`Competition::solve`], reported to
+/// help solver teams understand the driver's decisions.
+#[derive(Debug, Clone)]
+pub struct SolveReport {
+    pub auction_id: Option<auction::Id>,
+    /// Number of orders in the auction after pre-processing.
+    pub orders: usize,
+    /// Number of orders settled by the winning solution.
+    pub solved_orders: usize,
+    /// Number of solutions returned by the solver.
+    pub solutions: usize,
+    /// Number of orders dropped before solving by the reason they were
+    /// dropped for.
+    pub skipped_orders: BTreeMap<&'static str, usize>,
+    /// Number of discarded solutions by the reason they were discarded for.
+    pub skipped_solutions: BTreeMap<&'static str, usize>,
+    /// Number of successfully merged solutions.
+    pub merges: usize,
+    pub winner_score: Option<eth::Ether>,
+    /// Time from receiving the auction until the winner was picked.
+    pub duration: std::time::Duration,
+}
+
+/// Measures the durations of consecutive phases, e.g. of a call to
+/// [`Competition::solve`].
+struct Stopwatch<C> {
+    clock: C,
+    lap_started: std::time::Instant,
+    laps: Vec<(&'static str, std::time::Duration)>,
+}
+
+impl<C: Fn() -> std::time::Instant> Stopwatch<C> {
+    fn new(clock: C) -> Self {
+        let lap_started = clock();
+        Self {
+            clock,
+            lap_started,
+            laps: Vec::new(),
+        }
+    }
+
+    /// Records the time since the previous lap ended as the duration of the
+    /// given phase.
+    fn lap(&mut self, phase: &'static str) {
+        let now = (self.clock)();
+        self.laps
+            .push((phase, now.duration_since(self.lap_started)));
+        self.lap_started = now;
+    }
+
+    fn into_laps(self) -> Vec<(&'static str, std::time::Duration)> {
+        self.laps
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Amounts {
     pub sell: eth::TokenAmount,
@@ -408,6 +975,47 @@ pub struct Revealed {
     /// can manually enforce certain rules which can not be enforced
     /// automatically.
     pub uninternalized_calldata: Bytes<Vec<u8>>,
+    /// The orders solved by the winning solution.
+    pub orders: Vec<order::Uid>,
+    /// The surplus each solved user order receives, denominated in the
+    /// order's surplus token.
+    pub surpluses: HashMap<order::Uid, eth::Asset>,
+}
+
+impl Revealed {
+    /// Commits to the set of solved orders with the root of a Merkle tree,
+    /// which allows proving the inclusion of individual orders later on.
+    ///
+    /// The leaves are the `keccak256` hashes of the order uids, sorted by uid
+    /// and without duplicates. Each parent node is the `keccak256` hash of the
+    /// concatenation of its two children, ordered so that the smaller hash
+    /// comes first. This way proofs don't need to encode whether a sibling is
+    /// on the left or on the right. A node without sibling is moved up to the
+    /// next level unchanged. The root of an empty set is all zeros.
+    pub fn orders_merkle_root(&self) -> [u8; 32] {
+        let mut nodes = self
+            .orders
+            .iter()
+            .map(|uid| uid.0 .0)
+            .sorted()
+            .dedup()
+            .map(|uid| web3::signing::keccak256(&uid))
+            .collect_vec();
+        while nodes.len() > 1 {
+            nodes = nodes
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => {
+                        let (a, b) = (left.min(right), left.max(right));
+                        web3::signing::keccak256(&[a.as_slice(), b.as_slice()].concat())
+                    }
+                    [node] => *node,
+                    _ => unreachable!("chunks of two"),
+                })
+                .collect();
+        }
+        nodes.first().copied().unwrap_or_default()
+    }
 }
 
 #[derive(Debug)]
@@ -434,4 +1042,218 @@ pub enum Error {
     Solver(#[from] solver::Error),
     #[error("failed to submit the solution")]
     SubmissionError,
+    #[error("the solver is paused")]
+    SolverPaused,
+    #[error("an order of the solution expired before it could be settled")]
+    OrderExpired,
+    #[error("the auction is older than the configured maximum age")]
+    AuctionTooOld,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_duration_of_each_phase() {
+        let start = std::time::Instant::now();
+        let elapsed = std::cell::Cell::new(std::time::Duration::ZERO);
+        let mut phases = Stopwatch::new(|| start + elapsed.get());
+
+        let advance =
+            |millis| elapsed.set(elapsed.get() + std::time::Duration::from_millis(millis));
+        advance(30);
+        phases.lap("liquidity");
+        advance(500);
+        phases.lap("solver");
+        phases.lap("merge");
+        advance(20);
+        phases.lap("encode");
+
+        assert_eq!(
+            phases.into_laps(),
+            vec![
+                ("liquidity", std::time::Duration::from_millis(30)),
+                ("solver", std::time::Duration::from_millis(500)),
+                ("merge", std::time::Duration::ZERO),
+                ("encode", std::time::Duration::from_millis(20)),
+            ]
+        );
+    }
+
+    #[test]
+    fn inefficient_settlement_loses() {
+        // Candidates are the gas used by a settlement with the given score.
+        let candidates = vec![
+            (eth::Ether(1_000.into()), eth::Gas(100.into())),
+            (eth::Ether(1_500.into()), eth::Gas(500.into())),
+        ];
+        let winner = |min: Option<eth::U256>| {
+            let mut discarded = 0;
+            let candidates = efficient(candidates.clone(), |gas| *gas, min, |_, _| discarded += 1);
+            let winner = best(&candidates, |score, _| Score {
+                score: *score,
+                interactions: 1,
+                orders: 1,
+                hash: Default::default(),
+            })
+            .map(|(score, _)| score.0.as_u64());
+            (winner, discarded)
+        };
+
+        // Without a threshold the higher absolute score wins.
+        assert_eq!(winner(None), (Some(1_500), 0));
+        // With a threshold of 5 wei per gas the settlement which generates only
+        // 3 wei per gas is discarded, even though its score is higher.
+        assert_eq!(winner(Some(5.into())), (Some(1_000), 1));
+        assert_eq!(winner(Some(20.into())), (None, 2));
+    }
+
+    #[test]
+    fn stale_auction_is_too_old() {
+        let now = chrono::Utc::now();
+        let max_age = Some(std::time::Duration::from_secs(5));
+
+        let stale = now - chrono::Duration::seconds(10);
+        let fresh = now - chrono::Duration::seconds(1);
+
+        assert!(is_too_old(stale, now, max_age));
+        assert!(!is_too_old(fresh, now, max_age));
+        // Without a maximum age no auction is too old.
+        assert!(!is_too_old(stale, now, None));
+    }
+
+    #[test]
+    fn better_pending_settlement_is_kept() {
+        let eth = |wei: u64| eth::Ether(wei.into());
+        let policy = solver::PendingSettlement::KeepBetter;
+
+        // A lower scoring new settlement doesn't overwrite the pending one.
+        assert!(keeps_pending(policy, Some(eth(900)), eth(1_000)));
+        assert!(keeps_pending(policy, None, eth(1_000)));
+        // A new settlement scoring at least as well does.
+        assert!(!keeps_pending(policy, Some(eth(1_000)), eth(1_000)));
+        assert!(!keeps_pending(policy, Some(eth(1_100)), eth(1_000)));
+
+        // By default new settlements always replace the pending one.
+        let policy = solver::PendingSettlement::Replace;
+        assert!(!keeps_pending(policy, Some(eth(900)), eth(1_000)));
+        assert!(!keeps_pending(policy, None, eth(1_000)));
+    }
+
+    #[test]
+    fn pending_settlement_needs_orders_in_auction() {
+        let uid = |byte: u8| order::Uid([byte; order::UID_LEN].into());
+
+        assert!(settles_only([uid(1), uid(2)], [uid(3), uid(2), uid(1)]));
+        assert!(settles_only([], [uid(1)]));
+        // Order 2 is no longer part of the auction.
+        assert!(!settles_only([uid(1), uid(2)], [uid(1), uid(3)]));
+    }
+
+    #[test]
+    fn equal_scores_are_tiebroken_deterministically() {
+        let score = |interactions, orders, hash| Score {
+            score: eth::Ether(1_000.into()),
+            interactions,
+            orders,
+            hash: eth::H256::repeat_byte(hash),
+        };
+        let winner = |scores: &[Score]| *scores.iter().max().unwrap();
+
+        // Fewer interactions win, regardless of the order of the settlements.
+        let (a, b) = (score(1, 2, 1), score(2, 1, 0));
+        assert_eq!(winner(&[a, b]), a);
+        assert_eq!(winner(&[b, a]), a);
+        // Then fewer orders.
+        let (a, b) = (score(1, 1, 1), score(1, 2, 0));
+        assert_eq!(winner(&[a, b]), a);
+        assert_eq!(winner(&[b, a]), a);
+        // Then the lower hash.
+        let (a, b) = (score(1, 1, 0), score(1, 1, 1));
+        assert_eq!(winner(&[a, b]), a);
+        assert_eq!(winner(&[b, a]), a);
+        // A higher score always wins.
+        let higher = Score {
+            score: eth::Ether(1_001.into()),
+            ..score(5, 5, 5)
+        };
+        assert_eq!(winner(&[a, higher]), higher);
+    }
+
+    #[test]
+    fn counts_merge_attempts() {
+        // Items are bitmasks which can only be merged if they don't overlap.
+        let merge = |a: &u32, b: &u32| (a & b == 0).then_some(a | b);
+
+        let (merged, attempts) = combine([1, 2, 4].into_iter(), || false, merge);
+        assert_eq!(merged, vec![1, 3, 2, 5, 7, 6, 4]);
+        assert_eq!(attempts, 4);
+
+        // Failed merges count as attempts, too.
+        let (merged, attempts) = combine([1, 2, 1].into_iter(), || false, merge);
+        assert_eq!(merged, vec![1, 3, 2, 3, 1]);
+        assert_eq!(attempts, 4);
+
+        let (merged, attempts) = combine(std::iter::empty(), || false, merge);
+        assert!(merged.is_empty());
+        assert_eq!(attempts, 0);
+    }
+
+    #[test]
+    fn stops_merging_at_deadline() {
+        let merge = |a: &u32, b: &u32| (a & b == 0).then_some(a | b);
+        let items = [1, 2, 4, 8, 16];
+
+        let (_, all_attempts) = combine(items.into_iter(), || false, merge);
+        assert_eq!(all_attempts, 26);
+
+        // Every merge takes one tick, the deadline is reached after three.
+        let ticks = std::cell::Cell::new(0);
+        let (merged, attempts) = combine(
+            items.into_iter(),
+            || ticks.get() >= 3,
+            |a, b| {
+                ticks.set(ticks.get() + 1);
+                merge(a, b)
+            },
+        );
+        assert_eq!(attempts, 3);
+        // Items which couldn't be merged anymore are still returned.
+        assert_eq!(merged, [1, 3, 2, 5, 7, 4, 8, 16]);
+
+        // Nothing gets merged if the deadline has already passed.
+        let (merged, attempts) = combine(items.into_iter(), || true, merge);
+        assert_eq!(merged, items);
+        assert_eq!(attempts, 0);
+    }
+
+    #[test]
+    fn orders_merkle_root() {
+        let revealed = |uids: &[u8]| Revealed {
+            internalized_calldata: Default::default(),
+            uninternalized_calldata: Default::default(),
+            orders: uids
+                .iter()
+                .map(|&byte| order::Uid([byte; order::UID_LEN].into()))
+                .collect(),
+            surpluses: Default::default(),
+        };
+
+        // The root only depends on the set of orders.
+        let root = revealed(&[3, 1, 2]).orders_merkle_root();
+        assert_eq!(root, revealed(&[1, 2, 3]).orders_merkle_root());
+        assert_eq!(root, revealed(&[2, 3, 1, 2]).orders_merkle_root());
+        assert_eq!(
+            root,
+            hex_literal::hex!("424ca49062671fd3c3b37795e94346e48803b97980c0d5bca996f02ff2a1162e")
+        );
+
+        // A single order is its own leaf.
+        assert_eq!(
+            revealed(&[1]).orders_merkle_root(),
+            web3::signing::keccak256(&[1; order::UID_LEN])
+        );
+        assert_eq!(revealed(&[]).orders_merkle_root(), [0; 32]);
+    }
 }
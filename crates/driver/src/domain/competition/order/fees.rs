@@ -43,6 +43,9 @@ pub enum FeePolicy {
         /// Percentage of the order's volume should be taken as a protocol
         /// fee.
         factor: f64,
+        /// The integrator the fee is paid out to, for partner fees specified
+        /// in the order's app data.
+        recipient: Option<eth::Address>,
     },
 }
 
@@ -120,6 +120,17 @@ impl Order {
         eth.is_contract(self.receiver()).await
     }
 
+    /// Whether this order is no longer valid at the given time.
+    pub fn is_expired(&self, now: util::Timestamp) -> bool {
+        self.valid_to.0 <= now.0
+    }
+
+    /// Whether this order is still valid at the given time but expires within
+    /// the grace period after it.
+    pub fn expires_within(&self, now: util::Timestamp, grace: std::time::Duration) -> bool {
+        !self.is_expired(now) && u64::from(self.valid_to.0) <= u64::from(now.0) + grace.as_secs()
+    }
+
     /// Does this order buy ETH?
     pub fn buys_eth(&self) -> bool {
         self.buy.token == eth::ETH_TOKEN
@@ -303,12 +314,78 @@ pub enum Kind {
     /// The order surplus is the additional money that the solver managed to
     /// solve for, above what the user specified in the order. The exact amount
     /// of fees that are taken is determined by the solver.
-    Limit,
+    Limit {
+        /// Optional limit price relative to the auction's reference prices,
+        /// which tightens the signed limit price at solve time.
+        oracle: Option<OracleLimit>,
+    },
     /// An order submitted by a privileged user, which provides liquidity for
     /// our settlement contract.
     Liquidity,
 }
 
+/// A limit price expressed relative to the reference prices of the order's
+/// tokens instead of as fixed amounts, e.g. "sell at 1% above the reference
+/// price".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OracleLimit {
+    /// How much better than the reference price the limit price is, in basis
+    /// points. Negative values accept prices worse than the reference price.
+    pub offset_bps: i32,
+}
+
+impl OracleLimit {
+    const BPS: i32 = 10_000;
+
+    /// Tightens the limit amounts of an order to the limit price implied by
+    /// the reference prices of its sell and buy tokens. The signed limit price
+    /// is still enforced onchain, so the amounts are never loosened. The
+    /// amounts are returned unchanged if the limit can't be computed.
+    pub fn apply(
+        &self,
+        side: Side,
+        amounts: Available,
+        sell_price: auction::Price,
+        buy_price: auction::Price,
+    ) -> Available {
+        let Ok(factor) = u32::try_from(Self::BPS + self.offset_bps) else {
+            return amounts;
+        };
+        let (factor, bps) = (eth::U256::from(factor), eth::U256::from(Self::BPS));
+        let (sell_price, buy_price) = (eth::U256::from(sell_price), eth::U256::from(buy_price));
+        match side {
+            Side::Sell => {
+                let limit = util::math::mul_ratio(amounts.sell.amount.0, sell_price, buy_price)
+                    .and_then(|oracle| util::math::mul_ratio_ceil(oracle, factor, bps));
+                match limit {
+                    Some(limit) => Available {
+                        buy: eth::Asset {
+                            amount: amounts.buy.amount.max(limit.into()),
+                            ..amounts.buy
+                        },
+                        ..amounts
+                    },
+                    None => amounts,
+                }
+            }
+            Side::Buy => {
+                let limit = util::math::mul_ratio(amounts.buy.amount.0, buy_price, sell_price)
+                    .and_then(|oracle| util::math::mul_ratio(oracle, bps, factor));
+                match limit {
+                    Some(limit) => Available {
+                        sell: eth::Asset {
+                            amount: amounts.sell.amount.min(limit.into()),
+                            ..amounts.sell
+                        },
+                        ..amounts
+                    },
+                    None => amounts,
+                }
+            }
+        }
+    }
+}
+
 /// [Balancer V2](https://docs.balancer.fi/) integration, used for settlement encoding.
 #[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
 pub enum SellTokenBalance {
@@ -397,6 +474,8 @@ pub struct Jit {
     pub buy_token_balance: BuyTokenBalance,
     pub signature: Signature,
     pub uid: Uid,
+    /// Whether the solver signed this order as partially fillable.
+    pub partially_fillable: bool,
 }
 
 impl Jit {
@@ -416,11 +495,17 @@ impl Jit {
         SellAmount(0.into())
     }
 
-    /// Returns the signed partially fillable property of the order. You can't
-    /// set this field in the API so it's enforced to be fill-or-kill. This
-    /// function only exists to not have magic values scattered everywhere.
+    /// Returns the signed partially fillable property of the order. A
+    /// partially fillable JIT order can be executed for any amount up to its
+    /// full target amount, otherwise it is fill-or-kill.
     pub fn partially_fillable(&self) -> Partial {
-        Partial::No
+        if self.partially_fillable {
+            Partial::Yes {
+                available: self.target(),
+            }
+        } else {
+            Partial::No
+        }
     }
 }
 
@@ -451,7 +536,7 @@ mod tests {
                 Some(executed) if executed.token == buy(0).token => Side::Buy,
                 _ => panic!(),
             },
-            kind: Kind::Limit,
+            kind: Kind::Limit { oracle: None },
             app_data: Default::default(),
             partial: available
                 .map(|available| Partial::Yes {
@@ -498,4 +583,57 @@ mod tests {
 
         assert_eq!(order(0, 0, Some(sell(0))).available().sell, sell(0));
     }
+
+    #[test]
+    fn oracle_limit() {
+        let available = |sell: u64, buy: u64| Available {
+            sell: eth::Asset {
+                token: eth::H160::from_low_u64_be(0x5e11).into(),
+                amount: eth::U256::from(sell).into(),
+            },
+            buy: eth::Asset {
+                token: eth::H160::from_low_u64_be(0xbbbb).into(),
+                amount: eth::U256::from(buy).into(),
+            },
+        };
+        let amounts = |available: Available| {
+            (
+                available.sell.amount.0.as_u64(),
+                available.buy.amount.0.as_u64(),
+            )
+        };
+        let price = |eth: u128| auction::Price::new(eth::U256::from(eth).into()).unwrap();
+        // The sell token is worth twice as much as the buy token.
+        let (sell_price, buy_price) = (price(2_000_000_000_000_000_000), price(10u128.pow(18)));
+
+        let oracle = OracleLimit { offset_bps: 100 };
+        assert_eq!(
+            amounts(oracle.apply(Side::Sell, available(1000, 1500), sell_price, buy_price)),
+            (1000, 2020)
+        );
+        assert_eq!(
+            amounts(oracle.apply(Side::Buy, available(1200, 2000), sell_price, buy_price)),
+            (990, 2000)
+        );
+
+        // A looser oracle limit never loosens the signed limit price.
+        let oracle = OracleLimit { offset_bps: -5000 };
+        assert_eq!(
+            amounts(oracle.apply(Side::Sell, available(1000, 1500), sell_price, buy_price)),
+            (1000, 1500)
+        );
+        assert_eq!(
+            amounts(oracle.apply(Side::Buy, available(800, 2000), sell_price, buy_price)),
+            (800, 2000)
+        );
+
+        // Offsets of -100% or lower can't be applied.
+        let oracle = OracleLimit {
+            offset_bps: -10_000,
+        };
+        assert_eq!(
+            amounts(oracle.apply(Side::Buy, available(800, 2000), sell_price, buy_price)),
+            (800, 2000)
+        );
+    }
 }
@@ -0,0 +1,238 @@
+//! Replays the merging, scoring and selection of [`Competition::solve`] over
+//! settlements recorded during a competition. The replay is deterministic and
+//! doesn't make any network requests, which makes it useful to debug why a
+//! solver won or lost an auction.
+//!
+//! [`Competition::solve`]: super::Competition::solve
+
+use {
+    super::{auction, best, combine, efficient, order, solution, Score, SolveReport},
+    crate::{domain::eth, infra::solver::SolutionMerging},
+    std::collections::{BTreeMap, HashSet},
+};
+
+/// The inputs of a competition as recorded for replaying it.
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub auction_id: Option<auction::Id>,
+    /// Number of orders in the auction.
+    pub orders: usize,
+    /// The settlements in the order the solver returned their solutions.
+    pub settlements: Vec<Recorded>,
+    pub solution_merging: SolutionMerging,
+    pub min_surplus_per_gas: Option<eth::U256>,
+}
+
+/// A settlement as encoded and scored during a competition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recorded {
+    pub id: solution::Id,
+    pub orders: HashSet<order::Uid>,
+    pub score: eth::Ether,
+    pub gas: eth::Gas,
+    pub interactions: usize,
+    /// The hash of the settlement's calldata.
+    pub hash: eth::H256,
+}
+
+impl Recorded {
+    /// Merges two settlements which don't settle the same order, like
+    /// [`solution::Solution::merge`] does. The merged settlement scores and
+    /// costs as much as both settlements together. Since its calldata is
+    /// unknown, its hash is derived from the hashes of both settlements.
+    fn merge(&self, other: &Self) -> Option<Self> {
+        if !self.orders.is_disjoint(&other.orders) {
+            return None;
+        }
+        Some(Self {
+            id: solution::Id::Merged([self.id.ids(), other.id.ids()].concat()),
+            orders: self.orders.union(&other.orders).copied().collect(),
+            score: eth::Ether(self.score.0.saturating_add(other.score.0)),
+            gas: eth::Gas(self.gas.0.saturating_add(other.gas.0)),
+            interactions: self.interactions + other.interactions,
+            hash: eth::H256(web3::signing::keccak256(
+                &[self.hash.0, other.hash.0].concat(),
+            )),
+        })
+    }
+
+    fn score(&self) -> Score {
+        Score {
+            score: self.score,
+            interactions: self.interactions,
+            orders: self.orders.len(),
+            hash: self.hash,
+        }
+    }
+}
+
+/// The outcome of a replayed competition.
+#[derive(Debug)]
+pub struct Replay {
+    pub winner: Option<Recorded>,
+    pub report: SolveReport,
+}
+
+/// Replays a recorded competition.
+pub fn replay(recording: &Recording) -> Replay {
+    let started = std::time::Instant::now();
+    let mut skipped = BTreeMap::<&'static str, usize>::new();
+    let mut skip = |reason, count| *skipped.entry(reason).or_default() += count;
+
+    let mut ids = HashSet::new();
+    let settlements = recording
+        .settlements
+        .iter()
+        .filter(|settlement| {
+            if !ids.insert(settlement.id.clone()) {
+                skip("DuplicateId", 1);
+                return false;
+            }
+            if settlement.orders.is_empty() {
+                skip("EmptySolution", 1);
+                return false;
+            }
+            true
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let (settlements, merges) = match recording.solution_merging {
+        SolutionMerging::Allowed => {
+            let mut merges = 0;
+            let (merged, _) = combine(
+                settlements.into_iter().take(super::MAX_SOLUTIONS_TO_MERGE),
+                || false,
+                |settlement, already_merged| {
+                    let merged = settlement.merge(already_merged)?;
+                    merges += 1;
+                    Some(merged)
+                },
+            );
+            (merged, merges)
+        }
+        SolutionMerging::Forbidden => (settlements, 0),
+    };
+
+    let settlements = efficient(
+        settlements
+            .into_iter()
+            .map(|settlement| (settlement.score, settlement))
+            .collect(),
+        |settlement| settlement.gas,
+        recording.min_surplus_per_gas,
+        |_, _| skip("InsufficientSurplusPerGas", 1),
+    );
+    let winner = best(&settlements, |_, settlement| settlement.score())
+        .map(|(_, settlement)| settlement.clone());
+
+    let report = SolveReport {
+        auction_id: recording.auction_id,
+        orders: recording.orders,
+        solved_orders: winner
+            .as_ref()
+            .map(|winner| winner.orders.len())
+            .unwrap_or_default(),
+        solutions: recording.settlements.len(),
+        skipped_orders: Default::default(),
+        skipped_solutions: skipped,
+        merges,
+        winner_score: winner.as_ref().map(|winner| winner.score),
+        duration: started.elapsed(),
+    };
+    Replay { winner, report }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uid(byte: u8) -> order::Uid {
+        order::Uid([byte; order::UID_LEN].into())
+    }
+
+    fn recorded(id: u64, orders: &[u8], score: u64, gas: u64) -> Recorded {
+        Recorded {
+            id: id.into(),
+            orders: orders.iter().copied().map(uid).collect(),
+            score: eth::Ether(score.into()),
+            gas: eth::Gas(gas.into()),
+            interactions: 1,
+            hash: eth::H256::repeat_byte(id as u8),
+        }
+    }
+
+    fn recording(settlements: Vec<Recorded>) -> Recording {
+        Recording {
+            auction_id: Some(auction::Id(1)),
+            orders: 3,
+            settlements,
+            solution_merging: SolutionMerging::Allowed,
+            min_surplus_per_gas: None,
+        }
+    }
+
+    #[test]
+    fn merged_settlement_wins() {
+        let recording = recording(vec![
+            recorded(1, &[1], 1_000, 100),
+            recorded(2, &[2], 1_500, 100),
+            // Settles the same order as solution 1, so it can't be merged.
+            recorded(3, &[1, 3], 2_000, 100),
+            recorded(2, &[3], 10_000, 100),
+            recorded(4, &[], 0, 0),
+        ]);
+
+        let Replay { winner, report } = replay(&recording);
+
+        let winner = winner.unwrap();
+        assert_eq!(winner.id, solution::Id::Merged(vec![3, 2]));
+        assert_eq!(winner.orders, [uid(1), uid(2), uid(3)].into());
+        assert_eq!(winner.score, eth::Ether(3_500.into()));
+        assert_eq!(report.solved_orders, 3);
+        assert_eq!(report.solutions, 5);
+        assert_eq!(report.merges, 2);
+        assert_eq!(report.winner_score, Some(eth::Ether(3_500.into())));
+        assert_eq!(
+            report.skipped_solutions,
+            [("DuplicateId", 1), ("EmptySolution", 1)].into()
+        );
+    }
+
+    #[test]
+    fn inefficient_settlements_are_discarded() {
+        let recording = Recording {
+            solution_merging: SolutionMerging::Forbidden,
+            min_surplus_per_gas: Some(5.into()),
+            ..recording(vec![
+                recorded(1, &[1], 1_000, 100),
+                // Scores higher but generates only 3 wei per gas.
+                recorded(2, &[2], 1_500, 500),
+            ])
+        };
+
+        let Replay { winner, report } = replay(&recording);
+
+        assert_eq!(winner.unwrap().id, solution::Id::Single(1));
+        assert_eq!(report.merges, 0);
+        assert_eq!(
+            report.skipped_solutions,
+            [("InsufficientSurplusPerGas", 1)].into()
+        );
+    }
+
+    #[test]
+    fn equal_scores_are_tiebroken_by_interactions() {
+        let mut complex = recorded(1, &[1], 1_000, 100);
+        complex.interactions = 3;
+        let recording = Recording {
+            solution_merging: SolutionMerging::Forbidden,
+            ..recording(vec![complex, recorded(2, &[2], 1_000, 100)])
+        };
+
+        assert_eq!(
+            replay(&recording).winner.unwrap().id,
+            solution::Id::Single(2)
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use {
+    super::Interaction,
+    ethabi::{ParamType, Token},
+};
+
+/// The calldata gas of a settlement attributed to its components.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GasBreakdown {
+    /// The encoded trades.
+    pub trades: u64,
+    /// The approvals preceding the settlement's interactions.
+    pub approvals: u64,
+    /// The swaps using liquidity indexed by the driver.
+    pub liquidity: u64,
+    /// The solver provided interactions.
+    pub custom: u64,
+    /// Everything else, i.e. the function selector, token and clearing price
+    /// vectors, ABI encoding overhead, hooks, unwraps and the auction id.
+    pub other: u64,
+}
+
+impl GasBreakdown {
+    /// The total calldata gas of the settlement.
+    pub fn total(&self) -> u64 {
+        self.trades + self.approvals + self.liquidity + self.custom + self.other
+    }
+}
+
+/// A component of the core interactions of a settlement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Component {
+    Approval,
+    Liquidity,
+    Custom,
+}
+
+impl From<&Interaction> for Component {
+    fn from(interaction: &Interaction) -> Self {
+        match interaction {
+            Interaction::Liquidity(_) => Self::Liquidity,
+            Interaction::Custom(_) => Self::Custom,
+        }
+    }
+}
+
+/// Breaks down the calldata gas of an uninternalized `settle()` call. The
+/// core interactions are expected to start with the given components, in
+/// order.
+pub(super) fn breakdown(input: &[u8], core: impl Iterator<Item = Component>) -> GasBreakdown {
+    let total = gas(input);
+    let mut breakdown = GasBreakdown::default();
+
+    // selector (4 bytes) + ABI encoded parameters + auction id (8 bytes)
+    let params = input
+        .get(4..input.len().saturating_sub(8))
+        .and_then(|params| ethabi::decode(&settle(), params).ok());
+    let Some([_, _, Token::Array(trades), Token::FixedArray(interactions)]) = params.as_deref()
+    else {
+        tracing::warn!("failed to decode settlement calldata");
+        breakdown.other = total;
+        return breakdown;
+    };
+
+    breakdown.trades = trades.iter().map(encoded_gas).sum();
+    if let Some(Token::Array(interactions)) = interactions.get(1) {
+        for (component, interaction) in core.zip(interactions) {
+            let gas = encoded_gas(interaction);
+            match component {
+                Component::Approval => breakdown.approvals += gas,
+                Component::Liquidity => breakdown.liquidity += gas,
+                Component::Custom => breakdown.custom += gas,
+            }
+        }
+    }
+    breakdown.other = total.saturating_sub(breakdown.total());
+    breakdown
+}
+
+/// The calldata gas of the given bytes, see EIP-2028.
+fn gas(data: &[u8]) -> u64 {
+    data.iter()
+        .map(|byte| match byte {
+            0 => 4,
+            _ => 16,
+        })
+        .sum()
+}
+
+fn encoded_gas(token: &Token) -> u64 {
+    gas(&ethabi::encode(std::slice::from_ref(token)))
+}
+
+// cf. https://github.com/cowprotocol/contracts/blob/v1.5.0/src/contracts/GPv2Settlement.sol#L121
+fn settle() -> [ParamType; 4] {
+    let uint = || ParamType::Uint(256);
+    let trade = ParamType::Tuple(vec![
+        uint(),
+        uint(),
+        ParamType::Address,
+        uint(),
+        uint(),
+        ParamType::Uint(32),
+        ParamType::FixedBytes(32),
+        uint(),
+        uint(),
+        uint(),
+        ParamType::Bytes,
+    ]);
+    let interaction = ParamType::Tuple(vec![ParamType::Address, uint(), ParamType::Bytes]);
+    [
+        ParamType::Array(Box::new(ParamType::Address)),
+        ParamType::Array(Box::new(uint())),
+        ParamType::Array(Box::new(trade)),
+        ParamType::FixedArray(Box::new(ParamType::Array(Box::new(interaction))), 3),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::domain::eth};
+
+    #[test]
+    fn breakdown_sums_to_total() {
+        let address = |byte: u8| Token::Address(eth::H160::repeat_byte(byte));
+        let uint = |value: u64| Token::Uint(value.into());
+        let interaction = |target: u8, call_data: Vec<u8>| {
+            Token::Tuple(vec![address(target), uint(0), Token::Bytes(call_data)])
+        };
+        let trade = Token::Tuple(vec![
+            uint(0),
+            uint(1),
+            address(3),
+            uint(100),
+            uint(90),
+            uint(u32::MAX.into()),
+            Token::FixedBytes(vec![7; 32]),
+            uint(0),
+            uint(0),
+            uint(100),
+            Token::Bytes(vec![9; 65]),
+        ]);
+        let params = ethabi::encode(&[
+            Token::Array(vec![address(1), address(2)]),
+            Token::Array(vec![uint(90), uint(100)]),
+            Token::Array(vec![trade.clone()]),
+            Token::FixedArray(vec![
+                Token::Array(vec![interaction(4, vec![1; 4])]),
+                Token::Array(vec![
+                    interaction(1, vec![2; 68]),
+                    interaction(5, vec![3; 100]),
+                    interaction(6, vec![4; 36]),
+                    // e.g. a WETH unwrap
+                    interaction(2, vec![5; 36]),
+                ]),
+                Token::Array(vec![]),
+            ]),
+        ]);
+        let input = [
+            [0x13, 0xd7, 0x9a, 0x0b].as_slice(),
+            &params,
+            &[0, 0, 0, 0, 0, 0, 0, 1],
+        ]
+        .concat();
+
+        let breakdown = breakdown(
+            &input,
+            [Component::Approval, Component::Liquidity, Component::Custom].into_iter(),
+        );
+
+        assert_eq!(breakdown.trades, encoded_gas(&trade));
+        assert_eq!(
+            breakdown.approvals,
+            encoded_gas(&interaction(1, vec![2; 68]))
+        );
+        assert_eq!(
+            breakdown.liquidity,
+            encoded_gas(&interaction(5, vec![3; 100]))
+        );
+        assert_eq!(breakdown.custom, encoded_gas(&interaction(6, vec![4; 36])));
+        assert!(breakdown.other > 0);
+        assert_eq!(breakdown.total(), gas(&input));
+    }
+}
@@ -1,5 +1,12 @@
 use {
-    super::{error::Math, interaction::Liquidity, settlement, slippage, trade::ClearingPrices},
+    super::{
+        error::Math,
+        explanation::{self, InteractionExplanation, Purpose},
+        interaction::Liquidity,
+        settlement,
+        slippage,
+        trade::ClearingPrices,
+    },
     crate::{
         domain::{
             competition::{
@@ -24,6 +31,12 @@ pub enum Error {
     MissingAuctionId,
     #[error("invalid clearing price: {0:?}")]
     InvalidClearingPrice(eth::TokenAddress),
+    #[error("gas payment requires a gas estimate")]
+    MissingGasEstimate,
+    #[error("missing price for gas payment token: {0:?}")]
+    MissingGasPaymentPrice(eth::TokenAddress),
+    #[error("could not compute partner fees: {0:?}")]
+    PartnerFees(super::error::Scoring),
     #[error(transparent)]
     Math(#[from] Math),
 }
@@ -36,14 +49,45 @@ pub fn tx(
     internalization: settlement::Internalization,
     solver_native_token: ManageNativeToken,
 ) -> Result<eth::Tx, Error> {
+    explained_tx(
+        auction,
+        solution,
+        contracts,
+        approvals,
+        internalization,
+        solver_native_token,
+    )
+    .map(|(tx, _)| tx)
+}
+
+/// Encodes the settlement transaction like [`tx`], additionally explaining
+/// each interaction it executes in execution order.
+pub fn explained_tx(
+    auction: &competition::Auction,
+    solution: &super::Solution,
+    contracts: &infra::blockchain::Contracts,
+    approvals: impl Iterator<Item = eth::allowance::Approval>,
+    internalization: settlement::Internalization,
+    solver_native_token: ManageNativeToken,
+) -> Result<(eth::Tx, Vec<InteractionExplanation>), Error> {
     let mut tokens = Vec::with_capacity(solution.prices.len() + (solution.trades().len() * 2));
     let mut clearing_prices =
         Vec::with_capacity(solution.prices.len() + (solution.trades().len() * 2));
     let mut trades: Vec<Trade> = Vec::with_capacity(solution.trades().len());
-    let mut pre_interactions = solution.pre_interactions.clone();
+    let mut hooks = Hooks {
+        pre: solution
+            .pre_interactions
+            .iter()
+            .map(Explained::call)
+            .collect(),
+        post: solution
+            .post_interactions
+            .iter()
+            .map(Explained::call)
+            .collect(),
+    };
     let mut interactions =
         Vec::with_capacity(approvals.size_hint().0 + solution.interactions().len());
-    let mut post_interactions = solution.post_interactions.clone();
     let mut native_unwrap = eth::TokenAmount(eth::U256::zero());
 
     // Encode uniform clearing price vector
@@ -60,8 +104,7 @@ pub fn tx(
     for trade in solution.trades() {
         let (price, mut trade) = match trade {
             super::Trade::Fulfillment(trade) => {
-                pre_interactions.extend(trade.order().pre_interactions.clone());
-                post_interactions.extend(trade.order().post_interactions.clone());
+                hooks.add(trade.order());
 
                 let uniform_prices = ClearingPrices {
                     sell: solution
@@ -161,53 +204,123 @@ pub fn tx(
     }
 
     // Encode allowances
-    for approval in approvals {
-        interactions.push(approve(&approval.0))
-    }
+    let (approvals, revocations) = allowances(approvals, solution.solver().revoke_approvals());
+    interactions.extend(approvals);
+
+    // Amounts the settlement contract's balances may decrease by
+    let mut outflows = Vec::new();
 
     // Encode interactions
-    let slippage = slippage::Parameters {
-        relative: solution.solver().slippage().relative.clone(),
-        max: solution.solver().slippage().absolute.map(Ether::into),
-        // TODO configure min slippage
-        min: None,
-        prices: auction.prices().clone(),
-    };
+    let slippage = slippage(auction, solution);
     for interaction in solution.interactions() {
         if matches!(internalization, settlement::Internalization::Enable)
             && interaction.internalize()
         {
+            // Internalized interactions are paid out of the buffers
+            outflows.extend(interaction.outputs());
             continue;
         }
 
-        interactions.push(match interaction {
-            competition::solution::Interaction::Custom(interaction) => eth::Interaction {
-                value: interaction.value,
-                target: interaction.target.into(),
-                call_data: interaction.call_data.clone(),
+        interactions.push(Explained {
+            interaction: match interaction {
+                competition::solution::Interaction::Custom(interaction) => eth::Interaction {
+                    value: interaction.value,
+                    target: interaction.target.into(),
+                    call_data: interaction.call_data.clone(),
+                },
+                competition::solution::Interaction::Liquidity(liquidity) => {
+                    outflows.push(slippage_outflow(liquidity, &slippage)?);
+                    liquidity_interaction(liquidity, &slippage, contracts.settlement())?
+                }
             },
-            competition::solution::Interaction::Liquidity(liquidity) => {
-                liquidity_interaction(liquidity, &slippage, contracts.settlement())?
-            }
+            explanation: explanation::explain_interaction(interaction, contracts.weth_address()),
         })
     }
 
     // Encode WETH unwrap
     if !native_unwrap.0.is_zero() && solver_native_token.insert_unwraps {
-        interactions.push(unwrap(native_unwrap, contracts.weth()));
+        interactions.push(Explained {
+            interaction: unwrap(native_unwrap, contracts.weth()),
+            explanation: explanation::explain_unwrap(contracts.weth_address()),
+        });
     }
 
+    // Encode the payouts of partner fees to the integrators
+    for (recipient, fee) in solution.partner_fees().map_err(Error::PartnerFees)? {
+        outflows.push(fee);
+        interactions.push(Explained {
+            interaction: transfer(fee, recipient),
+            explanation: InteractionExplanation::new(
+                Purpose::PartnerFee { recipient },
+                vec![fee.token],
+            ),
+        });
+    }
+
+    // Encode the gas payment to the relayer
+    if let Some(payment) = gas_payment(auction, solution)? {
+        outflows.push(payment.asset);
+        interactions.push(Explained {
+            interaction: transfer(payment.asset, payment.relayer),
+            explanation: InteractionExplanation::new(
+                Purpose::GasPayment {
+                    relayer: payment.relayer,
+                },
+                vec![payment.asset.token],
+            ),
+        });
+    }
+
+    // Encode the revocation of the allowances granted above
+    interactions.extend(revocations);
+
+    // Assert the balances of the settlement contract around the settlement
+    if let Some(checker) = solution.solver().balance_checker() {
+        let outflows = max_outflows(solution.prices.keys().copied(), outflows);
+        let settlement = contracts.settlement().address().into();
+        hooks.pre.splice(
+            0..0,
+            outflows.iter().map(|outflow| Explained {
+                interaction: snapshot_balance(checker, outflow.token, settlement),
+                explanation: InteractionExplanation::new(
+                    Purpose::BalanceSnapshot {
+                        token: outflow.token,
+                    },
+                    Default::default(),
+                ),
+            }),
+        );
+        hooks.post.extend(outflows.iter().map(|outflow| Explained {
+            interaction: assert_max_decrease(checker, outflow.token, settlement, outflow.amount),
+            explanation: InteractionExplanation::new(
+                Purpose::BalanceAssertion {
+                    token: outflow.token,
+                    max_decrease: outflow.amount,
+                },
+                Default::default(),
+            ),
+        }));
+    }
+
+    let phases = hooks.wrap(interactions);
+    let explanation = phases
+        .iter()
+        .flatten()
+        .map(|explained| explained.explanation.clone())
+        .collect();
+
     let tx = contracts
         .settlement()
         .settle(
             tokens,
             clearing_prices,
             trades.iter().map(codec::trade).collect(),
-            [
-                pre_interactions.iter().map(codec::interaction).collect(),
-                interactions.iter().map(codec::interaction).collect(),
-                post_interactions.iter().map(codec::interaction).collect(),
-            ],
+            phases.map(|phase| {
+                phase
+                    .iter()
+                    .map(|explained| codec::interaction(&explained.interaction))
+                    .collect()
+            }),
         )
         .into_inner();
 
@@ -215,15 +328,53 @@ pub fn tx(
     let mut calldata = tx.data.unwrap().0;
     calldata.extend(auction.id().ok_or(Error::MissingAuctionId)?.to_be_bytes());
 
-    Ok(eth::Tx {
+    let tx = eth::Tx {
         from: solution.solver().address(),
         to: contracts.settlement().address().into(),
         input: calldata.into(),
         value: Ether(0.into()),
         access_list: Default::default(),
+    };
+    Ok((tx, explanation))
+}
+
+/// The amount of the input token a liquidity interaction may take beyond its
+/// stated input, given the slippage it is encoded with.
+fn slippage_outflow(
+    liquidity: &Liquidity,
+    slippage: &slippage::Parameters,
+) -> Result<eth::Asset, Error> {
+    let (input, _) = slippage.apply_to(&slippage::Interaction {
+        input: liquidity.input,
+        output: liquidity.output,
+    })?;
+    Ok(eth::Asset {
+        token: input.0.token,
+        amount: input
+            .0
+            .amount
+            .0
+            .saturating_sub(liquidity.input.amount.0)
+            .into(),
     })
 }
 
+/// The slippage parameters liquidity interactions of the solution are encoded
+/// with.
+pub fn slippage(
+    auction: &competition::Auction,
+    solution: &super::Solution,
+) -> slippage::Parameters {
+    slippage::Parameters {
+        relative: solution.solver().slippage().relative.clone(),
+        tiers: solution.solver().slippage().tiers.clone(),
+        max: solution.solver().slippage().absolute.map(Ether::into),
+        // TODO configure min slippage
+        min: None,
+        prices: auction.prices().clone(),
+    }
+}
+
 pub fn liquidity_interaction(
     liquidity: &Liquidity,
     slippage: &slippage::Parameters,
@@ -274,6 +425,184 @@ pub fn approve(allowance: &Allowance) -> eth::Interaction {
     }
 }
 
+/// Encodes the approvals granted at the start of the settlement core and, if
+/// `revoke` is set, the interactions resetting them to zero again at its end.
+fn allowances(
+    approvals: impl Iterator<Item = eth::allowance::Approval>,
+    revoke: bool,
+) -> (Vec<Explained>, Vec<Explained>) {
+    let mut interactions = (Vec::new(), Vec::new());
+    for approval in approvals {
+        interactions.0.push(Explained {
+            interaction: approve(&approval.0),
+            explanation: explanation::explain_approval(&approval),
+        });
+        // Allowances reset to zero before being approved don't need revoking.
+        if revoke && !approval.0.amount.is_zero() {
+            interactions.1.push(Explained {
+                interaction: approve(&approval.revoke().0),
+                explanation: InteractionExplanation::new(
+                    Purpose::Revocation {
+                        spender: approval.0.spender,
+                    },
+                    Default::default(),
+                ),
+            });
+        }
+    }
+    interactions
+}
+
+/// A payment for the gas of a settlement, transferred to the relayer out of
+/// the settlement contract's buffers.
+#[derive(Debug, Clone, Copy)]
+pub struct GasPayment {
+    /// The transferred amount of the gas payment token.
+    pub asset: eth::Asset,
+    pub relayer: eth::Address,
+    /// The value of the transferred amount in the native token.
+    pub cost: eth::Ether,
+}
+
+/// The gas payment of a settlement of the solution, if the solver pays for
+/// gas in an ERC20 token.
+pub fn gas_payment(
+    auction: &competition::Auction,
+    solution: &super::Solution,
+) -> Result<Option<GasPayment>, Error> {
+    let Some(payment) = solution.solver().gas_payment() else {
+        return Ok(None);
+    };
+    let gas = solution.gas().ok_or(Error::MissingGasEstimate)?;
+    let price = auction
+        .tokens()
+        .get(payment.token)
+        .price
+        .ok_or(Error::MissingGasPaymentPrice(payment.token))?;
+    let cost = gas_cost(gas, auction.gas_price().effective())?;
+    Ok(Some(GasPayment {
+        asset: eth::Asset {
+            token: payment.token,
+            amount: price.from_eth(cost),
+        },
+        relayer: payment.relayer,
+        cost,
+    }))
+}
+
+/// The cost of `gas` at the given gas price.
+fn gas_cost(gas: eth::Gas, gas_price: eth::EffectiveGasPrice) -> Result<eth::Ether, Math> {
+    let cost = gas.0.checked_mul(gas_price.0 .0).ok_or(Math::Overflow)?;
+    Ok(cost.into())
+}
+
+/// An ERC20 transfer of `asset` from the settlement contract to `recipient`.
+fn transfer(asset: eth::Asset, recipient: eth::Address) -> eth::Interaction {
+    let mut encoded_amount = [0u8; 32];
+    let selector = hex_literal::hex!("a9059cbb");
+    asset.amount.0.to_big_endian(&mut encoded_amount);
+    eth::Interaction {
+        target: asset.token.0.into(),
+        value: eth::U256::zero().into(),
+        // selector (4 bytes) + recipient (20 byte address padded to 32 bytes) + amount (32
+        // bytes)
+        call_data: [
+            selector.as_slice(),
+            [0; 12].as_slice(),
+            recipient.0.as_bytes(),
+            &encoded_amount,
+        ]
+        .concat()
+        .into(),
+    }
+}
+
+/// The most the settlement contract's balance of a token may decrease by over
+/// a settlement.
+#[derive(Debug, PartialEq, Eq)]
+struct MaxOutflow {
+    token: eth::TokenAddress,
+    amount: eth::U256,
+}
+
+/// The maximum outflows of the traded tokens and of every token the settlement
+/// transfers out of the settlement contract.
+fn max_outflows(
+    tokens: impl IntoIterator<Item = eth::TokenAddress>,
+    outflows: impl IntoIterator<Item = eth::Asset>,
+) -> Vec<MaxOutflow> {
+    let outflows = outflows
+        .into_iter()
+        .into_grouping_map_by(|asset| asset.token)
+        .fold(eth::U256::zero(), |sum, _, asset| {
+            sum.saturating_add(asset.amount.0)
+        });
+    tokens
+        .into_iter()
+        .chain(outflows.keys().copied())
+        .sorted()
+        .dedup()
+        .map(|token| MaxOutflow {
+            token,
+            amount: outflows.get(&token).copied().unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// A call to the balance checker recording the balance `owner` holds of
+/// `token`, i.e. `snapshotBalance(address token, address owner)`.
+fn snapshot_balance(
+    checker: eth::ContractAddress,
+    token: eth::TokenAddress,
+    owner: eth::Address,
+) -> eth::Interaction {
+    let selector = hex_literal::hex!("48476c71");
+    eth::Interaction {
+        target: checker.into(),
+        value: eth::U256::zero().into(),
+        // selector (4 bytes) + token and owner (20 byte addresses padded to 32 bytes)
+        call_data: [
+            selector.as_slice(),
+            [0; 12].as_slice(),
+            token.0 .0.as_bytes(),
+            [0; 12].as_slice(),
+            owner.0.as_bytes(),
+        ]
+        .concat()
+        .into(),
+    }
+}
+
+/// A call to the balance checker reverting if the balance `owner` holds of
+/// `token` decreased by more than `max_decrease` since it was snapshotted, i.e.
+/// `assertMaxDecrease(address token, address owner, uint256 maxDecrease)`.
+fn assert_max_decrease(
+    checker: eth::ContractAddress,
+    token: eth::TokenAddress,
+    owner: eth::Address,
+    max_decrease: eth::U256,
+) -> eth::Interaction {
+    let mut encoded_max_decrease = [0u8; 32];
+    let selector = hex_literal::hex!("e52ed15e");
+    max_decrease.to_big_endian(&mut encoded_max_decrease);
+    eth::Interaction {
+        target: checker.into(),
+        value: eth::U256::zero().into(),
+        // selector (4 bytes) + token and owner (20 byte addresses padded to 32 bytes) +
+        // max decrease (32 bytes)
+        call_data: [
+            selector.as_slice(),
+            [0; 12].as_slice(),
+            token.0 .0.as_bytes(),
+            [0; 12].as_slice(),
+            owner.0.as_bytes(),
+            &encoded_max_decrease,
+        ]
+        .concat()
+        .into(),
+    }
+}
+
 fn unwrap(amount: eth::TokenAmount, weth: &contracts::WETH9) -> eth::Interaction {
     let tx = weth.withdraw(amount.into()).into_inner();
     eth::Interaction {
@@ -283,6 +612,49 @@ fn unwrap(amount: eth::TokenAmount, weth: &contracts::WETH9) -> eth::Interaction
     }
 }
 
+/// An encoded interaction together with the explanation of what it does.
+#[derive(Debug, Clone, PartialEq)]
+struct Explained {
+    interaction: eth::Interaction,
+    explanation: InteractionExplanation,
+}
+
+impl Explained {
+    /// A hook or other raw interaction for which nothing is known besides its
+    /// target.
+    fn call(interaction: &eth::Interaction) -> Self {
+        Self {
+            interaction: interaction.clone(),
+            explanation: explanation::explain_call(interaction),
+        }
+    }
+}
+
+/// Interactions executed before and after the core interactions of a
+/// settlement. Solver provided interactions run first, followed by the hooks
+/// which users attached to their orders via the app data.
+#[derive(Debug, Default)]
+struct Hooks {
+    pre: Vec<Explained>,
+    post: Vec<Explained>,
+}
+
+impl Hooks {
+    /// Adds the pre- and post-interaction hooks of a settled order.
+    fn add(&mut self, order: &competition::Order) {
+        self.pre
+            .extend(order.pre_interactions.iter().map(Explained::call));
+        self.post
+            .extend(order.post_interactions.iter().map(Explained::call));
+    }
+
+    /// Surrounds the core interactions with the hooks, yielding the three
+    /// interaction phases in the order expected by the settlement contract.
+    fn wrap(self, interactions: Vec<Explained>) -> [Vec<Explained>; 3] {
+        [self.pre, interactions, self.post]
+    }
+}
+
 struct Trade {
     sell_token_index: eth::U256,
     buy_token_index: eth::U256,
@@ -409,7 +781,7 @@ mod codec {
 
 #[cfg(test)]
 mod test {
-    use {super::*, hex_literal::hex};
+    use {super::*, hex_literal::hex, num::BigRational};
 
     #[test]
     fn test_approve() {
@@ -426,4 +798,284 @@ mod test {
         );
         assert_eq!(interaction.call_data.0.as_slice(), hex!("095ea7b3000000000000000000000000000000000022d473030f116ddee9f6b43ac78ba3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"));
     }
+
+    #[test]
+    fn test_approval_modes() {
+        let token = |address: u64| eth::TokenAddress::from(eth::H160::from_low_u64_be(address));
+        let asset = |token, amount: u64| eth::Asset {
+            token,
+            amount: eth::U256::from(amount).into(),
+        };
+        let router = eth::H160::from_low_u64_be(0x5017);
+        let interaction = competition::solution::Interaction::Liquidity(Liquidity {
+            liquidity: liquidity::Liquidity {
+                id: liquidity::Id(0),
+                gas: eth::U256::from(100_000).into(),
+                kind: liquidity::Kind::UniswapV2(liquidity::uniswap::v2::Pool {
+                    address: eth::H160::from_low_u64_be(0x9001).into(),
+                    router: router.into(),
+                    reserves: liquidity::uniswap::v2::Reserves::new(
+                        asset(token(1), 1_000_000),
+                        asset(token(2), 1_000_000),
+                    )
+                    .unwrap(),
+                }),
+                fetched_at: None,
+            },
+            input: asset(token(1), 100),
+            output: asset(token(2), 99),
+            internalize: false,
+        });
+        let slippage = slippage::Parameters {
+            relative: BigRational::new(1.into(), 10.into()),
+            tiers: Default::default(),
+            max: None,
+            min: None,
+            prices: Default::default(),
+        };
+        let approved = |mode| {
+            let approvals = interaction.allowances(mode, &slippage).unwrap();
+            assert_eq!(approvals.len(), 1);
+            let call = approve(&approvals[0].0);
+            assert_eq!(call.target, eth::H160::from_low_u64_be(1).into());
+            eth::U256::from_big_endian(&call.call_data.0[36..])
+        };
+
+        assert_eq!(
+            approved(allowance::ApprovalMode::Max),
+            eth::U256::max_value()
+        );
+
+        // Exact approvals cover the maximum input the swap is encoded with,
+        // i.e. `swapTokensForExactTokens(amountOut, amountInMax, ...)`.
+        let competition::solution::Interaction::Liquidity(liquidity) = &interaction else {
+            unreachable!()
+        };
+        let settlement = contracts::GPv2Settlement::at(
+            &ethrpc::dummy::web3(),
+            eth::H160::from_low_u64_be(0x9008),
+        );
+        let swap = liquidity_interaction(liquidity, &slippage, &settlement).unwrap();
+        let max_input = eth::U256::from_big_endian(&swap.call_data.0[36..68]);
+        assert_eq!(max_input, eth::U256::from(110));
+        assert_eq!(approved(allowance::ApprovalMode::Exact), max_input);
+    }
+
+    #[test]
+    fn test_approval_revocations() {
+        let approval = |token: u64, spender: u64| {
+            allowance::Approval(Allowance {
+                token: eth::H160::from_low_u64_be(token).into(),
+                spender: eth::H160::from_low_u64_be(spender).into(),
+                amount: eth::U256::max_value(),
+            })
+        };
+        let granted = [approval(1, 2), approval(3, 4)];
+
+        let (approvals, revocations) = allowances(granted.into_iter(), false);
+        assert_eq!(approvals.len(), 2);
+        assert!(revocations.is_empty());
+
+        // Every granted approval is reset to zero by a matching revocation.
+        let (approvals, revocations) = allowances(granted.into_iter(), true);
+        assert_eq!(
+            approvals,
+            granted
+                .iter()
+                .map(|a| Explained {
+                    interaction: approve(&a.0),
+                    explanation: InteractionExplanation::new(
+                        Purpose::Approval {
+                            spender: a.0.spender,
+                        },
+                        vec![],
+                    ),
+                })
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            revocations,
+            granted
+                .iter()
+                .map(|a| Explained {
+                    interaction: approve(&Allowance {
+                        amount: eth::U256::zero(),
+                        ..a.0
+                    }),
+                    explanation: InteractionExplanation::new(
+                        Purpose::Revocation {
+                            spender: a.0.spender,
+                        },
+                        vec![],
+                    ),
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_gas_payment() {
+        let payment = infra::solver::GasPayment {
+            token: eth::H160::from_slice(&hex!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")).into(),
+            relayer: eth::H160::from_slice(&hex!("000000000022D473030F116dDEE9F6B43aC78BA3"))
+                .into(),
+        };
+        // 100k gas at 10 gwei cost 0.001 ETH, which is 0.002 of a token worth
+        // 0.5 ETH.
+        let cost = gas_cost(eth::Gas(100_000.into()), eth::U256::exp10(10).into()).unwrap();
+        assert_eq!(cost, eth::U256::exp10(15).into());
+        let amount = competition::auction::Price::new((eth::U256::exp10(18) / 2).into())
+            .unwrap()
+            .from_eth(cost);
+        assert_eq!(amount, eth::U256::from(2_000_000_000_000_000_u64).into());
+
+        let interaction = transfer(
+            eth::Asset {
+                token: payment.token,
+                amount,
+            },
+            payment.relayer,
+        );
+        assert_eq!(
+            interaction.target,
+            eth::H160::from_slice(&hex!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")).into(),
+        );
+        assert_eq!(interaction.value, eth::U256::zero().into());
+        assert_eq!(interaction.call_data.0.as_slice(), hex!("a9059cbb000000000000000000000000000000000022d473030f116ddee9f6b43ac78ba300000000000000000000000000000000000000000000000000071afd498d0000"));
+    }
+
+    #[test]
+    fn test_partner_fee() {
+        let partner: eth::Address =
+            eth::H160::from_slice(&hex!("000000000022D473030F116dDEE9F6B43aC78BA3")).into();
+        let asset = |token: u64, amount: u64| eth::Asset {
+            token: eth::H160::from_low_u64_be(token).into(),
+            amount: eth::U256::from(amount).into(),
+        };
+        // Selling 1000 A for 800 B after a partner fee of 20% followed by a
+        // protocol fee of 20% of the volume.
+        let trade = super::super::scoring::Trade::new(
+            asset(0xa, 1_000),
+            asset(0xb, 500),
+            order::Side::Sell,
+            eth::U256::from(1_000).into(),
+            super::super::trade::CustomClearingPrices {
+                sell: 800.into(),
+                buy: 1_000.into(),
+            },
+            vec![
+                order::FeePolicy::Volume {
+                    factor: 0.2,
+                    recipient: None,
+                },
+                order::FeePolicy::Volume {
+                    factor: 0.2,
+                    recipient: Some(partner),
+                },
+            ],
+        );
+
+        // Only the partner fee of 200 B is paid out, the protocol fee stays in
+        // the settlement contract.
+        let fees = trade.partner_fees().unwrap();
+        assert_eq!(fees, vec![(partner, asset(0xb, 200))]);
+
+        let interaction = transfer(fees[0].1, fees[0].0);
+        assert_eq!(interaction.target, eth::H160::from_low_u64_be(0xb).into());
+        assert_eq!(interaction.value, eth::U256::zero().into());
+        assert_eq!(interaction.call_data.0.as_slice(), hex!("a9059cbb000000000000000000000000000000000022d473030f116ddee9f6b43ac78ba300000000000000000000000000000000000000000000000000000000000000c8"));
+    }
+
+    #[test]
+    fn test_balance_assertions() {
+        let token = |byte: u64| eth::TokenAddress::from(eth::H160::from_low_u64_be(byte));
+        let asset = |byte: u64, amount: u64| eth::Asset {
+            token: token(byte),
+            amount: eth::U256::from(amount).into(),
+        };
+
+        // Internalized interactions and a fee transfer take 200 of token B out
+        // of the settlement contract, the gas payment 10 of token D which isn't
+        // traded. Token A may not decrease at all.
+        let outflows = max_outflows(
+            [token(0xb), token(0xa), token(0xb)],
+            [asset(0xb, 150), asset(0xb, 50), asset(0xd, 10)],
+        );
+        assert_eq!(
+            outflows,
+            vec![
+                MaxOutflow {
+                    token: token(0xa),
+                    amount: 0.into(),
+                },
+                MaxOutflow {
+                    token: token(0xb),
+                    amount: 200.into(),
+                },
+                MaxOutflow {
+                    token: token(0xd),
+                    amount: 10.into(),
+                },
+            ]
+        );
+
+        let checker = eth::ContractAddress(eth::H160::from_low_u64_be(0xc4ec));
+        let settlement =
+            eth::H160::from_slice(&hex!("9008D19f58AAbD9eD0D60971565AA8510560ab41")).into();
+        let snapshot = snapshot_balance(checker, token(0xb), settlement);
+        assert_eq!(snapshot.target, checker.0.into());
+        assert_eq!(snapshot.value, eth::U256::zero().into());
+        assert_eq!(snapshot.call_data.0.as_slice(), hex!("48476c71000000000000000000000000000000000000000000000000000000000000000b0000000000000000000000009008d19f58aabd9ed0d60971565aa8510560ab41"));
+
+        let assertion = assert_max_decrease(checker, token(0xb), settlement, outflows[1].amount);
+        assert_eq!(assertion.target, checker.0.into());
+        assert_eq!(assertion.value, eth::U256::zero().into());
+        assert_eq!(assertion.call_data.0.as_slice(), hex!("e52ed15e000000000000000000000000000000000000000000000000000000000000000b0000000000000000000000009008d19f58aabd9ed0d60971565aa8510560ab4100000000000000000000000000000000000000000000000000000000000000c8"));
+    }
+
+    #[test]
+    fn test_order_hooks() {
+        let interaction = |target: u64| eth::Interaction {
+            target: eth::H160::from_low_u64_be(target).into(),
+            value: eth::U256::zero().into(),
+            call_data: Default::default(),
+        };
+        let order = competition::Order {
+            uid: Default::default(),
+            receiver: None,
+            valid_to: crate::util::Timestamp(u32::MAX),
+            buy: eth::Asset {
+                token: eth::H160::from_low_u64_be(0xbbbb).into(),
+                amount: eth::U256::from(100).into(),
+            },
+            sell: eth::Asset {
+                token: eth::H160::from_low_u64_be(0x5e11).into(),
+                amount: eth::U256::from(100).into(),
+            },
+            side: order::Side::Sell,
+            kind: order::Kind::Market,
+            app_data: Default::default(),
+            partial: Partial::No,
+            pre_interactions: vec![interaction(1)],
+            post_interactions: vec![interaction(2)],
+            sell_token_balance: order::SellTokenBalance::Erc20,
+            buy_token_balance: order::BuyTokenBalance::Erc20,
+            signature: order::Signature {
+                scheme: order::signature::Scheme::PreSign,
+                data: Default::default(),
+                signer: Default::default(),
+            },
+            protocol_fees: Default::default(),
+        };
+
+        let mut hooks = Hooks::default();
+        hooks.add(&order);
+        let [pre, core, post] = hooks.wrap(vec![Explained::call(&interaction(3))]);
+
+        // The order's pre-hook is encoded first, before the settlement core,
+        // and its post-hook after it.
+        assert_eq!(pre, vec![Explained::call(&interaction(1))]);
+        assert_eq!(core, vec![Explained::call(&interaction(3))]);
+        assert_eq!(post, vec![Explained::call(&interaction(2))]);
+    }
 }
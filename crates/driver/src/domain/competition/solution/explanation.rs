@@ -0,0 +1,236 @@
+use {
+    super::Interaction,
+    crate::domain::{eth, liquidity},
+};
+
+/// A human-readable description of a single interaction executed as part of a
+/// settlement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InteractionExplanation {
+    pub purpose: Purpose,
+    /// The tokens taken from the settlement contract by the interaction.
+    pub inputs: Vec<eth::TokenAddress>,
+    /// The tokens sent into the settlement contract by the interaction.
+    pub outputs: Vec<eth::TokenAddress>,
+    /// Whether the interaction is omitted from the settlement and executed
+    /// using the settlement contract's buffers instead.
+    pub internalized: bool,
+}
+
+/// What an interaction does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Purpose {
+    /// An ERC20 approval for the given spender.
+    Approval { spender: eth::Address },
+    /// A swap using one of the liquidity sources indexed by the driver.
+    Swap {
+        liquidity: liquidity::Id,
+        kind: &'static str,
+        /// The block the liquidity's state was fetched at, if known.
+        fetched_at: Option<eth::BlockNo>,
+    },
+    /// Wrapping native ETH into WETH.
+    Wrap,
+    /// Unwrapping WETH into native ETH.
+    Unwrap,
+    /// An arbitrary call to the given target, e.g. a hook or solver provided
+    /// interaction.
+    Custom { target: eth::Address },
+    /// Resetting an ERC20 approval granted for the settlement back to zero.
+    Revocation { spender: eth::Address },
+    /// Paying out a partner fee to the integrator.
+    PartnerFee { recipient: eth::Address },
+    /// Paying the relayer for the gas of the settlement.
+    GasPayment { relayer: eth::Address },
+    /// Recording the settlement contract's balance of a token before the
+    /// settlement.
+    BalanceSnapshot { token: eth::TokenAddress },
+    /// Asserting that the settlement contract's balance of a token didn't
+    /// decrease by more than `max_decrease` over the settlement.
+    BalanceAssertion {
+        token: eth::TokenAddress,
+        max_decrease: eth::U256,
+    },
+}
+
+impl InteractionExplanation {
+    /// Explains an uninternalized interaction which takes `inputs` from the
+    /// settlement contract without sending any tokens back.
+    pub fn new(purpose: Purpose, inputs: Vec<eth::TokenAddress>) -> Self {
+        Self {
+            purpose,
+            inputs,
+            outputs: Default::default(),
+            internalized: false,
+        }
+    }
+}
+
+/// Explains a hook or other raw interaction for which nothing is known besides
+/// its target.
+pub fn explain_call(interaction: &eth::Interaction) -> InteractionExplanation {
+    InteractionExplanation::new(
+        Purpose::Custom {
+            target: interaction.target,
+        },
+        Default::default(),
+    )
+}
+
+/// Explains an ERC20 approval granted at the start of the settlement.
+pub fn explain_approval(approval: &eth::allowance::Approval) -> InteractionExplanation {
+    InteractionExplanation::new(
+        Purpose::Approval {
+            spender: approval.0.spender,
+        },
+        Default::default(),
+    )
+}
+
+/// Explains the WETH unwrap inserted for orders buying ETH.
+pub fn explain_unwrap(weth: eth::WethAddress) -> InteractionExplanation {
+    InteractionExplanation {
+        purpose: Purpose::Unwrap,
+        inputs: vec![weth.into()],
+        outputs: vec![eth::ETH_TOKEN],
+        internalized: false,
+    }
+}
+
+/// Explains a solver provided interaction.
+pub fn explain_interaction(
+    interaction: &Interaction,
+    weth: eth::WethAddress,
+) -> InteractionExplanation {
+    let tokens = |assets: Vec<eth::Asset>| assets.into_iter().map(|asset| asset.token).collect();
+    let inputs: Vec<eth::TokenAddress> = tokens(interaction.inputs());
+    let outputs: Vec<eth::TokenAddress> = tokens(interaction.outputs());
+    let weth = eth::TokenAddress::from(weth);
+
+    let purpose = match interaction {
+        Interaction::Liquidity(interaction) => Purpose::Swap {
+            liquidity: interaction.liquidity.id,
+            kind: (&interaction.liquidity.kind).into(),
+            fetched_at: interaction.fetched_at(),
+        },
+        Interaction::Custom(custom) if custom.target == weth.0 => {
+            if inputs == [eth::ETH_TOKEN] && outputs == [weth] {
+                Purpose::Wrap
+            } else if inputs == [weth] && outputs == [eth::ETH_TOKEN] {
+                Purpose::Unwrap
+            } else {
+                Purpose::Custom {
+                    target: custom.target.into(),
+                }
+            }
+        }
+        Interaction::Custom(custom) => Purpose::Custom {
+            target: custom.target.into(),
+        },
+    };
+
+    InteractionExplanation {
+        purpose,
+        inputs,
+        outputs,
+        internalized: interaction.internalize(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::domain::competition::solution::interaction};
+
+    fn token(address: u64) -> eth::TokenAddress {
+        eth::H160::from_low_u64_be(address).into()
+    }
+
+    fn asset(token: eth::TokenAddress) -> eth::Asset {
+        eth::Asset {
+            token,
+            amount: eth::U256::from(100).into(),
+        }
+    }
+
+    #[test]
+    fn explains_approval_swap_and_wrap() {
+        let weth = eth::WethAddress(token(0xeeee));
+        let usdc = token(0x05dc);
+        let router = eth::H160::from_low_u64_be(0x5017);
+
+        let approvals = [eth::allowance::Approval(eth::Allowance {
+            token: weth.into(),
+            spender: router.into(),
+            amount: eth::U256::max_value(),
+        })];
+        let interactions = [
+            Interaction::Custom(interaction::Custom {
+                target: weth.0 .0,
+                value: eth::U256::from(100).into(),
+                call_data: Default::default(),
+                allowances: Default::default(),
+                inputs: vec![asset(eth::ETH_TOKEN)],
+                outputs: vec![asset(weth.into())],
+                internalize: false,
+            }),
+            Interaction::Liquidity(interaction::Liquidity {
+                liquidity: liquidity::Liquidity {
+                    id: liquidity::Id(7),
+                    gas: eth::U256::from(100_000).into(),
+                    kind: liquidity::Kind::UniswapV2(liquidity::uniswap::v2::Pool {
+                        address: eth::H160::from_low_u64_be(0x9001).into(),
+                        router: router.into(),
+                        reserves: liquidity::uniswap::v2::Reserves::new(
+                            asset(weth.into()),
+                            asset(usdc),
+                        )
+                        .unwrap(),
+                    }),
+                    fetched_at: Some(eth::BlockNo(17)),
+                },
+                input: asset(weth.into()),
+                output: asset(usdc),
+                internalize: true,
+            }),
+        ];
+
+        let explanation = approvals
+            .iter()
+            .map(explain_approval)
+            .chain(
+                interactions
+                    .iter()
+                    .map(|interaction| explain_interaction(interaction, weth)),
+            )
+            .collect::<Vec<_>>();
+        assert_eq!(
+            explanation,
+            vec![
+                InteractionExplanation {
+                    purpose: Purpose::Approval {
+                        spender: router.into()
+                    },
+                    inputs: vec![],
+                    outputs: vec![],
+                    internalized: false,
+                },
+                InteractionExplanation {
+                    purpose: Purpose::Wrap,
+                    inputs: vec![eth::ETH_TOKEN],
+                    outputs: vec![weth.into()],
+                    internalized: false,
+                },
+                InteractionExplanation {
+                    purpose: Purpose::Swap {
+                        liquidity: liquidity::Id(7),
+                        kind: "UniswapV2",
+                        fetched_at: Some(eth::BlockNo(17)),
+                    },
+                    inputs: vec![weth.into()],
+                    outputs: vec![usdc],
+                    internalized: true,
+                },
+            ]
+        );
+    }
+}
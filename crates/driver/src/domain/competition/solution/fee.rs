@@ -126,7 +126,7 @@ impl Fulfillment {
                 )?;
                 self.calculate_fee(price_limits, prices, *factor, *max_volume_factor)
             }
-            FeePolicy::Volume { factor } => self.fee_from_volume(prices, *factor),
+            FeePolicy::Volume { factor, .. } => self.fee_from_volume(prices, *factor),
         }
     }
 
@@ -1,6 +1,9 @@
-use crate::{
-    domain::{self, eth, liquidity},
-    util::Bytes,
+use {
+    super::{error::Math, slippage},
+    crate::{
+        domain::{self, eth, liquidity},
+        util::Bytes,
+    },
 };
 
 /// Interaction with a smart contract which is needed to execute this solution
@@ -40,9 +43,15 @@ impl Interaction {
     }
 
     /// Returns the ERC20 approvals required for executing this interaction
-    /// onchain.
-    pub fn allowances(&self) -> Vec<eth::allowance::Required> {
-        match self {
+    /// onchain. Allowances of custom interactions are always the ones
+    /// requested by the solver. Exact approvals for liquidity cover the
+    /// maximum input the swap is encoded with, i.e. including `slippage`.
+    pub fn allowances(
+        &self,
+        mode: eth::allowance::ApprovalMode,
+        slippage: &slippage::Parameters,
+    ) -> Result<Vec<eth::allowance::Required>, Math> {
+        Ok(match self {
             Interaction::Custom(interaction) => interaction.allowances.clone(),
             Interaction::Liquidity(interaction) => {
                 let address = match &interaction.liquidity.kind {
@@ -53,19 +62,29 @@ impl Interaction {
                     liquidity::Kind::Swapr(pool) => pool.base.router.into(),
                     liquidity::Kind::ZeroEx(pool) => pool.zeroex.address().into(),
                 };
-                // As a gas optimization, we always approve the max amount possible. This
+                // As a gas optimization, we approve the max amount possible by default. This
                 // minimizes the number of approvals necessary, and therefore
                 // minimizes the approval fees over time. This is a
                 // potential security issue, but we assume that the router contract for protocol
-                // indexed liquidity to be safe.
+                // indexed liquidity to be safe unless exact approvals are configured.
+                let amount = match mode {
+                    eth::allowance::ApprovalMode::Max => eth::U256::max_value(),
+                    eth::allowance::ApprovalMode::Exact => {
+                        let (input, _) = slippage.apply_to(&slippage::Interaction {
+                            input: interaction.input,
+                            output: interaction.output,
+                        })?;
+                        input.0.amount.0
+                    }
+                };
                 vec![eth::Allowance {
                     token: interaction.input.token,
                     spender: address,
-                    amount: eth::U256::max_value(),
+                    amount,
                 }
                 .into()]
             }
-        }
+        })
     }
 }
 
@@ -98,3 +117,11 @@ pub struct Liquidity {
     /// settlement contract?
     pub internalize: bool,
 }
+
+impl Liquidity {
+    /// The block the state of the used liquidity was fetched at, if known.
+    /// Indicates how stale the state the interaction was computed with may be.
+    pub fn fetched_at(&self) -> Option<eth::BlockNo> {
+        self.liquidity.fetched_at
+    }
+}
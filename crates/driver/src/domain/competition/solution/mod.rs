@@ -6,12 +6,13 @@ use {
         domain::{
             competition::{self, order},
             eth::{self, TokenAddress},
+            liquidity,
         },
         infra::{
             blockchain::{self, Ethereum},
             config::file::FeeHandler,
             simulator,
-            solver::{ManageNativeToken, Solver},
+            solver::{self, ManageNativeToken, Solver},
             Simulator,
         },
     },
@@ -22,7 +23,9 @@ use {
     thiserror::Error,
 };
 
+pub mod calldata;
 pub mod encoding;
+pub mod explanation;
 pub mod fee;
 pub mod interaction;
 pub mod scoring;
@@ -81,7 +84,7 @@ impl Solution {
                 Fulfillment::new(
                     competition::Order {
                         uid: jit.order().uid,
-                        kind: order::Kind::Limit,
+                        kind: order::Kind::Limit { oracle: None },
                         side: jit.order().side,
                         sell: jit.order().sell,
                         buy: jit.order().buy,
@@ -156,11 +159,38 @@ impl Solution {
         Ok(Self { trades, ..solution })
     }
 
+    /// Parses solutions in the JSON format emitted by solver engines (i.e.
+    /// `{"solutions": [...]}`) into solutions for the given auction. This
+    /// allows feeding solutions into encoding and scoring without a live
+    /// solver engine.
+    pub fn from_engine_json(
+        value: serde_json::Value,
+        auction: &competition::Auction,
+        liquidity: &[liquidity::Liquidity],
+        solver: &Solver,
+    ) -> Result<Vec<Self>, solver::Error> {
+        solver.solutions_from_json(value, auction, liquidity)
+    }
+
     /// The ID of this solution.
     pub fn id(&self) -> &Id {
         &self.id
     }
 
+    /// A deterministic ID derived from the trades, clearing prices and
+    /// interactions of this solution. Unlike [`Self::id`], it is the same for
+    /// identical solutions across rounds, which makes it easy to spot a solver
+    /// returning the same solution repeatedly.
+    pub fn content_id(&self) -> eth::H256 {
+        content_id(
+            &self.trades,
+            &self.prices,
+            &self.pre_interactions,
+            &self.interactions,
+            &self.post_interactions,
+        )
+    }
+
     /// Trades settled by this solution.
     pub fn trades(&self) -> &[Trade] {
         &self.trades
@@ -171,6 +201,58 @@ impl Solution {
         &self.interactions
     }
 
+    /// A token whose clearing price is inconsistent with the auction's
+    /// reference prices, i.e. relative to the other tokens it is priced more
+    /// than `max_deviation` times higher or lower than the reference prices
+    /// suggest. Tokens without a reference price are ignored.
+    pub fn mispriced_token(
+        &self,
+        reference: &auction::Prices,
+        max_deviation: f64,
+    ) -> Option<eth::TokenAddress> {
+        price_outlier(
+            self.prices.iter().filter_map(|(token, clearing)| {
+                Some((*token, *clearing, (*reference.get(token)?).into()))
+            }),
+            max_deviation,
+        )
+    }
+
+    /// Every address a settlement of this solution pays out to or calls into:
+    /// the owners and receivers of all traded orders, the targets of their
+    /// hooks and the targets of all solver provided interactions. Interactions
+    /// with liquidity indexed by the driver are not included.
+    pub fn addresses_involved(&self) -> HashSet<eth::Address> {
+        let orders = self.trades.iter().flat_map(|trade| match trade {
+            Trade::Fulfillment(fulfillment) => {
+                let order = fulfillment.order();
+                let hooks = order
+                    .pre_interactions
+                    .iter()
+                    .chain(&order.post_interactions)
+                    .map(|interaction| interaction.target);
+                [order.signature.signer, order.receiver()]
+                    .into_iter()
+                    .chain(hooks)
+                    .collect_vec()
+            }
+            Trade::Jit(jit) => vec![jit.order().signature.signer, jit.order().receiver],
+        });
+        let hooks = self
+            .pre_interactions
+            .iter()
+            .chain(&self.post_interactions)
+            .map(|interaction| interaction.target);
+        let interactions = self
+            .interactions
+            .iter()
+            .filter_map(|interaction| match interaction {
+                Interaction::Custom(custom) => Some(custom.target.into()),
+                Interaction::Liquidity(_) => None,
+            });
+        orders.chain(hooks).chain(interactions).collect()
+    }
+
     /// The solver which generated this solution.
     pub fn solver(&self) -> &Solver {
         &self.solver
@@ -206,57 +288,139 @@ impl Solution {
         for trade in self.trades().iter().filter(|trade| {
             self.trade_count_for_scorable(trade, surplus_capturing_jit_order_owners)
         }) {
-            // Solver generated fulfillment does not include the fee in the executed amount
-            // for sell orders.
-            let executed = match trade.side() {
-                order::Side::Sell => (trade.executed().0 + trade.fee().0).into(),
-                order::Side::Buy => trade.executed(),
-            };
-            let buy = trade.buy();
-            let sell = trade.sell();
-            let uniform_prices = ClearingPrices {
-                sell: self
-                    .clearing_price(sell.token)
-                    .ok_or(error::Scoring::InvalidClearingPrices)?,
-                buy: self
-                    .clearing_price(buy.token)
-                    .ok_or(error::Scoring::InvalidClearingPrices)?,
-            };
-            trades.push(scoring::Trade::new(
-                sell,
-                buy,
-                trade.side(),
-                executed,
-                trade.custom_prices(&uniform_prices)?,
-                trade.protocol_fees(),
-            ))
+            trades.push(self.scoring_trade(trade)?)
         }
 
         let scoring = scoring::Scoring::new(trades);
         scoring.score(prices).map_err(error::Scoring::from)
     }
 
+    /// The surplus of every user order settled by this solution, denominated
+    /// in the order's surplus token.
+    pub fn surpluses(&self) -> HashMap<order::Uid, eth::Asset> {
+        let mut surpluses = HashMap::new();
+        for trade in &self.trades {
+            let Trade::Fulfillment(fulfillment) = trade else {
+                continue;
+            };
+            if fulfillment.order().kind == order::Kind::Liquidity {
+                continue;
+            }
+            match self
+                .scoring_trade(trade)
+                .and_then(|trade| trade.surplus().map_err(error::Scoring::from))
+            {
+                Ok(surplus) => {
+                    surpluses.insert(fulfillment.order().uid, surplus);
+                }
+                // This should never happen for a solution that was scored, so skipping the
+                // order is better than failing the reveal, but we should still alert.
+                Err(err) => tracing::error!(?trade, ?err, "could not compute surplus"),
+            }
+        }
+        surpluses
+    }
+
+    /// The partner fees charged by the user orders settled by this solution
+    /// and the integrators they are paid out to.
+    pub fn partner_fees(&self) -> Result<Vec<(eth::Address, eth::Asset)>, error::Scoring> {
+        let mut fees = Vec::new();
+        for trade in &self.trades {
+            let Trade::Fulfillment(fulfillment) = trade else {
+                continue;
+            };
+            if fulfillment.order().kind == order::Kind::Liquidity {
+                continue;
+            }
+            for (recipient, fee) in self.scoring_trade(trade)?.partner_fees()? {
+                if fee.amount.0.is_zero() {
+                    continue;
+                }
+                // The settlement contract holds native token proceeds as WETH until they are
+                // unwrapped for the user.
+                fees.push((
+                    recipient,
+                    eth::Asset {
+                        token: fee.token.wrap(self.weth),
+                        amount: fee.amount,
+                    },
+                ));
+            }
+        }
+        Ok(fees)
+    }
+
+    /// The trade with the values expected by the settlement contract, as used
+    /// for scoring.
+    fn scoring_trade(&self, trade: &Trade) -> Result<scoring::Trade, error::Scoring> {
+        // Solver generated fulfillment does not include the fee in the executed amount
+        // for sell orders.
+        let executed = match trade.side() {
+            order::Side::Sell => (trade.executed().0 + trade.fee().0).into(),
+            order::Side::Buy => trade.executed(),
+        };
+        let buy = trade.buy();
+        let sell = trade.sell();
+        let uniform_prices = ClearingPrices {
+            sell: self
+                .clearing_price(sell.token)
+                .ok_or(error::Scoring::InvalidClearingPrices)?,
+            buy: self
+                .clearing_price(buy.token)
+                .ok_or(error::Scoring::InvalidClearingPrices)?,
+        };
+        Ok(scoring::Trade::new(
+            sell,
+            buy,
+            trade.side(),
+            executed,
+            trade.custom_prices(&uniform_prices)?,
+            trade.protocol_fees(),
+        ))
+    }
+
     /// Approval interactions necessary for encoding the settlement.
     pub async fn approvals(
         &self,
         eth: &Ethereum,
+        auction: &competition::Auction,
         internalization: settlement::Internalization,
+        mode: eth::allowance::ApprovalMode,
     ) -> Result<impl Iterator<Item = eth::allowance::Approval>, Error> {
         let settlement_contract = &eth.contracts().settlement();
-        let allowances =
-            try_join_all(self.allowances(internalization).map(|required| async move {
-                eth.erc20(required.0.token)
-                    .allowance(settlement_contract.address().into(), required.0.spender)
-                    .await
-                    .map(|existing| (required, existing))
-            }))
-            .await?;
+        let slippage = encoding::slippage(auction, self);
+        let allowances = self
+            .allowances(internalization, mode, &slippage)
+            .map_err(encoding::Error::from)?;
+        let allowances = try_join_all(allowances.map(|required| async move {
+            eth.erc20(required.0.token)
+                .allowance(settlement_contract.address().into(), required.0.spender)
+                .await
+                .map(|existing| (required, existing))
+        }))
+        .await?;
+        let reset_tokens = self.solver().reset_approval_tokens().clone();
         let approvals = allowances
             .into_iter()
-            .filter_map(|(required, existing)| required.approval(&existing));
+            .flat_map(move |(required, existing)| {
+                required.approval(&existing, reset_tokens.contains(&required.0.token))
+            });
         Ok(approvals)
     }
 
+    /// Whether the solution contains more JIT orders than the solver is
+    /// allowed to settle at once. Surplus capturing JIT orders are settled
+    /// like user orders and don't count towards the limit.
+    pub fn exceeds_max_jit_orders(&self) -> bool {
+        self.solver.max_jit_orders().is_some_and(|max| {
+            self.trades
+                .iter()
+                .filter(|trade| matches!(trade, Trade::Jit(_)))
+                .count()
+                > max
+        })
+    }
+
     /// An empty solution has no trades which is allowed to capture surplus and
     /// a score of 0.
     pub fn is_empty(&self, surplus_capturing_jit_order_owners: &HashSet<eth::Address>) -> bool {
@@ -281,8 +445,8 @@ impl Solution {
 
         // Solution prices need to be congruent, i.e. there needs to be a unique factor
         // to scale all common tokens from one solution into the other.
-        let factor =
-            scaling_factor(&self.prices, &other.prices).ok_or(error::Merge::IncongruentPrices)?;
+        let factor = scaling_factor(&self.prices, &other.prices)
+            .map_err(|token| error::Merge::PriceMismatch { token })?;
 
         // To avoid precision issues, make sure we always scale up settlements
         if factor < BigRational::one() {
@@ -301,7 +465,7 @@ impl Solution {
                     // This shouldn't fail unless there are rounding errors given that the scaling
                     // factor is unique
                     if *entry.get() != scaled {
-                        return Err(error::Merge::IncongruentPrices);
+                        return Err(error::Merge::PriceMismatch { token: *token });
                     }
                 }
                 Entry::Vacant(entry) => {
@@ -311,7 +475,7 @@ impl Solution {
         }
 
         // Merge remaining fields
-        Ok(Solution {
+        let merged = Solution {
             id: Id::Merged([self.id.ids(), other.id.ids()].concat()),
             trades: [self.trades.clone(), other.trades.clone()].concat(),
             prices,
@@ -335,7 +499,11 @@ impl Solution {
                 (None, Some(gas)) => Some(gas),
                 (None, None) => None,
             },
-        })
+        };
+        if merged.exceeds_max_jit_orders() {
+            return Err(error::Merge::TooManyJitOrders);
+        }
+        Ok(merged)
     }
 
     /// Return the trades which fulfill non-liquidity auction orders. These are
@@ -356,24 +524,26 @@ impl Solution {
     fn allowances(
         &self,
         internalization: settlement::Internalization,
-    ) -> impl Iterator<Item = eth::allowance::Required> {
+        mode: eth::allowance::ApprovalMode,
+        slippage: &slippage::Parameters,
+    ) -> Result<impl Iterator<Item = eth::allowance::Required>, error::Math> {
         let mut normalized = HashMap::new();
-        let allowances = self.interactions.iter().flat_map(|interaction| {
-            if interaction.internalize()
-                && matches!(internalization, settlement::Internalization::Enable)
-            {
-                vec![]
-            } else {
-                interaction.allowances()
-            }
-        });
-        for allowance in allowances {
+        let allowances = self
+            .interactions
+            .iter()
+            .filter(|interaction| {
+                !(interaction.internalize()
+                    && matches!(internalization, settlement::Internalization::Enable))
+            })
+            .map(|interaction| interaction.allowances(mode, slippage))
+            .collect::<Result<Vec<_>, _>>()?;
+        for allowance in allowances.into_iter().flatten() {
             let amount = normalized
                 .entry((allowance.0.token, allowance.0.spender))
                 .or_insert(eth::U256::zero());
             *amount = amount.saturating_add(allowance.0.amount);
         }
-        normalized
+        Ok(normalized
             .into_iter()
             .map(|((token, spender), amount)| {
                 eth::Allowance {
@@ -383,7 +553,7 @@ impl Solution {
                 }
                 .into()
             })
-            .sorted()
+            .sorted())
     }
 
     /// Encode the solution into a [`Settlement`], which can be used to execute
@@ -405,44 +575,11 @@ impl Solution {
     /// The rule which relates two prices for tokens X and Y is:
     /// amount_x * price_x = amount_y * price_y
     pub fn clearing_prices(&self) -> Vec<eth::Asset> {
-        let prices = self.prices.iter().map(|(&token, &amount)| eth::Asset {
-            token,
-            amount: amount.into(),
-        });
-
-        if self.user_trades().any(|trade| trade.order().buys_eth()) {
-            // The solution contains an order which buys ETH. Solvers only produce solutions
-            // for ERC20 tokens, while the driver adds special [`Interaction`]s to
-            // wrap/unwrap the ETH tokens into WETH, and sends orders to the solver with
-            // WETH instead of ETH. Once the driver receives the solution which fulfills an
-            // ETH order, a clearing price for ETH needs to be added, equal to the
-            // WETH clearing price.
-
-            // If no order trades WETH, the WETH price is not necessary, only the ETH
-            // price is needed. Remove the unneeded WETH price, which slightly reduces
-            // gas used by the settlement.
-            let mut prices = if self.user_trades().all(|trade| {
-                trade.order().sell.token != self.weth.0 && trade.order().buy.token != self.weth.0
-            }) {
-                prices
-                    .filter(|price| price.token != self.weth.0)
-                    .collect_vec()
-            } else {
-                prices.collect_vec()
-            };
-
-            // Add a clearing price for ETH equal to WETH.
-            prices.push(eth::Asset {
-                token: eth::ETH_TOKEN,
-                amount: self.prices[&self.weth.into()].to_owned().into(),
-            });
-
-            return prices;
-        }
-
-        // TODO: We should probably filter out all unused prices to save gas.
-
-        prices.collect_vec()
+        let traded = self
+            .user_trades()
+            .map(|trade| (trade.order().sell.token, trade.order().buy.token))
+            .collect_vec();
+        clearing_prices(&self.id, &self.prices, self.weth, &traded)
     }
 
     /// Clearing price for the given token.
@@ -452,6 +589,19 @@ impl Solution {
         self.prices.get(&token).map(ToOwned::to_owned)
     }
 
+    /// The fees charged by all trades of this solution in the native token,
+    /// valued at the solution's clearing prices. `None` if a clearing price is
+    /// missing.
+    pub fn total_fees_native(&self) -> Option<eth::Ether> {
+        let native = self.clearing_price(self.weth.0)?;
+        let fees = self
+            .trades
+            .iter()
+            .map(|trade| Some((trade.fee().0, self.clearing_price(trade.sell().token)?)))
+            .collect::<Option<Vec<_>>>()?;
+        trade::native_fee_value(fees, native).map(eth::Ether)
+    }
+
     /// Whether there is a reasonable risk of this solution reverting on chain.
     pub fn revertable(&self) -> bool {
         self.interactions
@@ -480,30 +630,204 @@ impl std::fmt::Debug for Solution {
     }
 }
 
+/// The clearing prices of a solution with the given `prices` settling user
+/// orders which trade the given (sell, buy) token pairs, see
+/// [`Solution::clearing_prices`].
+fn clearing_prices(
+    id: &Id,
+    prices: &Prices,
+    weth: eth::WethAddress,
+    traded: &[(eth::TokenAddress, eth::TokenAddress)],
+) -> Vec<eth::Asset> {
+    let mut clearing_prices = prices
+        .iter()
+        .map(|(&token, &amount)| eth::Asset {
+            token,
+            amount: amount.into(),
+        })
+        .collect_vec();
+
+    if traded.iter().any(|(_, buy)| *buy == eth::ETH_TOKEN) {
+        // The solution contains an order which buys ETH. Solvers only produce solutions
+        // for ERC20 tokens, while the driver adds special [`Interaction`]s to
+        // wrap/unwrap the ETH tokens into WETH, and sends orders to the solver with
+        // WETH instead of ETH. Once the driver receives the solution which fulfills an
+        // ETH order, a clearing price for ETH needs to be added, equal to the
+        // WETH clearing price.
+
+        // If no order trades WETH, the WETH price is not necessary, only the ETH
+        // price is needed. Remove the unneeded WETH price, which slightly reduces
+        // gas used by the settlement.
+        if traded
+            .iter()
+            .all(|(sell, buy)| *sell != weth.0 && *buy != weth.0)
+        {
+            clearing_prices.retain(|price| price.token != weth.0);
+        }
+
+        // Add a clearing price for ETH equal to WETH.
+        clearing_prices.push(eth::Asset {
+            token: eth::ETH_TOKEN,
+            amount: prices[&weth.into()].to_owned().into(),
+        });
+    }
+
+    // TODO: We should probably filter out all unused prices to save gas.
+
+    // Safety net for the WETH price removal: every token traded by a user
+    // order needs a clearing price in the settlement.
+    let traded = traded.iter().flat_map(|(sell, buy)| [*sell, *buy]);
+    for token in unpriced_tokens(&clearing_prices, traded) {
+        tracing::warn!(?token, ?id, "removed clearing price of a traded token");
+    }
+
+    clearing_prices
+}
+
+/// The traded tokens which are missing a clearing price, without duplicates.
+fn unpriced_tokens(
+    prices: &[eth::Asset],
+    traded: impl Iterator<Item = eth::TokenAddress>,
+) -> Vec<eth::TokenAddress> {
+    traded
+        .filter(|token| !prices.iter().any(|price| price.token == *token))
+        .unique()
+        .collect()
+}
+
+/// Hashes the content of a solution. Clearing prices are sorted by token so
+/// that the ID doesn't depend on their order, and variable length fields are
+/// prefixed with their length so that different contents can't collide.
+fn content_id(
+    trades: &[Trade],
+    prices: &Prices,
+    pre_interactions: &[eth::Interaction],
+    interactions: &[Interaction],
+    post_interactions: &[eth::Interaction],
+) -> eth::H256 {
+    fn u256(data: &mut Vec<u8>, value: eth::U256) {
+        let mut bytes = [0; 32];
+        value.to_big_endian(&mut bytes);
+        data.extend(bytes);
+    }
+    fn bytes(data: &mut Vec<u8>, bytes: &[u8]) {
+        data.extend((bytes.len() as u64).to_be_bytes());
+        data.extend(bytes);
+    }
+    fn call(data: &mut Vec<u8>, interaction: &eth::Interaction) {
+        data.extend(interaction.target.0.as_bytes());
+        u256(data, interaction.value.0);
+        bytes(data, &interaction.call_data.0);
+    }
+    fn calls(data: &mut Vec<u8>, interactions: &[eth::Interaction]) {
+        data.extend((interactions.len() as u64).to_be_bytes());
+        for interaction in interactions {
+            call(data, interaction);
+        }
+    }
+
+    let mut data = Vec::new();
+    data.extend((trades.len() as u64).to_be_bytes());
+    for trade in trades {
+        let (uid, executed, fee) = match trade {
+            Trade::Fulfillment(trade) => (trade.order().uid, trade.executed(), trade.fee()),
+            Trade::Jit(trade) => (trade.order().uid, trade.executed(), trade.fee()),
+        };
+        data.extend(uid.0 .0);
+        u256(&mut data, executed.0);
+        u256(&mut data, fee.0);
+    }
+    data.extend((prices.len() as u64).to_be_bytes());
+    for (token, price) in prices.iter().sorted_by_key(|(token, _)| **token) {
+        data.extend(token.0 .0.as_bytes());
+        u256(&mut data, *price);
+    }
+    calls(&mut data, pre_interactions);
+    data.extend((interactions.len() as u64).to_be_bytes());
+    for interaction in interactions {
+        match interaction {
+            Interaction::Custom(custom) => {
+                data.push(0);
+                call(
+                    &mut data,
+                    &eth::Interaction {
+                        target: custom.target.into(),
+                        value: custom.value,
+                        call_data: custom.call_data.clone(),
+                    },
+                );
+            }
+            Interaction::Liquidity(liquidity) => {
+                data.push(1);
+                data.extend((liquidity.liquidity.id.0 as u64).to_be_bytes());
+                for asset in [liquidity.input, liquidity.output] {
+                    data.extend(asset.token.0 .0.as_bytes());
+                    u256(&mut data, asset.amount.0);
+                }
+            }
+        }
+    }
+    calls(&mut data, post_interactions);
+    eth::H256(web3::signing::keccak256(&data))
+}
+
+/// Given `(token, clearing price, reference price)` triples, compares the
+/// tokens with the highest and the lowest clearing price relative to their
+/// reference price against the median ratio of all tokens. Returns the one
+/// deviating more if it is priced more than `max_deviation` times higher or
+/// lower than the median suggests.
+fn price_outlier(
+    prices: impl Iterator<Item = (eth::TokenAddress, eth::U256, eth::U256)>,
+    max_deviation: f64,
+) -> Option<eth::TokenAddress> {
+    let ratios = prices
+        .filter(|(_, _, reference)| !reference.is_zero())
+        .map(|(token, clearing, reference)| {
+            (token, clearing.to_f64_lossy() / reference.to_f64_lossy())
+        })
+        .sorted_by(|a, b| a.1.total_cmp(&b.1))
+        .collect_vec();
+    let (lowest, min) = ratios.first()?;
+    let (highest, max) = ratios.last()?;
+    // Ratios are scaled arbitrarily, so the median of an even number of them
+    // is their geometric mean.
+    let len = ratios.len();
+    let median = match len % 2 {
+        0 => (ratios[len / 2 - 1].1 * ratios[len / 2].1).sqrt(),
+        _ => ratios[len / 2].1,
+    };
+    let (token, deviation) = if max / median >= median / min {
+        (highest, max / median)
+    } else {
+        (lowest, median / min)
+    };
+    (deviation > max_deviation).then_some(*token)
+}
+
 /// Given two solutions returns the factors with
 /// which prices of the second solution would have to be multiplied so that the
 /// given token would have the same price in both solutions.
 /// If the solutions have no prices in common any scaling factor is valid (we
-/// return 1). Returns None if the solutions have more than one price in common
-/// and the scaling factor is not unique.
-fn scaling_factor(first: &Prices, second: &Prices) -> Option<BigRational> {
-    let factors: HashSet<_> = first
+/// return 1). Returns the first common token whose prices don't agree with the
+/// ones of the other common tokens if the scaling factor is not unique.
+fn scaling_factor(first: &Prices, second: &Prices) -> Result<BigRational, eth::TokenAddress> {
+    let mut factors = first
         .keys()
-        .collect::<HashSet<_>>()
-        .intersection(&second.keys().collect::<HashSet<_>>())
-        .map(|&token| {
-            let first_price = first[token];
-            let second_price = second[token];
-            BigRational::new(
-                number::conversions::u256_to_big_int(&first_price),
-                number::conversions::u256_to_big_int(&second_price),
-            )
-        })
-        .collect();
-    match factors.len() {
-        0 => Some(BigRational::one()),
-        1 => factors.into_iter().next(),
-        _ => None,
+        .filter(|token| second.contains_key(token))
+        .sorted()
+        .map(|token| {
+            let factor = BigRational::new(
+                number::conversions::u256_to_big_int(&first[token]),
+                number::conversions::u256_to_big_int(&second[token]),
+            );
+            (token, factor)
+        });
+    let Some((_, reference)) = factors.next() else {
+        return Ok(BigRational::one());
+    };
+    match factors.find(|(_, factor)| *factor != reference) {
+        Some((token, _)) => Err(*token),
+        None => Ok(reference),
     }
 }
 
@@ -548,10 +872,12 @@ pub mod error {
         Incompatible(&'static str),
         #[error("duplicate trade")]
         DuplicateTrade,
-        #[error("incongruent prices")]
-        IncongruentPrices,
+        #[error("clearing prices of {token:?} are inconsistent with the other shared tokens")]
+        PriceMismatch { token: TokenAddress },
         #[error("math error: {0:?}")]
         Math(anyhow::Error),
+        #[error("merged solution contains more JIT orders than allowed")]
+        TooManyJitOrders,
     }
 
     #[derive(Debug, thiserror::Error)]
@@ -567,6 +893,11 @@ pub mod error {
              trusted"
         )]
         NonBufferableTokensUsed(BTreeSet<TokenAddress>),
+        #[error(
+            "buffers overdrawn: solution internalizes more of these tokens than the settlement \
+             contract holds"
+        )]
+        BufferOverdrawn(BTreeSet<TokenAddress>),
         #[error("invalid internalization: uninternalized solution fails to simulate")]
         FailingInternalization,
         #[error("Gas estimate of {0:?} exceeded the per settlement limit of {1:?}")]
@@ -577,6 +908,10 @@ pub mod error {
         DifferentSolvers,
         #[error("encoding error: {0:?}")]
         Encoding(#[from] encoding::Error),
+        #[error("solution involves blocked address {0:?}")]
+        BlockedAddress(eth::Address),
+        #[error("clearing price of {0:?} deviates too much from its reference price")]
+        MispricedToken(TokenAddress),
     }
 
     #[derive(Debug, thiserror::Error)]
@@ -631,3 +966,210 @@ pub mod error {
         Math(#[from] Math),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(address: u64) -> eth::TokenAddress {
+        eth::H160::from_low_u64_be(address).into()
+    }
+
+    #[test]
+    fn warns_about_removed_prices_of_traded_tokens() {
+        /// Collects everything logged while running `f`.
+        fn logs(f: impl FnOnce()) -> String {
+            let logs = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+            let writer = {
+                let logs = logs.clone();
+                move || LogWriter(logs.clone())
+            };
+            let subscriber = tracing_subscriber::fmt()
+                .with_writer(writer)
+                .with_ansi(false)
+                .finish();
+            tracing::subscriber::with_default(subscriber, f);
+            let logs = logs.lock().unwrap();
+            String::from_utf8(logs.clone()).unwrap()
+        }
+
+        struct LogWriter(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+        impl std::io::Write for LogWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        const WARNING: &str = "removed clearing price of a traded token";
+        let id = Id::Single(1);
+        let weth = eth::WethAddress(token(1));
+        let prices = |tokens: &[eth::TokenAddress]| {
+            tokens
+                .iter()
+                .map(|&token| (token, eth::U256::one()))
+                .collect::<Prices>()
+        };
+
+        // An order sells WETH for ETH, so the WETH price is kept next to the
+        // added ETH price.
+        let traded = [(weth.0, eth::ETH_TOKEN)];
+        let logged = logs(|| {
+            let clearing_prices = clearing_prices(&id, &prices(&[weth.0]), weth, &traded);
+            assert_eq!(clearing_prices.len(), 2);
+        });
+        assert!(!logged.contains(WARNING), "{logged}");
+
+        // An order sells WETH for another token but the WETH price is missing.
+        let traded = [(weth.0, token(2))];
+        let logged = logs(|| {
+            let clearing_prices = clearing_prices(&id, &prices(&[token(2)]), weth, &traded);
+            assert_eq!(clearing_prices.len(), 1);
+        });
+        assert!(logged.contains(WARNING), "{logged}");
+        assert!(logged.contains(&format!("{:?}", weth.0)), "{logged}");
+    }
+
+    #[test]
+    fn surplus_of_known_fill() {
+        let asset = |token, amount: u64| eth::Asset {
+            token,
+            amount: eth::U256::from(amount).into(),
+        };
+        // A sell order for 100 A with a limit of 90 B gets filled at a price of
+        // 0.95 B per A, so it receives 95 B, which is 5 B more than its limit.
+        let trade = scoring::Trade::new(
+            asset(token(1), 100),
+            asset(token(2), 90),
+            order::Side::Sell,
+            eth::U256::from(100).into(),
+            trade::CustomClearingPrices {
+                sell: 95.into(),
+                buy: 100.into(),
+            },
+            vec![],
+        );
+        assert_eq!(trade.surplus().unwrap(), asset(token(2), 5));
+    }
+
+    #[test]
+    fn detects_price_mismatch_between_merged_solutions() {
+        let prices = |prices: &[(u64, u64)]| {
+            prices
+                .iter()
+                .map(|(address, price)| (token(*address), eth::U256::from(*price)))
+                .collect::<Prices>()
+        };
+        let first = prices(&[(1, 100), (2, 200), (3, 300)]);
+
+        // The shared tokens are priced consistently after scaling by 1/2.
+        let congruent = prices(&[(1, 200), (2, 400), (4, 1000)]);
+        assert_eq!(
+            scaling_factor(&first, &congruent),
+            Ok(BigRational::new(1.into(), 2.into()))
+        );
+
+        // Token 3 is priced inconsistently with tokens 1 and 2.
+        let mismatched = prices(&[(1, 200), (2, 400), (3, 300)]);
+        assert_eq!(scaling_factor(&first, &mismatched), Err(token(3)));
+
+        // Without shared tokens any scaling is consistent.
+        let disjoint = prices(&[(4, 1000)]);
+        assert_eq!(scaling_factor(&first, &disjoint), Ok(BigRational::one()));
+    }
+
+    #[test]
+    fn identical_solutions_share_content_id() {
+        let custom = |call_data: Vec<u8>| {
+            Interaction::Custom(interaction::Custom {
+                target: eth::H160::from_low_u64_be(1).into(),
+                value: eth::U256::zero().into(),
+                call_data: call_data.into(),
+                allowances: Default::default(),
+                inputs: Default::default(),
+                outputs: Default::default(),
+                internalize: false,
+            })
+        };
+        let prices = |prices: &[(u64, u64)]| {
+            prices
+                .iter()
+                .map(|(address, price)| (token(*address), eth::U256::from(*price)))
+                .collect::<Prices>()
+        };
+        let id = |prices: &Prices, interactions: &[Interaction]| {
+            content_id(&[], prices, &[], interactions, &[])
+        };
+
+        let solution = (
+            prices(&[(1, 100), (2, 200), (3, 300)]),
+            vec![custom(vec![1])],
+        );
+        // The same content, with the prices inserted in a different order.
+        let identical = (
+            prices(&[(3, 300), (2, 200), (1, 100)]),
+            vec![custom(vec![1])],
+        );
+        assert_eq!(id(&solution.0, &solution.1), id(&identical.0, &identical.1));
+
+        let other_price = prices(&[(1, 100), (2, 201), (3, 300)]);
+        assert_ne!(id(&solution.0, &solution.1), id(&other_price, &solution.1));
+        let other_interaction = vec![custom(vec![2])];
+        assert_ne!(
+            id(&solution.0, &solution.1),
+            id(&solution.0, &other_interaction)
+        );
+    }
+
+    #[test]
+    fn detects_mispriced_tokens() {
+        let reference = |token: u64, price: u64| {
+            (
+                self::token(token),
+                eth::U256::exp10(18) * eth::U256::from(price),
+                eth::U256::from(price),
+            )
+        };
+
+        // Clearing prices are scaled arbitrarily but consistent with the
+        // reference prices.
+        let consistent = [reference(1, 1), reference(2, 2000), reference(3, 3)];
+        assert_eq!(price_outlier(consistent.into_iter(), 10.), None);
+
+        // Token 2 is priced 100x higher than the reference prices suggest.
+        let mispriced = [
+            reference(1, 1),
+            (
+                token(2),
+                eth::U256::exp10(18) * eth::U256::from(2000 * 100),
+                eth::U256::from(2000),
+            ),
+            reference(3, 3),
+        ];
+        assert_eq!(price_outlier(mispriced.into_iter(), 10.), Some(token(2)));
+        // Deviations within the configured factor are accepted.
+        assert_eq!(price_outlier(mispriced.into_iter(), 1000.), None);
+
+        // Token 2 is priced 100x lower than the reference prices suggest, so
+        // it is reported instead of the tokens with the highest ratio.
+        let underpriced = [
+            reference(1, 1),
+            (
+                token(2),
+                eth::U256::exp10(16) * eth::U256::from(2000),
+                eth::U256::from(2000),
+            ),
+            reference(3, 3),
+        ];
+        assert_eq!(price_outlier(underpriced.into_iter(), 10.), Some(token(2)));
+
+        // Tokens without a reference price are not passed in, so a single
+        // token can't deviate from anything.
+        assert_eq!(price_outlier([reference(1, 1)].into_iter(), 10.), None);
+    }
+}
@@ -173,22 +173,62 @@ impl Trade {
     ///
     /// Denominated in SURPLUS token
     fn protocol_fees(&self) -> Result<eth::Asset, Error> {
+        let amount = self.protocol_fee_per_policy()?.into_iter().fold(
+            TokenAmount::default(),
+            |mut amount, (_, fee)| {
+                amount += fee;
+                amount
+            },
+        );
+        Ok(eth::Asset {
+            token: self.surplus_token(),
+            amount,
+        })
+    }
+
+    /// The partner fees of the trade and the integrators they are paid out
+    /// to, as specified in the order's app data.
+    ///
+    /// Denominated in SURPLUS token
+    pub fn partner_fees(&self) -> Result<Vec<(eth::Address, eth::Asset)>, Error> {
+        Ok(self
+            .protocol_fee_per_policy()?
+            .into_iter()
+            .filter_map(|(policy, amount)| match policy {
+                FeePolicy::Volume {
+                    recipient: Some(recipient),
+                    ..
+                } => Some((
+                    *recipient,
+                    eth::Asset {
+                        token: self.surplus_token(),
+                        amount,
+                    },
+                )),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// The protocol fee charged by each of the fee policies attached to the
+    /// order, in reverse order of application.
+    ///
+    /// Denominated in SURPLUS token
+    fn protocol_fee_per_policy(&self) -> Result<Vec<(&FeePolicy, TokenAmount)>, Error> {
         let mut current_trade = self.clone();
         let mut amount = TokenAmount::default();
+        let mut fees = Vec::with_capacity(self.policies.len());
         for (i, protocol_fee) in self.policies.iter().enumerate().rev() {
             let fee = current_trade.protocol_fee(protocol_fee)?;
             // Do not need to calculate the last custom prices because in the last iteration
             // the prices are not used anymore to calculate the protocol fee
             amount += fee;
+            fees.push((protocol_fee, fee));
             if !i.is_zero() {
                 current_trade.custom_price = self.calculate_custom_prices(amount)?;
             }
         }
-
-        Ok(eth::Asset {
-            token: self.surplus_token(),
-            amount,
-        })
+        Ok(fees)
     }
 
     /// The effective amount that left the user's wallet including all fees.
@@ -283,7 +323,7 @@ impl Trade {
                 );
                 Ok(fee)
             }
-            FeePolicy::Volume { factor } => Ok(self.volume_fee(*factor)?.amount),
+            FeePolicy::Volume { factor, .. } => Ok(self.volume_fee(*factor)?.amount),
         }
     }
 
@@ -312,6 +352,14 @@ impl Trade {
         Ok(surplus?)
     }
 
+    /// Surplus of the trade over the order's limit price after all fees have
+    /// been applied.
+    ///
+    /// Denominated in SURPLUS token
+    pub fn surplus(&self) -> Result<eth::Asset, Error> {
+        self.surplus_over_limit_price()
+    }
+
     fn surplus_over_limit_price(&self) -> Result<eth::Asset, Error> {
         let limit_price = PriceLimits {
             sell: self.sell.amount,
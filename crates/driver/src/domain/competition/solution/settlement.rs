@@ -1,13 +1,29 @@
 use {
-    super::{encoding, trade::ClearingPrices, Error, Solution},
+    super::{
+        calldata::{self, GasBreakdown},
+        encoding,
+        explanation::InteractionExplanation,
+        trade::ClearingPrices,
+        Error,
+        Solution,
+    },
     crate::{
         domain::{
             competition::{self, auction, order, solution},
             eth,
+            liquidity::TokenPair,
+        },
+        infra::{
+            blockchain::Ethereum,
+            observe,
+            simulator,
+            solver::{DistinctTokenPenalty, ManageNativeToken},
+            Simulator,
         },
-        infra::{blockchain::Ethereum, observe, solver::ManageNativeToken, Simulator},
+        util::{self, conv::u256::U256Ext},
     },
-    futures::future::try_join_all,
+    futures::{future::try_join_all, Future},
+    num::Signed,
     std::collections::{BTreeSet, HashMap, HashSet},
 };
 
@@ -26,6 +42,8 @@ use {
 ///   the case where no interactions were internalized. Additionally the solver
 ///   account is known to have sufficient Ether to execute the transaction.
 /// - Internalization: internalized interactions only use trusted tokens.
+/// - Screening: the solution doesn't involve any address blocked by the
+///   solver's configuration.
 ///
 /// Publishing a settlement which violates these rules would result in slashing
 /// for the solver (earning reduced rewards). Enforcing these rules ensures that
@@ -38,7 +56,19 @@ pub struct Settlement {
     transaction: SettlementTx,
     /// The gas parameters used by the settlement.
     pub gas: Gas,
+    /// The gas estimate of the uninternalized settlement transaction, if any
+    /// of its interactions are internalized.
+    uninternalized_gas: Option<eth::Gas>,
     solution: Solution,
+    /// The approvals encoded in the uninternalized transaction.
+    approvals: Vec<eth::allowance::Approval>,
+    /// The payment for the settlement's gas out of the settlement contract's
+    /// buffers, if the solver pays for gas in an ERC20 token.
+    gas_payment: Option<encoding::GasPayment>,
+    /// The explanation of the interactions of the uninternalized transaction.
+    explanation: Vec<InteractionExplanation>,
+    /// The user orders which expire soon after the settlement is submitted.
+    expiring_orders: HashSet<order::Uid>,
 }
 
 #[derive(Debug, Clone)]
@@ -87,27 +117,99 @@ impl Settlement {
             return Err(Error::NonBufferableTokensUsed(untrusted_tokens));
         }
 
+        // Buffer rule: check that internalized interactions and the gas payment
+        // don't draw more of a token from the settlement contract than it holds.
+        let gas_payment = encoding::gas_payment(auction, &solution)?;
+        if solution.solver().reject_buffer_overdraws() {
+            let overdrawn_tokens = overdrawn_buffers(
+                solution
+                    .interactions
+                    .iter()
+                    .filter(|interaction| interaction.internalize())
+                    .flat_map(|interaction| interaction.outputs())
+                    .chain(gas_payment.map(|payment| payment.asset)),
+                |token| auction.tokens().get(token).available_balance,
+            );
+            if !overdrawn_tokens.is_empty() {
+                return Err(Error::BufferOverdrawn(overdrawn_tokens));
+            }
+        }
+
+        // Screening rule: check that the solution doesn't pay out to or call into
+        // blocked addresses.
+        if let Some(address) = solution
+            .addresses_involved()
+            .into_iter()
+            .find(|address| solution.solver().is_blocked(address))
+        {
+            return Err(Error::BlockedAddress(address));
+        }
+
+        // Pricing rule: check that the clearing prices are roughly consistent with
+        // the reference prices of the auction.
+        if let Some(max_deviation) = solution.solver().max_price_deviation() {
+            if let Some(token) = solution.mispriced_token(&auction.prices(), max_deviation) {
+                return Err(Error::MispricedToken(token));
+            }
+        }
+
         // Encode the solution into a settlement.
+        let approvals: Vec<_> = solution
+            .approvals(
+                eth,
+                auction,
+                Internalization::Disable,
+                solution.solver().approval_mode(),
+            )
+            .await?
+            .collect();
+        let (uninternalized, explanation) = encoding::explained_tx(
+            auction,
+            &solution,
+            eth.contracts(),
+            approvals.iter().copied(),
+            Internalization::Disable,
+            solver_native_token,
+        )?;
         let tx = SettlementTx {
             internalized: encoding::tx(
                 auction,
                 &solution,
                 eth.contracts(),
-                solution.approvals(eth, Internalization::Enable).await?,
+                solution
+                    .approvals(
+                        eth,
+                        auction,
+                        Internalization::Enable,
+                        solution.solver().approval_mode(),
+                    )
+                    .await?,
                 Internalization::Enable,
                 solver_native_token,
             )?,
-            uninternalized: encoding::tx(
-                auction,
-                &solution,
-                eth.contracts(),
-                solution.approvals(eth, Internalization::Disable).await?,
-                Internalization::Disable,
-                solver_native_token,
-            )?,
+            uninternalized,
             may_revert: solution.revertable(),
         };
-        Self::new(auction.id().unwrap(), solution, tx, eth, simulator).await
+        let diagnosed = simulator.isolates_reverts().then(|| solution.clone());
+        let settlement = Self::new(
+            auction.id().unwrap(),
+            solution,
+            tx,
+            approvals,
+            gas_payment,
+            explanation,
+            eth,
+            simulator,
+        )
+        .await;
+        if let (Err(Error::Simulation(simulator::Error::Revert(err))), Some(solution)) =
+            (&settlement, diagnosed)
+        {
+            let isolated =
+                isolate_revert(&solution, err, auction, eth, simulator, solver_native_token).await;
+            observe::revert_isolated(solution.id(), isolated);
+        }
+        settlement
     }
 
     /// Create a new settlement and ensure that it is valid.
@@ -115,6 +217,9 @@ impl Settlement {
         auction_id: auction::Id,
         solution: Solution,
         transaction: SettlementTx,
+        approvals: Vec<eth::allowance::Approval>,
+        gas_payment: Option<encoding::GasPayment>,
+        explanation: Vec<InteractionExplanation>,
         eth: &Ethereum,
         simulator: &Simulator,
     ) -> Result<Self, Error> {
@@ -155,6 +260,17 @@ impl Settlement {
             simulator,
         )
         .await?;
+        let gas = solution
+            .solver()
+            .gas_buffer()
+            .apply(gas, solution.interactions());
+        let gas = solution.solver().gas_overrides().apply(
+            gas,
+            solution
+                .interactions()
+                .iter()
+                .filter(|interaction| !interaction.internalize()),
+        );
         let price = eth.gas_price().await?;
         let gas = Gas::new(gas, eth.block_gas_limit(), price)?;
 
@@ -166,7 +282,7 @@ impl Settlement {
         }
 
         // Is at least one interaction internalized?
-        if solution
+        let uninternalized_gas = if solution
             .interactions()
             .iter()
             .any(|interaction| interaction.internalize())
@@ -177,20 +293,37 @@ impl Settlement {
             // these rules constitutes a punishable offense for the solver, even if
             // the interactions are internalized. To ensure that this doesn't happen, check
             // that the settlement simulates even when internalizations are disabled.
-            Self::simulate(
+            let (_, gas) = Self::simulate(
                 transaction.uninternalized.clone(),
                 &partial_access_list,
                 eth,
                 simulator,
             )
             .await?;
-        }
+            let gas = solution
+                .solver()
+                .gas_buffer()
+                .apply(gas, solution.interactions());
+            Some(
+                solution
+                    .solver()
+                    .gas_overrides()
+                    .apply(gas, solution.interactions()),
+            )
+        } else {
+            None
+        };
 
         Ok(Self {
             auction_id,
             solution,
             transaction: transaction.with_access_list(access_list),
             gas,
+            uninternalized_gas,
+            approvals,
+            gas_payment,
+            explanation,
+            expiring_orders: Default::default(),
         })
     }
 
@@ -226,20 +359,84 @@ impl Settlement {
         }
     }
 
+    /// The user orders of this settlement which are no longer valid at the
+    /// given time.
+    pub fn expired_orders(&self, now: util::Timestamp) -> HashSet<order::Uid> {
+        self.solution
+            .user_trades()
+            .filter(|trade| trade.order().is_expired(now))
+            .map(|trade| trade.order().uid)
+            .collect()
+    }
+
+    /// Flags the user orders of this settlement which are still valid at the
+    /// given time but expire within the grace period after it.
+    pub fn flag_expiring_orders(self, now: util::Timestamp, grace: std::time::Duration) -> Self {
+        let expiring_orders = self
+            .solution
+            .user_trades()
+            .filter(|trade| trade.order().expires_within(now, grace))
+            .map(|trade| trade.order().uid)
+            .collect();
+        Self {
+            expiring_orders,
+            ..self
+        }
+    }
+
+    /// The user orders flagged as expiring soon, see
+    /// [`Self::flag_expiring_orders`].
+    pub fn expiring_orders(&self) -> &HashSet<order::Uid> {
+        &self.expiring_orders
+    }
+
+    /// Encodes the solution of the settlement again for another auction, e.g.
+    /// to let a pending settlement compete in a newer auction. The settlement
+    /// gets simulated again and is tagged with the new auction's id.
+    pub async fn for_auction(
+        &self,
+        auction: &competition::Auction,
+        eth: &Ethereum,
+        simulator: &Simulator,
+    ) -> Result<Self, Error> {
+        let solution = self.solution.clone();
+        let solver_native_token = solution.solver().solver_native_token();
+        Self::encode(solution, auction, eth, simulator, solver_native_token).await
+    }
+
     /// Whether the settlement contains interactions that could possibly revert
     /// on chain
     pub fn may_revert(&self) -> bool {
         self.transaction.may_revert
     }
 
-    /// Score as defined per CIP38. Equal to surplus + protocol fees.
+    /// Score as defined per CIP38. Equal to surplus + protocol fees, reduced
+    /// by the solver's configured penalty for touching many distinct tokens.
     pub fn score(
         &self,
         prices: &auction::Prices,
         surplus_capturing_jit_order_owners: &HashSet<eth::Address>,
     ) -> Result<eth::Ether, solution::error::Scoring> {
-        self.solution
-            .scoring(prices, surplus_capturing_jit_order_owners)
+        let score = self
+            .solution
+            .scoring(prices, surplus_capturing_jit_order_owners)?;
+        Ok(net_score(
+            score,
+            self.solution.prices.len(),
+            self.solution.solver().distinct_token_penalty(),
+            self.gas_payment.map(|payment| payment.cost),
+        ))
+    }
+
+    /// Compares the profitability of the settlement, i.e. its score net of
+    /// the gas cost of executing it, with and without internalizing
+    /// interactions. Returns `None` if no interaction is internalized.
+    pub fn profitability(&self, score: eth::Ether) -> Option<Profitability> {
+        let price = self.gas.price.effective().0 .0;
+        let cost = |gas: eth::Gas| eth::Ether(gas.0.saturating_mul(price));
+        self.uninternalized_gas.map(|uninternalized| {
+            Profitability::new(score, cost(self.gas.estimate), cost(uninternalized))
+        })
     }
 
     /// The solution encoded in this settlement.
@@ -247,6 +444,17 @@ impl Settlement {
         self.solution.id()
     }
 
+    /// The fees charged by the trades of this settlement in the native token,
+    /// valued at the settlement's clearing prices.
+    pub fn total_fees_native(&self) -> Option<eth::Ether> {
+        self.solution.total_fees_native()
+    }
+
+    /// Number of interactions executed by this settlement.
+    pub fn interaction_count(&self) -> usize {
+        self.solution.interactions().len()
+    }
+
     /// Address of the solver which generated this settlement.
     pub fn solver(&self) -> eth::Address {
         self.solution.solver().address()
@@ -277,6 +485,78 @@ impl Settlement {
         acc
     }
 
+    /// The surplus of each settled user order, denominated in the order's
+    /// surplus token.
+    pub fn surpluses(&self) -> HashMap<order::Uid, eth::Asset> {
+        self.solution.surpluses()
+    }
+
+    /// A human-readable breakdown of the interactions executed by the
+    /// uninternalized settlement transaction, in execution order. Internalized
+    /// interactions are included and flagged as such.
+    pub fn explain(&self) -> Vec<InteractionExplanation> {
+        self.explanation.clone()
+    }
+
+    /// The calldata gas of the uninternalized settlement transaction,
+    /// attributed to the trades, approvals and interactions it encodes.
+    pub fn calldata_gas_breakdown(&self) -> GasBreakdown {
+        let core = self
+            .approvals
+            .iter()
+            .map(|_| calldata::Component::Approval)
+            .chain(self.solution.interactions.iter().map(Into::into));
+        calldata::breakdown(&self.transaction.uninternalized.input.0, core)
+    }
+
+    /// The spread captured between the opposing orders of every token pair
+    /// traded by this settlement, denominated in the second token of the
+    /// pair. See [`spread_capture`].
+    pub fn spread_capture(&self) -> HashMap<TokenPair, eth::U256> {
+        spread_capture(self.executed_user_trades())
+    }
+
+    /// The `(sold, bought)` assets of every executed user order, with native
+    /// ETH wrapped to WETH.
+    fn executed_user_trades(&self) -> Vec<(eth::Asset, eth::Asset)> {
+        let tokens = self
+            .solution
+            .user_trades()
+            .map(|trade| {
+                let order = trade.order();
+                (
+                    order.uid,
+                    (
+                        order.sell.token.wrap(self.solution.weth),
+                        order.buy.token.wrap(self.solution.weth),
+                    ),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        self.orders()
+            .into_iter()
+            .filter_map(|(uid, amounts)| {
+                let (sell, buy) = tokens.get(&uid)?;
+                Some((
+                    eth::Asset {
+                        token: *sell,
+                        amount: amounts.sell,
+                    },
+                    eth::Asset {
+                        token: *buy,
+                        amount: amounts.buy,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// The number of user trades matched peer-to-peer (CoW) rather than
+    /// routed through liquidity. See [`cow_match_count`].
+    pub fn cow_match_count(&self) -> usize {
+        cow_match_count(self.executed_user_trades())
+    }
+
     /// The uniform price vector this settlement proposes
     pub fn prices(&self) -> HashMap<eth::TokenAddress, eth::TokenAmount> {
         self.solution
@@ -287,6 +567,167 @@ impl Settlement {
     }
 }
 
+/// Finds the interaction which makes a settlement revert by bisecting over
+/// prefixes of its interactions. Prefixes are simulated together with all
+/// trades, so reverts which happen without any interaction, e.g. because the
+/// payouts rely on the skipped interactions, can't be isolated.
+///
+/// Returns the index of the first reverting interaction and its revert reason.
+async fn isolate_revert(
+    solution: &Solution,
+    revert: &simulator::RevertError,
+    auction: &competition::Auction,
+    eth: &Ethereum,
+    simulator: &Simulator,
+    solver_native_token: ManageNativeToken,
+) -> Option<(usize, String)> {
+    let simulate_prefix = |len: usize| {
+        let prefix = Solution {
+            interactions: solution.interactions[..len].to_vec(),
+            ..solution.clone()
+        };
+        async move {
+            let approvals = prefix
+                .approvals(
+                    eth,
+                    auction,
+                    Internalization::Disable,
+                    prefix.solver().approval_mode(),
+                )
+                .await
+                .ok()?;
+            let tx = encoding::tx(
+                auction,
+                &prefix,
+                eth.contracts(),
+                approvals,
+                Internalization::Disable,
+                solver_native_token,
+            )
+            .ok()?;
+            match simulator.gas(&tx).await {
+                Err(simulator::Error::Revert(err)) => Some(err.err.to_string()),
+                _ => None,
+            }
+        }
+    };
+    first_reverting_interaction(
+        solution.interactions.len(),
+        revert.err.to_string(),
+        simulate_prefix,
+    )
+    .await
+}
+
+/// Bisects for the first interaction making a settlement with `len`
+/// interactions revert. `simulate_prefix(n)` returns the revert reason of the
+/// settlement with only its first `n` interactions, or `None` if it succeeds.
+async fn first_reverting_interaction<F, Fut>(
+    len: usize,
+    reason: String,
+    simulate_prefix: F,
+) -> Option<(usize, String)>
+where
+    F: Fn(usize) -> Fut,
+    Fut: Future<Output = Option<String>>,
+{
+    if len == 0 || simulate_prefix(0).await.is_some() {
+        return None;
+    }
+    // The prefix of `succeeds` interactions succeeds while the one of `reverts`
+    // interactions reverts with `reason`.
+    let (mut succeeds, mut reverts, mut reason) = (0, len, reason);
+    while reverts - succeeds > 1 {
+        let mid = succeeds + (reverts - succeeds) / 2;
+        match simulate_prefix(mid).await {
+            Some(mid_reason) => (reverts, reason) = (mid, mid_reason),
+            None => succeeds = mid,
+        }
+    }
+    Some((reverts - 1, reason))
+}
+
+/// Computes the spread captured between opposing orders for every token pair
+/// `(a, b)` given the `(sold, bought)` assets of each executed order.
+///
+/// Orders selling `a` receive `b` at an average rate of `b` per `a`, while
+/// orders selling `b` pay `b` at an average rate per `a` they receive. The
+/// difference between the two rates applied to the volume of `a` matched
+/// between both sides is the spread captured by the settlement, denominated in
+/// `b`. Pairs traded in a single direction capture no spread.
+fn spread_capture(
+    trades: impl IntoIterator<Item = (eth::Asset, eth::Asset)>,
+) -> HashMap<TokenPair, eth::U256> {
+    #[derive(Default)]
+    struct Sides {
+        a_sold: eth::U256,
+        b_received: eth::U256,
+        b_sold: eth::U256,
+        a_received: eth::U256,
+    }
+
+    let mut pairs = HashMap::<TokenPair, Sides>::new();
+    for (sold, bought) in trades {
+        let Ok(pair) = TokenPair::new(sold.token, bought.token) else {
+            continue;
+        };
+        let sides = pairs.entry(pair).or_default();
+        if pair.get().0 == sold.token {
+            sides.a_sold = sides.a_sold.saturating_add(sold.amount.0);
+            sides.b_received = sides.b_received.saturating_add(bought.amount.0);
+        } else {
+            sides.b_sold = sides.b_sold.saturating_add(sold.amount.0);
+            sides.a_received = sides.a_received.saturating_add(bought.amount.0);
+        }
+    }
+
+    pairs
+        .into_iter()
+        .map(|(pair, sides)| {
+            if sides.a_sold.is_zero() || sides.a_received.is_zero() {
+                return (pair, eth::U256::zero());
+            }
+            let sell_rate = sides.b_received.to_big_rational() / sides.a_sold.to_big_rational();
+            let buy_rate = sides.b_sold.to_big_rational() / sides.a_received.to_big_rational();
+            let volume = sides.a_sold.min(sides.a_received).to_big_rational();
+            let spread = (buy_rate - sell_rate) * volume;
+            let spread = if spread.is_positive() {
+                eth::U256::from_big_rational(&spread).unwrap_or(eth::U256::MAX)
+            } else {
+                eth::U256::zero()
+            };
+            (pair, spread)
+        })
+        .collect()
+}
+
+/// Counts the trades, given as `(sold, bought)` assets, whose full execution
+/// is covered by opposing trades of the same token pair: the opposing trades
+/// buy at least all of the sold tokens and sell at least all of the bought
+/// tokens, so no liquidity is needed to execute the trade. Matches over rings
+/// of more than two tokens are not detected.
+fn cow_match_count(trades: impl IntoIterator<Item = (eth::Asset, eth::Asset)>) -> usize {
+    let trades = trades.into_iter().collect::<Vec<_>>();
+    // Total amounts sold and bought per directed `(sell, buy)` token pair.
+    let mut volumes = HashMap::<_, (eth::U256, eth::U256)>::new();
+    for (sold, bought) in &trades {
+        let volume = volumes.entry((sold.token, bought.token)).or_default();
+        volume.0 = volume.0.saturating_add(sold.amount.0);
+        volume.1 = volume.1.saturating_add(bought.amount.0);
+    }
+
+    trades
+        .iter()
+        .filter(|(sold, bought)| {
+            volumes.get(&(bought.token, sold.token)).is_some_and(
+                |(opposing_sold, opposing_bought)| {
+                    *opposing_bought >= sold.amount.0 && *opposing_sold >= bought.amount.0
+                },
+            )
+        })
+        .count()
+}
+
 /// Should the interactions be internalized?
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Internalization {
@@ -302,6 +743,42 @@ pub enum Internalization {
     Disable,
 }
 
+/// The profitability of a settlement with internalized interactions compared
+/// to the same settlement executed without internalization. Profits are the
+/// score net of the gas cost of the settlement, in wei, and can be negative.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profitability {
+    /// The profit when internalizing interactions.
+    pub internalized: num::BigInt,
+    /// The profit when executing all interactions.
+    pub uninternalized: num::BigInt,
+}
+
+impl Profitability {
+    fn new(
+        score: eth::Ether,
+        internalized_cost: eth::Ether,
+        uninternalized_cost: eth::Ether,
+    ) -> Self {
+        let profit = |cost: eth::Ether| num::BigInt::from(score) - num::BigInt::from(cost);
+        Self {
+            internalized: profit(internalized_cost),
+            uninternalized: profit(uninternalized_cost),
+        }
+    }
+
+    /// How much more profitable the settlement is thanks to internalization.
+    pub fn delta(&self) -> num::BigInt {
+        &self.internalized - &self.uninternalized
+    }
+
+    /// Whether the settlement is only profitable because its interactions
+    /// are internalized.
+    pub fn requires_internalization(&self) -> bool {
+        self.internalized.is_positive() && !self.uninternalized.is_positive()
+    }
+}
+
 /// Gas parameters associated with a settlement.
 #[derive(Clone, Copy, Debug)]
 pub struct Gas {
@@ -362,3 +839,198 @@ impl Gas {
         self.limit * self.price.max()
     }
 }
+
+/// The score of a settlement touching `tokens` distinct tokens, reduced by the
+/// distinct token penalty and the cost of the gas payment, if any.
+fn net_score(
+    score: eth::Ether,
+    tokens: usize,
+    penalty: DistinctTokenPenalty,
+    gas_payment: Option<eth::Ether>,
+) -> eth::Ether {
+    let score = penalty.apply(score, tokens);
+    // The gas payment is paid out of the protocol's buffers.
+    match gas_payment {
+        Some(cost) => eth::Ether(score.0.saturating_sub(cost.0)),
+        None => score,
+    }
+}
+
+/// The tokens of which the `outflows` add up to more than the `available`
+/// buffered balance.
+fn overdrawn_buffers(
+    outflows: impl IntoIterator<Item = eth::Asset>,
+    available: impl Fn(eth::TokenAddress) -> eth::U256,
+) -> BTreeSet<eth::TokenAddress> {
+    let mut totals = HashMap::<eth::TokenAddress, eth::U256>::new();
+    for asset in outflows {
+        let total = totals.entry(asset.token).or_default();
+        *total = total.saturating_add(asset.amount.0);
+    }
+    totals
+        .into_iter()
+        .filter(|(token, total)| *total > available(*token))
+        .map(|(token, _)| token)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profitable_only_when_internalized() {
+        let eth = |wei: u64| eth::Ether(wei.into());
+
+        // Internalization saves 4_000 wei of gas, turning a loss of 1_000 wei
+        // into a profit of 3_000 wei.
+        let profitability = Profitability::new(eth(10_000), eth(7_000), eth(11_000));
+        assert_eq!(profitability.internalized, 3_000.into());
+        assert_eq!(profitability.uninternalized, (-1_000).into());
+        assert_eq!(profitability.delta(), 4_000.into());
+        assert!(profitability.requires_internalization());
+
+        // Profitable either way.
+        let profitability = Profitability::new(eth(10_000), eth(7_000), eth(9_000));
+        assert_eq!(profitability.delta(), 2_000.into());
+        assert!(!profitability.requires_internalization());
+
+        // Unprofitable either way.
+        let profitability = Profitability::new(eth(10_000), eth(12_000), eth(13_000));
+        assert!(!profitability.requires_internalization());
+    }
+
+    #[test]
+    fn settlement_touching_more_tokens_scores_lower() {
+        let eth = |wei: u64| eth::Ether(wei.into());
+        let penalty = DistinctTokenPenalty {
+            threshold: 2,
+            per_token: eth(100),
+        };
+
+        // A settlement of two tokens ranks above one of four tokens, even
+        // though the latter has a higher score before the penalty.
+        let simple = net_score(eth(1_000), 2, penalty, None);
+        let complex = net_score(eth(1_050), 4, penalty, None);
+        assert_eq!(simple, eth(1_000));
+        assert_eq!(complex, eth(850));
+        assert!(complex < simple);
+
+        // The gas payment is deducted on top of the penalty.
+        assert_eq!(net_score(eth(1_050), 4, penalty, Some(eth(50))), eth(800));
+        // Without a penalty the higher score wins.
+        let disabled = DistinctTokenPenalty {
+            threshold: 0,
+            per_token: eth(0),
+        };
+        assert!(
+            net_score(eth(1_050), 4, disabled, None) > net_score(eth(1_000), 2, disabled, None)
+        );
+    }
+
+    #[tokio::test]
+    async fn bisection_finds_reverting_interaction() {
+        // The interaction at index 5 of 8 reverts, so does every prefix
+        // containing it.
+        let simulate_prefix = |len: usize| async move {
+            (len > 5).then(|| "execution reverted: interaction 5".to_owned())
+        };
+        assert_eq!(
+            first_reverting_interaction(8, "full".to_owned(), simulate_prefix).await,
+            Some((5, "execution reverted: interaction 5".to_owned()))
+        );
+
+        // The last interaction reverts, only the full settlement fails.
+        let simulate_prefix = |len: usize| async move { (len > 7).then(|| "last".to_owned()) };
+        assert_eq!(
+            first_reverting_interaction(8, "full".to_owned(), simulate_prefix).await,
+            Some((7, "full".to_owned()))
+        );
+
+        // The settlement reverts without any interaction.
+        let simulate_prefix = |_| async { Some("trades".to_owned()) };
+        assert_eq!(
+            first_reverting_interaction(8, "full".to_owned(), simulate_prefix).await,
+            None
+        );
+    }
+
+    #[test]
+    fn spread_capture_of_cow() {
+        let a = eth::TokenAddress::from(eth::H160::from_low_u64_be(1));
+        let b = eth::TokenAddress::from(eth::H160::from_low_u64_be(2));
+        let asset = |token, amount: u64| eth::Asset {
+            token,
+            amount: eth::U256::from(amount).into(),
+        };
+
+        // The first order sells 100 A at 1.9 B/A while the second one buys the
+        // same 100 A at 2 B/A, leaving 0.1 B/A of spread on 100 A.
+        let spread = spread_capture([
+            (asset(a, 100), asset(b, 190)),
+            (asset(b, 200), asset(a, 100)),
+        ]);
+        assert_eq!(
+            spread,
+            HashMap::from([(TokenPair::new(a, b).unwrap(), eth::U256::from(10))])
+        );
+
+        // Without opposing orders no spread is captured.
+        let spread = spread_capture([(asset(a, 100), asset(b, 190))]);
+        assert_eq!(
+            spread,
+            HashMap::from([(TokenPair::new(a, b).unwrap(), eth::U256::zero())])
+        );
+    }
+
+    #[test]
+    fn counts_cow_matches() {
+        let [a, b, c, d] =
+            [1, 2, 3, 4].map(|i| eth::TokenAddress::from(eth::H160::from_low_u64_be(i)));
+        let asset = |token, amount: u64| eth::Asset {
+            token,
+            amount: eth::U256::from(amount).into(),
+        };
+
+        // The first order is fully matched against the second, larger one,
+        // which needs liquidity for its remainder. The third order is routed
+        // through an AMM.
+        let trades = [
+            (asset(a, 100), asset(b, 200)),
+            (asset(b, 500), asset(a, 250)),
+            (asset(c, 100), asset(d, 100)),
+        ];
+        assert_eq!(cow_match_count(trades), 1);
+
+        // Perfectly opposing orders are both matched.
+        let trades = [
+            (asset(a, 100), asset(b, 200)),
+            (asset(b, 200), asset(a, 100)),
+        ];
+        assert_eq!(cow_match_count(trades), 2);
+    }
+
+    #[test]
+    fn rejects_overdrawn_buffers() {
+        let [a, b] = [1, 2].map(|i| eth::TokenAddress::from(eth::H160::from_low_u64_be(i)));
+        let asset = |token, amount: u64| eth::Asset {
+            token,
+            amount: eth::U256::from(amount).into(),
+        };
+        let available = |token| match token {
+            token if token == a => eth::U256::from(100),
+            _ => eth::U256::from(50),
+        };
+
+        // Internalizing up to the buffered balance is fine.
+        assert!(
+            overdrawn_buffers([asset(a, 60), asset(a, 40), asset(b, 50)], available).is_empty()
+        );
+
+        // Together, both interactions draw more A than is buffered.
+        assert_eq!(
+            overdrawn_buffers([asset(a, 60), asset(a, 41), asset(b, 50)], available),
+            BTreeSet::from([a])
+        );
+    }
+}
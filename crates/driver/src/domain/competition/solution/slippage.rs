@@ -8,12 +8,16 @@ use {
     num::{BigRational, CheckedDiv, CheckedMul},
     number::conversions::big_rational_to_u256,
     shared::conversions::U256Ext,
+    std::collections::HashMap,
 };
 
 #[derive(Clone)]
 pub struct Parameters {
     /// The maximum relative slippage factor.
     pub relative: BigRational,
+    /// Relative slippage factors overriding [`Self::relative`] for tokens in
+    /// a volatility tier.
+    pub tiers: HashMap<eth::TokenAddress, BigRational>,
     /// The maximum absolute slippage in native tokens.
     pub max: Option<eth::U256>,
     /// The minimum absolute slippage in native tokens.
@@ -28,6 +32,17 @@ pub struct Interaction {
 }
 
 impl Parameters {
+    /// The relative slippage factor for an interaction. Tokens without a tier
+    /// use the default relative slippage and the most volatile of the two
+    /// tokens determines the slippage of the interaction.
+    fn relative(&self, interaction: &Interaction) -> &BigRational {
+        let relative = |token: &eth::TokenAddress| self.tiers.get(token).unwrap_or(&self.relative);
+        std::cmp::max(
+            relative(&interaction.input.token),
+            relative(&interaction.output.token),
+        )
+    }
+
     /// Apply the slippage parameters to the given liquidity increasing the in
     /// amount by the appropriate slippage while keeping the out amount the
     /// same.
@@ -41,9 +56,10 @@ impl Parameters {
         // 2. If no sell token price is available, compute the capped slippage using the
         //    buy token amount
         // 3. Fall back to using the default relative slippage without capping
+        let relative_slippage = self.relative(interaction);
         let slippage = if let Some(price) = self.prices.get(&interaction.input.token) {
             let amount = price.in_eth(interaction.input.amount);
-            let relative = amount.0.to_big_rational() * &self.relative;
+            let relative = amount.0.to_big_rational() * relative_slippage;
             let relative =
                 big_rational_to_u256(&relative).map_err(|_| super::error::Math::DivisionByZero)?;
 
@@ -65,7 +81,7 @@ impl Parameters {
             price.from_eth(eth::Ether(slippage))
         } else if let Some(price) = self.prices.get(&interaction.output.token) {
             let amount = price.in_eth(interaction.output.amount);
-            let relative = amount.0.to_big_rational() * &self.relative;
+            let relative = amount.0.to_big_rational() * relative_slippage;
             let relative =
                 big_rational_to_u256(&relative).map_err(|_| super::error::Math::DivisionByZero)?;
 
@@ -96,7 +112,7 @@ impl Parameters {
                 output_token = ?interaction.output.token,
                 "unable to compute capped slippage; falling back to relative slippage",
             );
-            let relative = interaction.input.amount.0.to_big_rational() * &self.relative;
+            let relative = interaction.input.amount.0.to_big_rational() * relative_slippage;
             big_rational_to_u256(&relative)
                 .map_err(|_| super::error::Math::DivisionByZero)?
                 .into()
@@ -125,6 +141,10 @@ mod tests {
         "A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"
     ));
 
+    const DAI: eth::H160 = eth::H160(hex_literal::hex!(
+        "6B175474E89094C44Da98b954EedeAC495271d0F"
+    ));
+
     #[test]
     fn test_input_price() {
         let interaction = Interaction {
@@ -147,6 +167,7 @@ mod tests {
         // no cap
         let slippage = Parameters {
             relative: Ratio::from_float(0.1).unwrap(),
+            tiers: Default::default(),
             max: None,
             min: None,
             prices,
@@ -207,6 +228,7 @@ mod tests {
         // no cap
         let slippage = Parameters {
             relative: Ratio::from_float(0.1).unwrap(),
+            tiers: Default::default(),
             max: None,
             min: None,
             prices,
@@ -249,6 +271,7 @@ mod tests {
     fn test_no_price() {
         let slippage = Parameters {
             relative: Ratio::from_float(1.).unwrap(),
+            tiers: Default::default(),
             max: Some(eth::U256::exp10(16)),
             min: Some(eth::U256::exp10(18)),
             prices: Default::default(),
@@ -274,4 +297,52 @@ mod tests {
         );
         assert_eq!(exact_output.0.amount, interaction.output.amount);
     }
+
+    #[test]
+    fn test_volatility_tiers() {
+        let bps = |bps: i32| BigRational::new(bps.into(), 10_000.into());
+        let slippage = Parameters {
+            relative: bps(100),
+            tiers: maplit::hashmap! {
+                USDC.into() => bps(5),
+                DAI.into() => bps(5),
+                GNO.into() => bps(50),
+            },
+            max: None,
+            min: None,
+            prices: Default::default(),
+        };
+        let interaction = |input: eth::H160, output: eth::H160| Interaction {
+            input: Asset {
+                token: input.into(),
+                amount: eth::U256::exp10(18).into(),
+            },
+            output: Asset {
+                token: output.into(),
+                amount: eth::U256::exp10(18).into(),
+            },
+        };
+
+        // Stable pair uses the tight stablecoin tier.
+        let (stable, _) = slippage.apply_to(&interaction(USDC, DAI)).unwrap();
+        assert_eq!(
+            stable.0.amount,
+            (eth::U256::exp10(18) + U256::from(5) * eth::U256::exp10(14)).into()
+        );
+
+        // The volatile token determines the slippage of a mixed pair.
+        let (volatile, _) = slippage.apply_to(&interaction(USDC, GNO)).unwrap();
+        assert_eq!(
+            volatile.0.amount,
+            (eth::U256::exp10(18) + U256::from(5) * eth::U256::exp10(15)).into()
+        );
+
+        // Tokens without a tier use the default slippage.
+        let weth = eth::H160::from_low_u64_be(1);
+        let (default, _) = slippage.apply_to(&interaction(weth, USDC)).unwrap();
+        assert_eq!(
+            default.0.amount,
+            (eth::U256::exp10(18) + eth::U256::exp10(16)).into()
+        );
+    }
 }
@@ -337,6 +337,21 @@ pub struct ClearingPrices {
     pub buy: eth::U256,
 }
 
+/// The value of fees in the token with the clearing price `native`. Fees are
+/// given as amounts together with the clearing price of the token they are
+/// charged in. Returns `None` on overflow or if `native` is zero.
+pub fn native_fee_value(
+    fees: impl IntoIterator<Item = (eth::U256, eth::U256)>,
+    native: eth::U256,
+) -> Option<eth::U256> {
+    // Sum up before dividing to only round once.
+    fees.into_iter()
+        .try_fold(eth::U256::zero(), |total, (amount, price)| {
+            total.checked_add(amount.checked_mul(price)?)
+        })?
+        .checked_div(native)
+}
+
 /// Custom clearing prices at which the trade was executed.
 ///
 /// These prices differ from uniform clearing prices, in that they are adjusted
@@ -422,3 +437,94 @@ pub struct Execution {
     /// The total amount being bought.
     pub buy: eth::Asset,
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{domain::competition::order::signature, util},
+    };
+
+    fn jit(side: Side, partially_fillable: bool) -> order::Jit {
+        order::Jit {
+            sell: eth::Asset {
+                token: eth::H160::from_low_u64_be(0x5e11).into(),
+                amount: eth::U256::from(1000).into(),
+            },
+            buy: eth::Asset {
+                token: eth::H160::from_low_u64_be(0xbbbb).into(),
+                amount: eth::U256::from(500).into(),
+            },
+            receiver: Default::default(),
+            valid_to: util::Timestamp(u32::MAX),
+            app_data: Default::default(),
+            side,
+            sell_token_balance: order::SellTokenBalance::Erc20,
+            buy_token_balance: order::BuyTokenBalance::Erc20,
+            signature: order::Signature {
+                scheme: signature::Scheme::PreSign,
+                data: Default::default(),
+                signer: Default::default(),
+            },
+            uid: Default::default(),
+            partially_fillable,
+        }
+    }
+
+    fn amount(value: u64) -> TargetAmount {
+        eth::U256::from(value).into()
+    }
+
+    fn fee(value: u64) -> SellAmount {
+        SellAmount(value.into())
+    }
+
+    #[test]
+    fn partial_jit_fill() {
+        // Sell orders include the fee in the executed target amount.
+        assert!(Jit::new(jit(Side::Sell, true), amount(600), fee(100)).is_ok());
+        assert!(Jit::new(jit(Side::Sell, true), amount(900), fee(100)).is_ok());
+        // Buy orders don't.
+        assert!(Jit::new(jit(Side::Buy, true), amount(250), fee(100)).is_ok());
+        assert!(Jit::new(jit(Side::Buy, true), amount(500), fee(100)).is_ok());
+    }
+
+    #[test]
+    fn over_filled_partial_jit() {
+        assert!(matches!(
+            Jit::new(jit(Side::Sell, true), amount(1000), fee(1)),
+            Err(error::Trade::InvalidExecutedAmount)
+        ));
+        assert!(matches!(
+            Jit::new(jit(Side::Buy, true), amount(501), fee(0)),
+            Err(error::Trade::InvalidExecutedAmount)
+        ));
+    }
+
+    #[test]
+    fn fill_or_kill_jit() {
+        assert!(Jit::new(jit(Side::Sell, false), amount(900), fee(100)).is_ok());
+        assert!(matches!(
+            Jit::new(jit(Side::Sell, false), amount(600), fee(100)),
+            Err(error::Trade::InvalidExecutedAmount)
+        ));
+    }
+
+    #[test]
+    fn fees_in_native_token() {
+        // Clearing prices: 1 WETH = 1000 units, 1 A = 3 units, 1 B = 1000 units.
+        let weth = eth::U256::from(1000);
+        let fees = [
+            (eth::U256::from(2000), eth::U256::from(3)),
+            (eth::U256::from(5), eth::U256::from(1000)),
+        ];
+        // 2000 A are worth 6 WETH and 5 B are worth 5 WETH.
+        assert_eq!(native_fee_value(fees, weth), Some(eth::U256::from(11)));
+        assert_eq!(native_fee_value([], weth), Some(eth::U256::zero()));
+        assert_eq!(native_fee_value(fees, eth::U256::zero()), None);
+        assert_eq!(
+            native_fee_value([(eth::U256::MAX, eth::U256::from(2))], weth),
+            None
+        );
+    }
+}
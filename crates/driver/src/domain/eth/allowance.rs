@@ -27,16 +27,35 @@ pub struct Required(pub Allowance);
 
 impl Required {
     /// Check if this allowance needs to be approved, and if so, return the
-    /// appropriate [`Approval`].
-    pub fn approval(&self, existing: &Existing) -> Option<Approval> {
+    /// appropriate [`Approval`]s.
+    ///
+    /// Some tokens (e.g. USDT) revert when changing a non-zero allowance to
+    /// another non-zero amount. If `reset` is set for such a token, the
+    /// existing allowance is approved down to zero first.
+    pub fn approval(&self, existing: &Existing, reset: bool) -> Vec<Approval> {
         if self.0.spender != existing.0.spender || self.0.amount <= existing.0.amount {
-            None
+            vec![]
+        } else if reset && !existing.0.amount.is_zero() {
+            vec![Approval(self.0).revoke(), Approval(self.0)]
         } else {
-            Some(Approval(self.0))
+            vec![Approval(self.0)]
         }
     }
 }
 
+/// How much is approved for the allowances required by interactions with
+/// liquidity indexed by the driver.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ApprovalMode {
+    /// Approve the maximal amount possible, which saves approvals in later
+    /// settlements but leaves the allowance in place.
+    #[default]
+    Max,
+    /// Approve exactly the amount used by the interaction, for routers which
+    /// aren't trusted with a lasting allowance.
+    Exact,
+}
+
 /// An approval which needs to be made with an approve() call, see
 /// https://eips.ethereum.org/EIPS/eip-20#methods.
 #[derive(Debug, Clone, Copy)]
@@ -60,3 +79,30 @@ impl Approval {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::domain::eth::H160};
+
+    #[test]
+    fn resets_non_zero_allowances() {
+        let allowance = |amount: u64| Allowance {
+            token: H160([1; 20]).into(),
+            spender: Address(H160([2; 20])),
+            amount: amount.into(),
+        };
+        let required = Required(allowance(100));
+        let amounts = |existing: u64, reset: bool| {
+            required
+                .approval(&Existing(allowance(existing)), reset)
+                .into_iter()
+                .map(|approval| approval.0.amount)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(amounts(100, true), vec![]);
+        assert_eq!(amounts(0, true), vec![100.into()]);
+        assert_eq!(amounts(50, false), vec![100.into()]);
+        assert_eq!(amounts(50, true), vec![0.into(), 100.into()]);
+    }
+}
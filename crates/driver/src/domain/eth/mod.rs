@@ -328,7 +328,7 @@ impl std::iter::Sum for Ether {
 }
 
 /// Block number.
-#[derive(Debug, Clone, Copy, From, Into)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, From, Into)]
 pub struct BlockNo(pub u64);
 
 /// An onchain transaction which interacts with a smart contract.
@@ -363,9 +363,11 @@ impl From<model::interaction::InteractionData> for Interaction {
 #[derive(Clone, Debug, From, Into)]
 pub struct TxId(pub H256);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TxStatus {
-    /// The transaction has been included and executed successfully.
-    Executed,
+    /// The transaction has been included in the given block and executed
+    /// successfully.
+    Executed { block: u64 },
     /// The transaction has been included but execution failed.
     Reverted,
     /// The transaction has not been included yet.
@@ -4,7 +4,11 @@
 use {
     crate::domain::eth,
     derive_more::{From, Into},
-    std::cmp::Ordering,
+    itertools::Itertools,
+    std::{
+        cmp::Ordering,
+        collections::{BTreeMap, HashMap, HashSet},
+    },
 };
 
 pub mod balancer;
@@ -19,6 +23,27 @@ pub struct Liquidity {
     /// Estimation of gas needed to use this liquidity on-chain.
     pub gas: eth::Gas,
     pub kind: Kind,
+    /// The block whose state this liquidity was fetched at, if known.
+    /// Liquidity fetched at a recent block may be served from a cache which
+    /// lags behind by a few blocks, so its exact block is not known.
+    pub fetched_at: Option<eth::BlockNo>,
+}
+
+impl Liquidity {
+    /// The tokens which can be traded with this liquidity.
+    pub fn tokens(&self) -> Vec<eth::TokenAddress> {
+        match &self.kind {
+            Kind::UniswapV2(pool) => pool.reserves.iter().map(|r| r.token).collect(),
+            Kind::UniswapV3(pool) => vec![pool.tokens.get().0, pool.tokens.get().1],
+            Kind::BalancerV2Stable(pool) => pool.reserves.tokens().collect(),
+            Kind::BalancerV2Weighted(pool) => pool.reserves.tokens().collect(),
+            Kind::Swapr(pool) => pool.base.reserves.iter().map(|r| r.token).collect(),
+            Kind::ZeroEx(limit_order) => vec![
+                limit_order.order.maker_token.into(),
+                limit_order.order.taker_token.into(),
+            ],
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, From, Into)]
@@ -91,6 +116,106 @@ impl TokenPair {
 #[error("token pair must have distict token addresses")]
 pub struct InvalidTokenPair;
 
+/// The number of liquidity sources available for token pairs, by kind of
+/// liquidity. Pairs without any liquidity can only be traded against other
+/// orders, i.e. in a CoW.
+#[derive(Debug, Default, Clone)]
+pub struct Coverage(HashMap<TokenPair, BTreeMap<&'static str, usize>>);
+
+impl Coverage {
+    /// Counts the liquidity available for each of the pairs. Liquidity of more
+    /// than two tokens covers every pair of its tokens.
+    pub fn new(pairs: &HashSet<TokenPair>, liquidity: &[Liquidity]) -> Self {
+        let mut coverage: HashMap<_, BTreeMap<_, _>> = pairs
+            .iter()
+            .map(|pair| (*pair, Default::default()))
+            .collect();
+        for liquidity in liquidity {
+            for (a, b) in liquidity.tokens().into_iter().tuple_combinations() {
+                let Ok(pair) = TokenPair::new(a, b) else {
+                    continue;
+                };
+                if let Some(sources) = coverage.get_mut(&pair) {
+                    *sources.entry((&liquidity.kind).into()).or_default() += 1;
+                }
+            }
+        }
+        Self(coverage)
+    }
+
+    /// The number of liquidity sources per kind available for the pair.
+    pub fn sources(&self, pair: &TokenPair) -> Option<&BTreeMap<&'static str, usize>> {
+        self.0.get(pair)
+    }
+
+    /// The total number of liquidity sources available for each pair.
+    pub fn totals(&self) -> impl Iterator<Item = (&TokenPair, usize)> {
+        self.0
+            .iter()
+            .map(|(pair, sources)| (pair, sources.values().sum()))
+    }
+
+    /// The pairs without any liquidity.
+    pub fn uncovered(&self) -> impl Iterator<Item = &TokenPair> {
+        self.totals()
+            .filter(|(_, total)| *total == 0)
+            .map(|(pair, _)| pair)
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error("swap parameters do not match pool")]
 pub struct InvalidSwap;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_coverage_per_pair_and_source() {
+        let token = |byte| eth::TokenAddress(eth::H160::repeat_byte(byte).into());
+        let pair = |a, b| TokenPair::new(token(a), token(b)).unwrap();
+        let pool = |id, a, b| Liquidity {
+            id: Id(id),
+            gas: Default::default(),
+            kind: Kind::UniswapV2(uniswap::v2::Pool {
+                address: eth::H160::repeat_byte(0xff).into(),
+                router: eth::H160::repeat_byte(0xfe).into(),
+                reserves: uniswap::v2::Reserves::new(
+                    eth::Asset {
+                        token: token(a),
+                        amount: eth::U256::one().into(),
+                    },
+                    eth::Asset {
+                        token: token(b),
+                        amount: eth::U256::one().into(),
+                    },
+                )
+                .unwrap(),
+            }),
+            fetched_at: None,
+        };
+
+        let pairs = HashSet::from([pair(1, 2), pair(2, 3), pair(3, 4)]);
+        let liquidity = [
+            pool(0, 1, 2),
+            pool(1, 2, 1),
+            pool(2, 3, 2),
+            // Not part of the requested pairs.
+            pool(3, 1, 4),
+        ];
+        let coverage = Coverage::new(&pairs, &liquidity);
+
+        assert_eq!(
+            coverage.sources(&pair(1, 2)),
+            Some(&BTreeMap::from([("UniswapV2", 2)]))
+        );
+        assert_eq!(
+            coverage.sources(&pair(2, 3)),
+            Some(&BTreeMap::from([("UniswapV2", 1)]))
+        );
+        assert_eq!(coverage.sources(&pair(3, 4)), Some(&BTreeMap::new()));
+        assert_eq!(coverage.sources(&pair(1, 4)), None);
+        assert_eq!(coverage.uncovered().collect::<Vec<_>>(), vec![&pair(3, 4)]);
+    }
+}
@@ -9,6 +9,7 @@ use {
     },
     ethrpc::current_block::into_stream,
     futures::{future::select_ok, FutureExt, StreamExt},
+    std::future::Future,
     thiserror::Error,
     tracing::Instrument,
 };
@@ -46,10 +47,15 @@ impl Mempools {
         let (tx_hash, _remaining_futures) =
             select_ok(self.mempools.iter().cloned().map(|mempool| {
                 async move {
-                    let result = self
-                        .submit(&mempool, solver, settlement, submission_deadline)
-                        .instrument(tracing::info_span!("mempool", kind = mempool.to_string()))
-                        .await;
+                    let span = tracing::info_span!("mempool", kind = mempool.to_string());
+                    let result = {
+                        let mempool = &mempool;
+                        with_retries(mempool.config().max_retries, |attempt| {
+                            self.submit(mempool, solver, settlement, submission_deadline, attempt)
+                        })
+                    }
+                    .instrument(span)
+                    .await;
                     observe::mempool_executed(&mempool, settlement, &result);
                     result
                 }
@@ -81,6 +87,7 @@ impl Mempools {
         solver: &Solver,
         settlement: &Settlement,
         submission_deadline: BlockNo,
+        attempt: usize,
     ) -> Result<eth::TxId, Error> {
         // Don't submit risky transactions if revert protection is
         // enabled and the settlement may revert in this mempool.
@@ -91,7 +98,60 @@ impl Mempools {
             return Err(Error::Disabled);
         }
 
-        let tx = settlement.transaction(settlement::Internalization::Enable);
+        // Don't submit at a gas price invalidating the assumptions the settlement
+        // was scored under.
+        let gas = match mempool.config().max_gas_price_increase {
+            Some(max_increase) => {
+                let fresh = self
+                    .ethereum
+                    .gas_price()
+                    .await
+                    .map_err(|err| Error::Other(err.into()))?;
+                settlement::Gas {
+                    price: submission_gas_price(settlement.gas.price, fresh, max_increase)
+                        .ok_or(Error::GasPriceIncreased)?,
+                    ..settlement.gas
+                }
+            }
+            None => settlement.gas,
+        };
+
+        // Resubmissions need to outbid the failed submissions and are only worth
+        // it if the settlement can still make it in time and still succeeds.
+        let gas = if attempt > 0 {
+            if self.ethereum.current_block().borrow().number >= submission_deadline {
+                return Err(Error::Expired);
+            }
+            let tx = settlement.transaction(settlement::Internalization::Enable);
+            if let Err(err) = self.ethereum.estimate_gas(tx).await {
+                return Err(if err.is_revert() {
+                    Error::SimulationRevert
+                } else {
+                    Error::Other(err.into())
+                });
+            }
+            settlement::Gas {
+                price: gas.price * GAS_PRICE_BUMP.powi(attempt as i32),
+                ..gas
+            }
+        } else {
+            gas
+        };
+
+        let tx = mempool.transaction(
+            settlement
+                .transaction(settlement::Internalization::Enable)
+                .clone(),
+        );
+        // Settlements of smart contract accounts need to be approved by their
+        // co-signer first.
+        let tx = match solver.co_signer() {
+            Some(co_signer) => co_signer
+                .co_sign(tx)
+                .await
+                .map_err(|err| Error::Other(err.into()))?,
+            None => tx,
+        };
 
         // Instantiate block stream and skip the current block before we submit the
         // settlement. This way we only run iterations in blocks that can potentially
@@ -99,24 +159,38 @@ impl Mempools {
         let mut block_stream = into_stream(self.ethereum.current_block().clone());
         block_stream.next().await;
 
-        let hash = mempool.submit(tx.clone(), settlement.gas, solver).await?;
+        let hash = mempool.submit(tx.clone(), gas, solver).await?;
 
-        // Wait for the transaction to be mined, expired or failing.
+        // Wait for the transaction to be mined and confirmed, expired or failing.
+        let mut included = None;
         let result = async {
             while let Some(block) = block_stream.next().await {
                 tracing::debug!(?hash, "checking if tx is confirmed");
-                let receipt = self
+                let status = self
                     .ethereum
                     .transaction_status(&hash)
                     .await
-                    .unwrap_or_else(|err| {
+                    .map_err(|err| {
                         tracing::warn!(?hash, ?err, "failed to get transaction status",);
-                        TxStatus::Pending
-                    });
-                match receipt {
-                    TxStatus::Executed => return Ok(hash.clone()),
-                    TxStatus::Reverted => return Err(Error::Revert(hash.clone())),
-                    TxStatus::Pending => {
+                    })
+                    .ok();
+                match confirmation(
+                    status,
+                    included,
+                    block.number,
+                    mempool.config().confirmations,
+                ) {
+                    Confirmation::Final => return Ok(hash.clone()),
+                    Confirmation::Reverted => return Err(Error::Revert(hash.clone())),
+                    Confirmation::Included(inclusion) => {
+                        tracing::debug!(?hash, inclusion, "tx included, waiting for confirmations");
+                        included = Some(inclusion);
+                    }
+                    Confirmation::Reorged => {
+                        tracing::warn!(?hash, ?included, "tx disappeared in a reorg");
+                        return Err(Error::Reorged(hash.clone()));
+                    }
+                    Confirmation::Pending => {
                         // Check if the current block reached the submission deadline block number
                         if block.number >= submission_deadline {
                             tracing::info!(
@@ -125,18 +199,18 @@ impl Mempools {
                                 current_block = block.number,
                                 "tx not confirmed in time, cancelling",
                             );
-                            self.cancel(mempool, settlement.gas.price, solver).await?;
+                            self.cancel(mempool, gas.price, solver).await?;
                             return Err(Error::Expired);
                         }
                         // Check if transaction still simulates
-                        if let Err(err) = self.ethereum.estimate_gas(tx).await {
+                        if let Err(err) = self.ethereum.estimate_gas(&tx).await {
                             if err.is_revert() {
                                 tracing::info!(
                                     ?hash,
                                     ?err,
                                     "tx started failing in mempool, cancelling"
                                 );
-                                self.cancel(mempool, settlement.gas.price, solver).await?;
+                                self.cancel(mempool, gas.price, solver).await?;
                                 return Err(Error::SimulationRevert);
                             } else {
                                 tracing::warn!(?hash, ?err, "couldn't re-simulate tx");
@@ -151,10 +225,10 @@ impl Mempools {
         }
         .await;
 
-        if result.is_err() {
+        if result.is_err() && !matches!(result, Err(Error::Reorged(_))) {
             // Do one last attempt to see if the transaction was confirmed (in case of race
             // conditions or misclassified errors like `OrderFilled` simulation failures).
-            if let Ok(TxStatus::Executed) = self.ethereum.transaction_status(&hash).await {
+            if let Ok(TxStatus::Executed { .. }) = self.ethereum.transaction_status(&hash).await {
                 tracing::info!(?hash, "Found confirmed transaction, ignoring error");
                 return Ok(hash);
             }
@@ -187,6 +261,86 @@ impl Mempools {
     }
 }
 
+/// Calls `submit` until it succeeds or fails with an error that isn't
+/// retryable, at most `max_retries + 1` times. Settlements dropped by a reorg
+/// are always resubmitted and don't count towards `max_retries`. `submit` gets
+/// passed the number of previously failed attempts.
+async fn with_retries<F, Fut>(max_retries: usize, mut submit: F) -> Result<eth::TxId, Error>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<eth::TxId, Error>>,
+{
+    let mut attempt = 0;
+    let mut retries = 0;
+    loop {
+        match submit(attempt).await {
+            Err(err @ Error::Reorged(_)) => {
+                attempt += 1;
+                tracing::info!(?err, attempt, "resubmitting reorged settlement");
+            }
+            Err(err) if err.is_retryable() && retries < max_retries => {
+                attempt += 1;
+                retries += 1;
+                tracing::info!(?err, attempt, "resubmitting failed settlement");
+            }
+            result => return result,
+        }
+    }
+}
+
+/// The state of a submitted settlement after checking its status.
+#[derive(Debug, PartialEq, Eq)]
+enum Confirmation {
+    /// The settlement got included with enough blocks built on top of it.
+    Final,
+    /// The settlement got included in the given block but doesn't have enough
+    /// confirmations yet.
+    Included(BlockNo),
+    /// The settlement got included but reverted.
+    Reverted,
+    /// The settlement hasn't been included yet.
+    Pending,
+    /// The settlement was included before but disappeared in a reorg.
+    Reorged,
+}
+
+/// Classifies the `status` of a settlement in block `current` which requires
+/// `confirmations` blocks on top of the including block to be final.
+/// `included` is the block it was last seen included in, if any. The status is
+/// `None` if it couldn't be fetched, in which case the settlement is assumed to
+/// be where it was last seen.
+fn confirmation(
+    status: Option<TxStatus>,
+    included: Option<BlockNo>,
+    current: BlockNo,
+    confirmations: u64,
+) -> Confirmation {
+    match (status, included) {
+        (Some(TxStatus::Executed { block }), _)
+            if current >= block.saturating_add(confirmations) =>
+        {
+            Confirmation::Final
+        }
+        (Some(TxStatus::Executed { block }), _) => Confirmation::Included(block),
+        (Some(TxStatus::Reverted), _) => Confirmation::Reverted,
+        (Some(TxStatus::Pending), Some(_)) => Confirmation::Reorged,
+        (None, Some(block)) => Confirmation::Included(block),
+        (Some(TxStatus::Pending), None) | (None, None) => Confirmation::Pending,
+    }
+}
+
+/// The gas price to submit a settlement with, given the gas price it was scored
+/// with and a fresh estimate. Returns `None` if the fresh gas price exceeds the
+/// scored one by more than `max_increase` (as a fraction of the scored price).
+fn submission_gas_price(
+    scored: eth::GasPrice,
+    fresh: eth::GasPrice,
+    max_increase: f64,
+) -> Option<eth::GasPrice> {
+    let limit = scored.effective().0 .0.to_f64_lossy() * (1.0 + max_increase);
+    (fresh.effective().0 .0.to_f64_lossy() <= limit).then_some(fresh)
+}
+
 #[derive(Debug, Error)]
 #[error("no mempools configured, cannot execute settlements")]
 pub struct NoMempools;
@@ -209,6 +363,139 @@ pub enum Error {
     Expired,
     #[error("Strategy disabled for this tx")]
     Disabled,
+    #[error("Gas price increased too much since the settlement was scored")]
+    GasPriceIncreased,
+    #[error("Included transaction disappeared in a reorg: {0:?}")]
+    Reorged(eth::TxId),
+    #[error(
+        "Nonce {0} was taken again after refetching it, the solver account is used concurrently"
+    )]
+    NonceConflict(eth::U256),
     #[error("Failed to submit: {0:?}")]
     Other(#[from] anyhow::Error),
 }
+
+impl Error {
+    /// Whether resubmitting the settlement may succeed. Settlements which
+    /// started reverting or can no longer make it in time are given up.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Revert(_) | Self::Other(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn declines_gas_price_above_margin() {
+        let gwei = |amount: u64| eth::U256::from(amount) * eth::U256::exp10(9);
+        let price =
+            |base: u64| eth::GasPrice::new(gwei(1_000).into(), gwei(1).into(), gwei(base).into());
+        // Scored with an effective gas price of 20 gwei, allowing a 10% increase.
+        let scored = price(19);
+
+        let fresh = submission_gas_price(scored, price(20), 0.1).unwrap();
+        assert_eq!(fresh.effective().0, gwei(21).into());
+        assert!(submission_gas_price(scored, price(21), 0.1).is_some());
+        assert!(submission_gas_price(scored, price(22), 0.1).is_none());
+        // Cheaper gas is always fine.
+        assert!(submission_gas_price(scored, price(5), 0.0).is_some());
+    }
+
+    #[tokio::test]
+    async fn resubmits_after_retryable_failure() {
+        let hash = eth::TxId(eth::H256::from_low_u64_be(1));
+        let mut attempts = Vec::new();
+        let result = with_retries(2, |attempt| {
+            attempts.push(attempt);
+            let result = match attempt {
+                0 => Err(Error::Other(anyhow::anyhow!("transaction underpriced"))),
+                _ => Ok(hash.clone()),
+            };
+            async move { result }
+        })
+        .await;
+
+        assert_eq!(result.unwrap().0, hash.0);
+        assert_eq!(attempts, vec![0, 1]);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_non_retryable_failure_or_max_retries() {
+        let mut attempts = 0;
+        let result = with_retries(2, |_| {
+            attempts += 1;
+            async { Err(Error::SimulationRevert) }
+        })
+        .await;
+        assert!(matches!(result, Err(Error::SimulationRevert)));
+        assert_eq!(attempts, 1);
+
+        let mut attempts = 0;
+        let result = with_retries(2, |_| {
+            attempts += 1;
+            async { Err(Error::Other(anyhow::anyhow!("transaction underpriced"))) }
+        })
+        .await;
+        assert!(matches!(result, Err(Error::Other(_))));
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn waits_for_confirmations() {
+        let executed = Some(TxStatus::Executed { block: 10 });
+
+        // Without a confirmation depth settlements are final once included.
+        assert_eq!(confirmation(executed, None, 10, 0), Confirmation::Final);
+
+        assert_eq!(
+            confirmation(executed, None, 10, 2),
+            Confirmation::Included(10)
+        );
+        assert_eq!(
+            confirmation(executed, Some(10), 11, 2),
+            Confirmation::Included(10)
+        );
+        assert_eq!(confirmation(executed, Some(10), 12, 2), Confirmation::Final);
+        assert_eq!(
+            confirmation(Some(TxStatus::Pending), None, 10, 2),
+            Confirmation::Pending
+        );
+    }
+
+    #[test]
+    fn keeps_waiting_when_status_is_unknown() {
+        // Failing to fetch the status of an included settlement isn't a reorg.
+        assert_eq!(
+            confirmation(None, Some(10), 11, 2),
+            Confirmation::Included(10)
+        );
+        assert_eq!(confirmation(None, None, 11, 2), Confirmation::Pending);
+    }
+
+    #[tokio::test]
+    async fn resubmits_after_reorg() {
+        let hash = eth::TxId(eth::H256::from_low_u64_be(1));
+        // The settlement got included in block 10 but a reorg dropped it.
+        assert_eq!(
+            confirmation(Some(TxStatus::Pending), Some(10), 11, 2),
+            Confirmation::Reorged
+        );
+
+        // Reorged settlements get resubmitted even if no retries are configured.
+        let mut attempts = Vec::new();
+        let result = with_retries(0, |attempt| {
+            attempts.push(attempt);
+            let result = match attempt {
+                0 => Err(Error::Reorged(hash.clone())),
+                _ => Ok(hash.clone()),
+            };
+            async move { result }
+        })
+        .await;
+
+        assert_eq!(result.unwrap().0, hash.0);
+        assert_eq!(attempts, vec![0, 1]);
+    }
+}
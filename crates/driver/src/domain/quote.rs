@@ -140,7 +140,7 @@ impl Order {
                 sell: self.sell(),
                 side: self.side,
                 kind: if quote_using_limit_orders {
-                    competition::order::Kind::Limit
+                    competition::order::Kind::Limit { oracle: None }
                 } else {
                     competition::order::Kind::Market
                 },
@@ -177,12 +177,13 @@ impl Order {
             ]
             .into_iter(),
             self.deadline,
+            infra::time::now(),
             eth,
             HashSet::default(),
         )
         .await
         .map_err(|err| match err {
-            auction::Error::InvalidTokens => panic!("fake auction with invalid tokens"),
+            auction::Error::InvalidTokens(_) => panic!("fake auction with invalid tokens"),
             auction::Error::InvalidAmounts => panic!("fake auction with invalid amounts"),
             auction::Error::Blockchain(e) => e.into(),
         })
@@ -282,6 +283,7 @@ mod encode {
     ) -> Result<Vec<eth::Interaction>, solution::encoding::Error> {
         let slippage = solution::slippage::Parameters {
             relative: Ratio::new_raw(DEFAULT_QUOTE_SLIPPAGE_BPS.into(), 10_000.into()),
+            tiers: Default::default(),
             max: None,
             min: None,
             prices: Default::default(),
@@ -299,7 +301,7 @@ mod encode {
         };
 
         Ok(interaction
-            .allowances()
+            .allowances(eth::allowance::ApprovalMode::Max, &slippage)?
             .iter()
             .flat_map(|Required(allowance)| {
                 let approval = Approval(*allowance);
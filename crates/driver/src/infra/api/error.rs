@@ -1,6 +1,6 @@
 use {
     crate::{
-        domain::{competition, quote},
+        domain::{competition, eth, quote},
         infra::api,
     },
     serde::Serialize,
@@ -20,6 +20,9 @@ enum Kind {
     InvalidAmounts,
     QuoteSameTokens,
     FailedToSubmit,
+    SolverPaused,
+    OrderExpired,
+    AuctionTooOld,
 }
 
 #[derive(Debug, Serialize)]
@@ -27,6 +30,10 @@ enum Kind {
 pub struct Error {
     kind: Kind,
     description: &'static str,
+    /// The tokens which caused the error, e.g. the ones missing from an
+    /// auction.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tokens: Vec<eth::H160>,
 }
 
 impl From<Kind> for (hyper::StatusCode, axum::Json<Error>) {
@@ -51,12 +58,16 @@ impl From<Kind> for (hyper::StatusCode, axum::Json<Error>) {
                  or sell amount"
             }
             Kind::FailedToSubmit => "Could not submit the solution to the blockchain",
+            Kind::SolverPaused => "The solver is paused and doesn't participate in auctions",
+            Kind::OrderExpired => "An order of the solution expired before it could be settled",
+            Kind::AuctionTooOld => "The auction is older than the configured maximum age",
         };
         (
             hyper::StatusCode::BAD_REQUEST,
             axum::Json(Error {
                 kind: value,
                 description,
+                tokens: Default::default(),
             }),
         )
     }
@@ -83,6 +94,9 @@ impl From<competition::Error> for (hyper::StatusCode, axum::Json<Error>) {
             competition::Error::DeadlineExceeded(_) => Kind::DeadlineExceeded,
             competition::Error::Solver(_) => Kind::SolverFailed,
             competition::Error::SubmissionError => Kind::FailedToSubmit,
+            competition::Error::SolverPaused => Kind::SolverPaused,
+            competition::Error::OrderExpired => Kind::OrderExpired,
+            competition::Error::AuctionTooOld => Kind::AuctionTooOld,
         };
         error.into()
     }
@@ -90,14 +104,21 @@ impl From<competition::Error> for (hyper::StatusCode, axum::Json<Error>) {
 
 impl From<api::routes::AuctionError> for (hyper::StatusCode, axum::Json<Error>) {
     fn from(value: api::routes::AuctionError) -> Self {
-        let error = match value {
-            api::routes::AuctionError::InvalidAuctionId => Kind::InvalidAuctionId,
-            api::routes::AuctionError::MissingSurplusFee => Kind::MissingSurplusFee,
-            api::routes::AuctionError::InvalidTokens => Kind::InvalidTokens,
-            api::routes::AuctionError::InvalidAmounts => Kind::InvalidAmounts,
-            api::routes::AuctionError::Blockchain(_) => Kind::Unknown,
+        let (error, tokens) = match value {
+            api::routes::AuctionError::InvalidAuctionId => (Kind::InvalidAuctionId, vec![]),
+            api::routes::AuctionError::MissingSurplusFee => (Kind::MissingSurplusFee, vec![]),
+            api::routes::AuctionError::InvalidTokens(tokens) => (Kind::InvalidTokens, tokens),
+            api::routes::AuctionError::InvalidAmounts => (Kind::InvalidAmounts, vec![]),
+            api::routes::AuctionError::Blockchain(_) => (Kind::Unknown, vec![]),
         };
-        error.into()
+        let (status, axum::Json(error)) = Self::from(error);
+        (
+            status,
+            axum::Json(Error {
+                tokens: tokens.into_iter().map(Into::into).collect(),
+                ..error
+            }),
+        )
     }
 }
 
@@ -109,3 +130,32 @@ impl From<api::routes::OrderError> for (hyper::StatusCode, axum::Json<Error>) {
         error.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_tokens_are_listed() {
+        let token = eth::H160::from_low_u64_be(1);
+        let (status, axum::Json(error)) = <(hyper::StatusCode, axum::Json<Error>)>::from(
+            api::routes::AuctionError::InvalidTokens(vec![token.into()]),
+        );
+        assert_eq!(status, hyper::StatusCode::BAD_REQUEST);
+        assert_eq!(
+            serde_json::to_value(error).unwrap(),
+            serde_json::json!({
+                "kind": "InvalidTokens",
+                "description": "Invalid tokens specified in the auction, the tokens for some \
+                                orders are missing",
+                "tokens": ["0x0000000000000000000000000000000000000001"],
+            })
+        );
+
+        // Other errors don't list any tokens.
+        let (_, axum::Json(error)) = <(hyper::StatusCode, axum::Json<Error>)>::from(
+            api::routes::AuctionError::InvalidAmounts,
+        );
+        assert!(serde_json::to_value(error).unwrap().get("tokens").is_none());
+    }
+}
@@ -50,9 +50,10 @@ impl Api {
         let tokens = tokens::Fetcher::new(&self.eth);
         let pre_processor = domain::competition::AuctionProcessor::new(&self.eth);
 
-        // Add the metrics and healthz endpoints.
+        // Add the metrics, healthz and config endpoints.
         app = routes::metrics(app);
         app = routes::healthz(app);
+        app = routes::config(app, &self.solvers);
 
         // Multiplex each solver as part of the API. Multiple solvers are multiplexed
         // on the same driver so only one liquidity collector collects the liquidity
@@ -66,6 +67,9 @@ impl Api {
             let router = routes::solve(router);
             let router = routes::reveal(router);
             let router = routes::settle(router);
+            let router = routes::auction(router);
+            let router = routes::control(router);
+            let router = routes::report(router);
             let router = router.with_state(State(Arc::new(Inner {
                 eth: self.eth.clone(),
                 solver: solver.clone(),
@@ -73,9 +77,13 @@ impl Api {
                     solver,
                     eth: self.eth.clone(),
                     liquidity: self.liquidity.clone(),
+                    tokens: tokens.clone(),
                     simulator: self.simulator.clone(),
                     mempools: self.mempools.clone(),
                     settlement: Default::default(),
+                    auction: Default::default(),
+                    report: Default::default(),
+                    paused: Default::default(),
                 },
                 liquidity: self.liquidity.clone(),
                 tokens: tokens.clone(),
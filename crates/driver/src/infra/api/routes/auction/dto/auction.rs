@@ -0,0 +1,25 @@
+use {
+    crate::domain::competition,
+    serde::Serialize,
+    serde_with::{serde_as, DisplayFromStr},
+};
+
+impl Auction {
+    pub fn new(auction: competition::Summary) -> Self {
+        Self {
+            id: auction.id.map(|id| id.0),
+            orders: auction.orders,
+            deadline: auction.deadline,
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Auction {
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    id: Option<i64>,
+    orders: usize,
+    deadline: chrono::DateTime<chrono::Utc>,
+}
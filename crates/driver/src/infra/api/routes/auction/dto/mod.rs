@@ -0,0 +1,3 @@
+mod auction;
+
+pub use auction::Auction;
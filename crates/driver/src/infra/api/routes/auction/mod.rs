@@ -0,0 +1,17 @@
+mod dto;
+
+use crate::infra::api::State;
+
+pub(in crate::infra::api) fn auction(router: axum::Router<State>) -> axum::Router<State> {
+    router.route("/auction", axum::routing::get(route))
+}
+
+async fn route(
+    state: axum::extract::State<State>,
+) -> Result<axum::Json<dto::Auction>, hyper::StatusCode> {
+    state
+        .competition()
+        .latest_auction()
+        .map(|auction| axum::Json(dto::Auction::new(auction)))
+        .ok_or(hyper::StatusCode::NOT_FOUND)
+}
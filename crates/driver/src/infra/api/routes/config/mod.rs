@@ -0,0 +1,15 @@
+mod dto;
+
+use crate::infra::solver::Solver;
+
+pub(in crate::infra::api) fn config(app: axum::Router<()>, solvers: &[Solver]) -> axum::Router<()> {
+    // The configuration doesn't change at runtime so it's only collected once.
+    let config = dto::Config::new(solvers);
+    app.route(
+        "/config",
+        axum::routing::get(move || {
+            let config = config.clone();
+            async move { axum::Json(config) }
+        }),
+    )
+}
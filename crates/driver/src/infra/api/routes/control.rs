@@ -0,0 +1,48 @@
+//! Endpoints pausing and resuming the participation of a solver in
+//! competitions at runtime, e.g. to stop a misbehaving solver during an
+//! incident without redeploying the driver.
+
+use crate::infra::{api::State, observe};
+
+pub(in crate::infra::api) fn control(router: axum::Router<State>) -> axum::Router<State> {
+    router
+        .route("/pause", axum::routing::post(pause))
+        .route("/resume", axum::routing::post(resume))
+}
+
+async fn pause(
+    state: axum::extract::State<State>,
+    headers: hyper::HeaderMap,
+) -> Result<hyper::StatusCode, hyper::StatusCode> {
+    authorize(&state, &headers)?;
+    state.competition().pause();
+    observe::paused(state.solver().name(), true);
+    Ok(hyper::StatusCode::OK)
+}
+
+async fn resume(
+    state: axum::extract::State<State>,
+    headers: hyper::HeaderMap,
+) -> Result<hyper::StatusCode, hyper::StatusCode> {
+    authorize(&state, &headers)?;
+    state.competition().resume();
+    observe::paused(state.solver().name(), false);
+    Ok(hyper::StatusCode::OK)
+}
+
+/// Checks that the request carries the solver's control token. The endpoints
+/// don't exist for solvers without a control token.
+fn authorize(state: &State, headers: &hyper::HeaderMap) -> Result<(), hyper::StatusCode> {
+    let token = state
+        .solver()
+        .control_token()
+        .ok_or(hyper::StatusCode::NOT_FOUND)?;
+    let authorized = headers
+        .get(hyper::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| token.authorizes(value));
+    if !authorized {
+        return Err(hyper::StatusCode::UNAUTHORIZED);
+    }
+    Ok(())
+}
@@ -1,16 +1,24 @@
+mod auction;
+mod config;
+mod control;
 mod healthz;
 mod info;
 mod metrics;
 mod quote;
+mod report;
 mod reveal;
 mod settle;
 mod solve;
 
 pub(super) use {
+    auction::auction,
+    config::config,
+    control::control,
     healthz::healthz,
     info::info,
     metrics::metrics,
     quote::{quote, OrderError},
+    report::report,
     reveal::reveal,
     settle::settle,
     solve::{solve, AuctionError},
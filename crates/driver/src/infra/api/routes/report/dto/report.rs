@@ -0,0 +1,42 @@
+use {
+    crate::{
+        domain::{competition, eth},
+        util::serialize,
+    },
+    serde::Serialize,
+    serde_with::{serde_as, DisplayFromStr},
+    std::collections::BTreeMap,
+};
+
+impl SolveReport {
+    pub fn new(report: competition::SolveReport) -> Self {
+        Self {
+            auction_id: report.auction_id.map(|id| id.0),
+            orders: report.orders,
+            solved_orders: report.solved_orders,
+            solutions: report.solutions,
+            skipped_orders: report.skipped_orders,
+            skipped_solutions: report.skipped_solutions,
+            merges: report.merges,
+            winner_score: report.winner_score.map(|score| score.0),
+            duration_ms: report.duration.as_millis(),
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolveReport {
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    auction_id: Option<i64>,
+    orders: usize,
+    solved_orders: usize,
+    solutions: usize,
+    skipped_orders: BTreeMap<&'static str, usize>,
+    skipped_solutions: BTreeMap<&'static str, usize>,
+    merges: usize,
+    #[serde_as(as = "Option<serialize::U256>")]
+    winner_score: Option<eth::U256>,
+    duration_ms: u128,
+}
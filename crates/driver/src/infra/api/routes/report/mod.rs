@@ -0,0 +1,17 @@
+mod dto;
+
+use crate::infra::api::State;
+
+pub(in crate::infra::api) fn report(router: axum::Router<State>) -> axum::Router<State> {
+    router.route("/report", axum::routing::get(route))
+}
+
+async fn route(
+    state: axum::extract::State<State>,
+) -> Result<axum::Json<dto::SolveReport>, hyper::StatusCode> {
+    state
+        .competition()
+        .latest_report()
+        .map(|report| axum::Json(dto::SolveReport::new(report)))
+        .ok_or(hyper::StatusCode::NOT_FOUND)
+}
@@ -1,7 +1,11 @@
 use {
-    crate::{domain::competition, util::serialize},
+    crate::{
+        domain::{competition, competition::order, eth},
+        util::serialize,
+    },
     serde::Serialize,
     serde_with::serde_as,
+    std::collections::HashMap,
 };
 
 impl Revealed {
@@ -11,15 +15,32 @@ impl Revealed {
                 internalized: reveal.internalized_calldata.into(),
                 uninternalized: reveal.uninternalized_calldata.into(),
             },
+            surpluses: reveal
+                .surpluses
+                .into_iter()
+                .map(|(order_id, surplus)| {
+                    (
+                        order_id.into(),
+                        Surplus {
+                            token: surplus.token.into(),
+                            amount: surplus.amount.into(),
+                        },
+                    )
+                })
+                .collect(),
         }
     }
 }
 
+type OrderId = [u8; order::UID_LEN];
+
 #[serde_as]
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Revealed {
     calldata: Calldata,
+    #[serde_as(as = "HashMap<serialize::Hex, _>")]
+    surpluses: HashMap<OrderId, Surplus>,
 }
 
 #[serde_as]
@@ -31,3 +52,13 @@ struct Calldata {
     #[serde_as(as = "serialize::Hex")]
     uninternalized: Vec<u8>,
 }
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Surplus {
+    /// The token the surplus is denominated in.
+    token: eth::H160,
+    #[serde_as(as = "serialize::U256")]
+    amount: eth::U256,
+}
@@ -49,7 +49,11 @@ impl Auction {
                     },
                     kind: match order.class {
                         Class::Market => competition::order::Kind::Market,
-                        Class::Limit => competition::order::Kind::Limit,
+                        Class::Limit => competition::order::Kind::Limit {
+                            oracle: order
+                                .oracle_limit_offset_bps
+                                .map(|offset_bps| competition::order::OracleLimit { offset_bps }),
+                        },
                         Class::Liquidity => competition::order::Kind::Liquidity,
                     },
                     app_data: order.app_data.into(),
@@ -132,8 +136,11 @@ impl Auction {
                                 max_volume_factor,
                                 quote: quote.into_domain(order.sell_token, order.buy_token),
                             },
-                            FeePolicy::Volume { factor } => {
-                                competition::order::FeePolicy::Volume { factor }
+                            FeePolicy::Volume { factor, recipient } => {
+                                competition::order::FeePolicy::Volume {
+                                    factor,
+                                    recipient: recipient.map(Into::into),
+                                }
                             }
                         })
                         .collect(),
@@ -151,6 +158,7 @@ impl Auction {
                 }
             }),
             time::Deadline::new(self.deadline, timeouts),
+            self.created,
             eth,
             self.surplus_capturing_jit_order_owners
                 .into_iter()
@@ -168,8 +176,9 @@ pub enum Error {
     InvalidAuctionId,
     #[error("surplus fee is missing for limit order")]
     MissingSurplusFee,
-    #[error("invalid tokens in auction")]
-    InvalidTokens,
+    /// See [`auction::Error::InvalidTokens`].
+    #[error("auction orders reference missing tokens: {0:?}")]
+    InvalidTokens(Vec<eth::TokenAddress>),
     #[error("invalid order amounts in auction")]
     InvalidAmounts,
     #[error("blockchain error: {0:?}")]
@@ -185,7 +194,7 @@ impl From<auction::InvalidId> for Error {
 impl From<auction::Error> for Error {
     fn from(value: auction::Error) -> Self {
         match value {
-            auction::Error::InvalidTokens => Self::InvalidTokens,
+            auction::Error::InvalidTokens(tokens) => Self::InvalidTokens(tokens),
             auction::Error::InvalidAmounts => Self::InvalidAmounts,
             auction::Error::Blockchain(err) => Self::Blockchain(err),
         }
@@ -201,6 +210,7 @@ pub struct Auction {
     tokens: Vec<Token>,
     orders: Vec<Order>,
     deadline: chrono::DateTime<chrono::Utc>,
+    created: chrono::DateTime<chrono::Utc>,
     #[serde(default)]
     surplus_capturing_jit_order_owners: Vec<eth::H160>,
 }
@@ -249,6 +259,10 @@ struct Order {
     #[serde(default)]
     buy_token_balance: BuyTokenBalance,
     class: Class,
+    /// Limit price relative to the reference prices in basis points. Only
+    /// applies to limit orders.
+    #[serde(default)]
+    oracle_limit_offset_bps: Option<i32>,
     #[serde_as(as = "serialize::Hex")]
     app_data: [u8; order::APP_DATA_LEN],
     signing_scheme: SigningScheme,
@@ -320,7 +334,11 @@ enum FeePolicy {
         quote: Quote,
     },
     #[serde(rename_all = "camelCase")]
-    Volume { factor: f64 },
+    Volume {
+        factor: f64,
+        #[serde(default)]
+        recipient: Option<eth::H160>,
+    },
 }
 
 #[serde_as]
@@ -6,9 +6,10 @@ use {
     super::Error,
     crate::infra::config::file::GasEstimatorType,
     crate::{domain::eth, infra::mempool},
+    anyhow::Context,
     ethcontract::dyns::DynWeb3,
-    gas_estimation::{nativegasestimator::NativeGasEstimator, GasPriceEstimating},
-    std::sync::Arc,
+    gas_estimation::{nativegasestimator::NativeGasEstimator, GasPrice1559, GasPriceEstimating},
+    std::{sync::Arc, time::Duration},
 };
 
 type MaxAdditionalTip = eth::U256;
@@ -36,6 +37,12 @@ impl GasPriceEstimator {
                     .map_err(Error::GasPrice)?,
             ),
             GasEstimatorType::Web3 => Arc::new(web3.clone()),
+            GasEstimatorType::FeeHistory(config) => Arc::new(FeeHistory {
+                web3: web3.clone(),
+                blocks: config.blocks,
+                reward_percentile: config.reward_percentile,
+                block_time: config.block_time,
+            }),
         };
         let additional_tip = mempools
             .iter()
@@ -45,7 +52,7 @@ impl GasPriceEstimator {
                     additional_tip_percentage,
                     ..
                 } => Some((max_additional_tip, additional_tip_percentage)),
-                mempool::Kind::Public(_) => None,
+                mempool::Kind::Public(_) | mempool::Kind::Relayer(_) => None,
             })
             .next();
         // Use the lowest max_fee_per_gas of all mempools as the max_fee_per_gas
@@ -101,3 +108,130 @@ impl GasPriceEstimator {
             .map_err(Error::GasPrice)
     }
 }
+
+/// Gas price estimator based on `eth_feeHistory`. Smooths out spikes of single
+/// blocks by averaging the base fee and priority fee over a window of recent
+/// blocks.
+struct FeeHistory {
+    web3: DynWeb3,
+    blocks: u64,
+    reward_percentile: f64,
+    block_time: Duration,
+}
+
+#[async_trait::async_trait]
+impl GasPriceEstimating for FeeHistory {
+    /// The gas limit doesn't influence the estimate: base and priority fees
+    /// are prices per unit of gas, no matter how much gas a transaction uses.
+    async fn estimate_with_limits(
+        &self,
+        _gas_limit: f64,
+        time_limit: Duration,
+    ) -> anyhow::Result<GasPrice1559> {
+        let history = self
+            .web3
+            .eth()
+            .fee_history(
+                window(self.blocks, self.block_time, time_limit).into(),
+                web3::types::BlockNumber::Latest,
+                Some(vec![self.reward_percentile]),
+            )
+            .await?;
+        let base_fees = history
+            .base_fee_per_gas
+            .iter()
+            .map(|fee| fee.to_f64_lossy())
+            .collect::<Vec<_>>();
+        let priority_fees = history
+            .reward
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|rewards| rewards.first())
+            .map(|fee| fee.to_f64_lossy())
+            .collect::<Vec<_>>();
+        smoothed(&base_fees, &priority_fees).context("empty fee history")
+    }
+}
+
+/// The number of most recent blocks to base an estimate on. Estimates for
+/// transactions which should be included soon only follow the blocks mined
+/// during the time limit, so they react faster to rising prices.
+fn window(blocks: u64, block_time: Duration, time_limit: Duration) -> u64 {
+    let mined = time_limit.as_millis() / block_time.as_millis().max(1);
+    u64::try_from(mined)
+        .unwrap_or(u64::MAX)
+        .clamp(1, blocks.max(1))
+}
+
+/// Computes a gas price estimate from a fee history, ordered from the oldest
+/// to the newest block.
+///
+/// The base fee is a weighted average where the weight grows linearly with
+/// the recency of the block, so the estimate follows trends without reacting
+/// to the spike of a single block. The priority fee is the median of the
+/// priority fees of the individual blocks.
+fn smoothed(base_fees: &[f64], priority_fees: &[f64]) -> Option<GasPrice1559> {
+    if base_fees.is_empty() {
+        return None;
+    }
+    let (weighted, weights) =
+        base_fees
+            .iter()
+            .zip(1u32..)
+            .fold((0., 0.), |(weighted, weights), (fee, weight)| {
+                (
+                    weighted + fee * f64::from(weight),
+                    weights + f64::from(weight),
+                )
+            });
+    let base_fee_per_gas = weighted / weights;
+
+    let mut priority_fees = priority_fees.to_vec();
+    priority_fees.sort_by(f64::total_cmp);
+    let max_priority_fee_per_gas = match priority_fees.len() {
+        0 => 0.,
+        len if len % 2 == 0 => (priority_fees[len / 2 - 1] + priority_fees[len / 2]) / 2.,
+        len => priority_fees[len / 2],
+    };
+
+    Some(GasPrice1559 {
+        base_fee_per_gas,
+        // Leave room for the base fee to double, like most wallets do.
+        max_fee_per_gas: base_fee_per_gas * 2. + max_priority_fee_per_gas,
+        max_priority_fee_per_gas,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_history_window_follows_time_limit() {
+        let window = |secs| window(10, Duration::from_secs(12), Duration::from_secs(secs));
+        assert_eq!(window(36), 3);
+        assert_eq!(window(40), 3);
+        // At least the latest and at most the configured number of blocks.
+        assert_eq!(window(5), 1);
+        assert_eq!(window(600), 10);
+    }
+
+    #[test]
+    fn smoothed_fee_history() {
+        // The spike in the middle is dampened and the newer blocks have more
+        // weight: (10 * 1 + 100 * 2 + 10 * 3 + 20 * 4) / 10 = 32
+        let estimate = smoothed(&[10., 100., 10., 20.], &[3., 1., 100., 2.]).unwrap();
+        assert_eq!(estimate.base_fee_per_gas, 32.);
+        assert_eq!(estimate.max_priority_fee_per_gas, 2.5);
+        assert_eq!(estimate.max_fee_per_gas, 66.5);
+
+        let estimate = smoothed(&[10.], &[1., 5., 2.]).unwrap();
+        assert_eq!(estimate.base_fee_per_gas, 10.);
+        assert_eq!(estimate.max_priority_fee_per_gas, 2.);
+
+        let estimate = smoothed(&[10.], &[]).unwrap();
+        assert_eq!(estimate.max_priority_fee_per_gas, 0.);
+
+        assert!(smoothed(&[], &[1.]).is_none());
+    }
+}
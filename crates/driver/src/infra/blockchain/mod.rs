@@ -1,6 +1,6 @@
 use {
     self::contracts::ContractAt,
-    crate::{boundary, domain::eth},
+    crate::{boundary, domain::eth, infra::simulator},
     ethcontract::dyns::DynWeb3,
     ethrpc::current_block::CurrentBlockStream,
     std::{fmt, sync::Arc},
@@ -170,23 +170,59 @@ impl Ethereum {
     pub async fn estimate_gas(&self, tx: &eth::Tx) -> Result<eth::Gas, Error> {
         self.web3
             .eth()
-            .estimate_gas(
-                web3::types::CallRequest {
-                    from: Some(tx.from.into()),
-                    to: Some(tx.to.into()),
-                    value: Some(tx.value.into()),
-                    data: Some(tx.input.clone().into()),
-                    access_list: Some(tx.access_list.clone().into()),
-                    gas_price: self.simulation_gas_price().await,
-                    ..Default::default()
-                },
-                None,
-            )
+            .estimate_gas(self.call_request(tx).await, None)
             .await
             .map(Into::into)
             .map_err(Into::into)
     }
 
+    /// Estimate gas used by a transaction as if it was included in the given
+    /// block instead of the next one.
+    pub async fn estimate_gas_in_block(
+        &self,
+        tx: &eth::Tx,
+        block: simulator::Block,
+    ) -> Result<eth::Gas, Error> {
+        let request = self.call_request(tx).await;
+        let block_overrides = serde_json::json!({
+            "number": web3::types::U64::from(block.number),
+            "time": web3::types::U64::from(block.timestamp),
+        });
+        let json = self
+            .web3
+            .transport()
+            .execute(
+                "eth_estimateGas",
+                vec![
+                    serde_json::to_value(&request).unwrap(),
+                    "latest".into(),
+                    serde_json::json!({}),
+                    block_overrides,
+                ],
+            )
+            .await?;
+        let gas: eth::U256 = web3::helpers::decode(json)?;
+        Ok(gas.into())
+    }
+
+    /// Trace the calls made by a transaction in the latest block using
+    /// `debug_traceCall`.
+    pub async fn trace_call(&self, tx: &eth::Tx) -> Result<serde_json::Value, Error> {
+        let request = self.call_request(tx).await;
+        Ok(self
+            .web3
+            .transport()
+            .execute(
+                "debug_traceCall",
+                vec![
+                    serde_json::to_value(&request).unwrap(),
+                    "latest".into(),
+                    serde_json::json!({ "tracer": "callTracer" }),
+                ],
+            )
+            .await?)
+    }
+
     pub async fn gas_price(&self) -> Result<eth::GasPrice, Error> {
         self.inner.gas.estimate().await
     }
@@ -219,12 +255,15 @@ impl Ethereum {
             .map(|result| match result {
                 Some(web3::types::TransactionReceipt {
                     status: Some(status),
+                    block_number,
                     ..
                 }) => {
                     if status.is_zero() {
                         eth::TxStatus::Reverted
                     } else {
-                        eth::TxStatus::Executed
+                        eth::TxStatus::Executed {
+                            block: block_number.unwrap_or_default().as_u64(),
+                        }
                     }
                 }
                 _ => eth::TxStatus::Pending,
@@ -232,6 +271,19 @@ impl Ethereum {
             .map_err(Into::into)
     }
 
+    /// The request simulating the transaction in `eth_call`-like methods.
+    async fn call_request(&self, tx: &eth::Tx) -> web3::types::CallRequest {
+        web3::types::CallRequest {
+            from: Some(tx.from.into()),
+            to: Some(tx.to.into()),
+            value: Some(tx.value.into()),
+            data: Some(tx.input.clone().into()),
+            access_list: Some(tx.access_list.clone().into()),
+            gas_price: self.simulation_gas_price().await,
+            ..Default::default()
+        }
+    }
+
     pub(super) async fn simulation_gas_price(&self) -> Option<eth::U256> {
         // Some nodes don't pick a reasonable default value when you don't specify a gas
         // price and default to 0. Additionally some sneaky tokens have special code
@@ -17,6 +17,11 @@ pub struct Args {
     )]
     pub log: String,
 
+    /// Enables `tokio-console` instrumentation so that the state of all tokio
+    /// tasks can be inspected live.
+    #[clap(long, env, action = clap::ArgAction::Set, default_value = "false")]
+    pub enable_task_console: bool,
+
     /// The node RPC API endpoint.
     #[clap(long, env)]
     pub ethrpc: Url,
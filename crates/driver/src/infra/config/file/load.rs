@@ -5,10 +5,11 @@ use {
             self,
             blockchain,
             config::file,
+            cosigner,
             liquidity,
             mempool,
             simulator,
-            solver::{self, SolutionMerging},
+            solver::{self, PendingSettlement, SolutionMerging},
         },
     },
     futures::future::join_all,
@@ -67,6 +68,17 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                 slippage: solver::Slippage {
                     relative: big_decimal_to_big_rational(&config.slippage.relative),
                     absolute: config.slippage.absolute.map(eth::Ether),
+                    tiers: config
+                        .slippage
+                        .tiers
+                        .iter()
+                        .flat_map(|tier| {
+                            let relative = big_decimal_to_big_rational(&tier.relative);
+                            tier.tokens
+                                .iter()
+                                .map(move |token| ((*token).into(), relative.clone()))
+                        })
+                        .collect(),
                 },
                 liquidity: if config.skip_liquidity {
                     solver::Liquidity::Skip
@@ -83,20 +95,82 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                         .try_into()
                         .unwrap(),
                 },
-                request_headers: config.request_headers,
+                request_headers: solver::RequestHeaders(config.request_headers),
                 fee_handler: config.fee_handler,
                 quote_using_limit_orders: config.quote_using_limit_orders,
                 merge_solutions: match config.merge_solutions {
                     true => SolutionMerging::Allowed,
                     false => SolutionMerging::Forbidden,
                 },
+                merge_time_share: config
+                    .merge_time_share
+                    .map(|share| share.try_into().unwrap()),
                 s3: config.s3.map(Into::into),
                 solver_native_token: config.manage_native_token.to_domain(),
                 quote_tx_origin: config.quote_tx_origin.map(eth::Address),
+                gas_buffer: solver::GasBuffer {
+                    custom: config.gas_buffer.custom,
+                    liquidity: config.gas_buffer.liquidity,
+                },
+                distinct_token_penalty: solver::DistinctTokenPenalty {
+                    threshold: config.distinct_token_penalty.threshold,
+                    per_token: eth::Ether(config.distinct_token_penalty.per_token),
+                },
+                min_surplus_per_gas: config.min_surplus_per_gas,
+                results_webhook: config.results_webhook,
+                max_jit_orders: config.max_jit_orders,
+                gas_payment: config.gas_payment.map(|payment| solver::GasPayment {
+                    token: payment.token.into(),
+                    relayer: payment.relayer.into(),
+                }),
+                min_order_value: config.min_order_value.map(eth::Ether),
+                blocked_addresses: config
+                    .blocked_addresses
+                    .into_iter()
+                    .map(eth::Address)
+                    .collect(),
+                max_price_deviation: config.max_price_deviation,
+                max_limit_price_deviation: config.max_limit_price_deviation,
+                denied_tokens: config.denied_tokens.into_iter().map(Into::into).collect(),
+                co_signer: config.co_signer.map(|co_signer| cosigner::Config {
+                    url: co_signer.url,
+                    account: co_signer.account.into(),
+                    timeout: co_signer.timeout,
+                }),
+                pending_settlement: match config.keep_better_pending_settlement {
+                    true => PendingSettlement::KeepBetter,
+                    false => PendingSettlement::Replace,
+                },
+                approval_mode: match config.exact_approvals {
+                    true => eth::allowance::ApprovalMode::Exact,
+                    false => eth::allowance::ApprovalMode::Max,
+                },
+                reset_approval_tokens: config
+                    .reset_approval_tokens
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                max_auction_age: config.max_auction_age,
+                revoke_approvals: config.revoke_approvals,
+                reject_buffer_overdraws: config.reject_buffer_overdraws,
+                shadow: config.shadow,
+                expiry_grace_period: config.expiry_grace_period,
+                response_size_limit: config.response_size_limit,
+                control_token: config.control_token.map(solver::ControlToken),
+                fallback_endpoint: config.fallback_endpoint,
+                balance_checker: config.balance_checker.map(Into::into),
+                gas_overrides: solver::GasOverrides(
+                    config
+                        .gas_overrides
+                        .into_iter()
+                        .map(|(target, gas)| (target.into(), eth::Gas(gas.into())))
+                        .collect(),
+                ),
             }
         }))
         .await,
         liquidity: liquidity::Config {
+            source_timeout: config.liquidity.source_timeout,
             base_tokens: config
                 .liquidity
                 .base_tokens
@@ -110,35 +184,43 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                 .iter()
                 .cloned()
                 .map(|config| match config {
-                    file::UniswapV2Config::Preset { preset } => match preset {
-                        file::UniswapV2Preset::UniswapV2 => {
-                            liquidity::config::UniswapV2::uniswap_v2(chain)
-                        }
-                        file::UniswapV2Preset::SushiSwap => {
-                            liquidity::config::UniswapV2::sushi_swap(chain)
-                        }
-                        file::UniswapV2Preset::Honeyswap => {
-                            liquidity::config::UniswapV2::honeyswap(chain)
-                        }
-                        file::UniswapV2Preset::Baoswap => {
-                            liquidity::config::UniswapV2::baoswap(chain)
-                        }
-                        file::UniswapV2Preset::PancakeSwap => {
-                            liquidity::config::UniswapV2::pancake_swap(chain)
-                        }
-                        file::UniswapV2Preset::TestnetUniswapV2 => {
-                            liquidity::config::UniswapV2::testnet_uniswapv2(chain)
+                    file::UniswapV2Config::Preset {
+                        preset,
+                        max_concurrent_fetches,
+                    } => liquidity::config::UniswapV2 {
+                        max_concurrent_fetches,
+                        ..match preset {
+                            file::UniswapV2Preset::UniswapV2 => {
+                                liquidity::config::UniswapV2::uniswap_v2(chain)
+                            }
+                            file::UniswapV2Preset::SushiSwap => {
+                                liquidity::config::UniswapV2::sushi_swap(chain)
+                            }
+                            file::UniswapV2Preset::Honeyswap => {
+                                liquidity::config::UniswapV2::honeyswap(chain)
+                            }
+                            file::UniswapV2Preset::Baoswap => {
+                                liquidity::config::UniswapV2::baoswap(chain)
+                            }
+                            file::UniswapV2Preset::PancakeSwap => {
+                                liquidity::config::UniswapV2::pancake_swap(chain)
+                            }
+                            file::UniswapV2Preset::TestnetUniswapV2 => {
+                                liquidity::config::UniswapV2::testnet_uniswapv2(chain)
+                            }
                         }
-                    }
-                    .expect("no Uniswap V2 preset for current network"),
+                        .expect("no Uniswap V2 preset for current network")
+                    },
                     file::UniswapV2Config::Manual {
                         router,
                         pool_code,
                         missing_pool_cache_time,
+                        max_concurrent_fetches,
                     } => liquidity::config::UniswapV2 {
                         router: router.into(),
                         pool_code: pool_code.into(),
                         missing_pool_cache_time,
+                        max_concurrent_fetches,
                     },
                 })
                 .collect(),
@@ -263,6 +345,9 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                 gas_price_cap: config.submission.gas_price_cap,
                 target_confirm_time: config.submission.target_confirm_time,
                 retry_interval: config.submission.retry_interval,
+                max_gas_price_increase: config.submission.max_gas_price_increase,
+                max_retries: config.submission.max_retries,
+                confirmations: config.submission.confirmations,
                 kind: match mempool {
                     file::Mempool::Public => {
                         // If there is no private mempool, revert protection is
@@ -292,6 +377,15 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                         additional_tip_percentage: *additional_tip_percentage,
                         use_soft_cancellations: *use_soft_cancellations,
                     },
+                    file::Mempool::Relayer {
+                        address,
+                        fee_recipient,
+                        fee_bps,
+                    } => mempool::Kind::Relayer(mempool::Relayer {
+                        address: (*address).into(),
+                        fee_recipient: (*fee_recipient).into(),
+                        fee_bps: *fee_bps,
+                    }),
                 },
             })
             .collect(),
@@ -329,6 +423,14 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
         },
         disable_access_list_simulation: config.disable_access_list_simulation,
         disable_gas_simulation: config.disable_gas_simulation.map(Into::into),
+        simulation_inclusion_offset: config.simulation_inclusion_offset.map(|offset| {
+            simulator::InclusionOffset {
+                blocks: offset.blocks,
+                block_time: offset.block_time,
+            }
+        }),
+        trace_failed_simulations: config.trace_failed_simulations,
+        isolate_simulation_reverts: config.isolate_simulation_reverts,
         gas_estimator: config.gas_estimator,
     }
 }
@@ -5,7 +5,7 @@ use {
     serde::{Deserialize, Serialize},
     serde_with::serde_as,
     solver::solver::Arn,
-    std::{collections::HashMap, time::Duration},
+    std::{collections::HashMap, num::NonZeroUsize, time::Duration},
 };
 
 mod load;
@@ -31,6 +31,23 @@ struct Config {
     #[serde_as(as = "Option<serialize::U256>")]
     disable_gas_simulation: Option<eth::U256>,
 
+    /// Simulate gas as if settlements were included a number of blocks after
+    /// the current one, for chains where the state at inclusion differs from
+    /// `latest`.
+    simulation_inclusion_offset: Option<InclusionOffsetConfig>,
+
+    /// Capture the call trace of settlements that revert in simulation and
+    /// attach it to the simulation error. Tracing is expensive, so it is
+    /// disabled by default.
+    #[serde(default)]
+    trace_failed_simulations: bool,
+
+    /// Bisect the interactions of settlements that revert in simulation to
+    /// find the first reverting one. This re-simulates the settlement many
+    /// times, so it is disabled by default.
+    #[serde(default)]
+    isolate_simulation_reverts: bool,
+
     /// Defines the gas estimator to use.
     #[serde(default)]
     gas_estimator: GasEstimatorType,
@@ -82,6 +99,27 @@ struct SubmissionConfig {
     #[serde(with = "humantime_serde", default = "default_retry_interval")]
     retry_interval: Duration,
 
+    /// Re-estimate the gas price right before submitting a settlement and
+    /// decline the submission if it exceeds the gas price the settlement was
+    /// scored with by more than this fraction (e.g. 0.1 for 10%). Disabled by
+    /// default.
+    #[serde(default)]
+    max_gas_price_increase: Option<f64>,
+
+    /// How often a settlement is resubmitted with a bumped gas price after a
+    /// retryable submission failure (e.g. the transaction got rejected or
+    /// mined reverted). Settlements are only resubmitted if they still
+    /// simulate successfully. Disabled by default.
+    #[serde(default)]
+    max_retries: usize,
+
+    /// How many blocks need to be built on top of the block including a
+    /// settlement before it is considered final. Settlements which disappear
+    /// in a reorg before that are resubmitted. By default settlements are
+    /// final as soon as they are included.
+    #[serde(default)]
+    confirmations: u64,
+
     /// The mempools to submit settlement transactions to. Can be the public
     /// mempool of a node or the private MEVBlocker mempool.
     #[serde(rename = "mempool", default)]
@@ -116,6 +154,18 @@ enum Mempool {
         #[serde(default = "default_soft_cancellations_flag")]
         use_soft_cancellations: bool,
     },
+    /// Submits to the public mempool, but settles through a relayer contract
+    /// which takes a fee.
+    #[serde(rename_all = "kebab-case")]
+    Relayer {
+        /// The relayer contract forwarding the settlement calldata to the
+        /// settlement contract.
+        address: eth::H160,
+        /// The account receiving the relayer's fee.
+        fee_recipient: eth::H160,
+        /// The relayer's fee in basis points.
+        fee_bps: u32,
+    },
 }
 
 #[derive(Debug, Deserialize)]
@@ -221,6 +271,13 @@ struct SolverConfig {
     #[serde(default)]
     merge_solutions: bool,
 
+    /// Share of the time remaining until the deadline which may be spent
+    /// merging solutions, e.g. 0.5 for half of it. Once used up, the solutions
+    /// merged so far are used to reserve time for scoring and settling them.
+    /// Unlimited by default.
+    #[serde(default)]
+    merge_time_share: Option<f64>,
+
     /// S3 configuration for storing the auctions in the form they are sent to
     /// the solver engine
     #[serde(default)]
@@ -233,6 +290,226 @@ struct SolverConfig {
     /// Which `tx.origin` is required to make a quote simulation pass.
     #[serde(default)]
     quote_tx_origin: Option<eth::H160>,
+
+    /// Score penalty for settlements touching many distinct tokens. Disabled
+    /// by default.
+    #[serde(default)]
+    distinct_token_penalty: DistinctTokenPenalty,
+
+    /// Safety buffers added to simulated gas estimates, which may otherwise
+    /// be too low if the state changes between simulation and execution.
+    #[serde(default)]
+    gas_buffer: GasBuffer,
+
+    /// Settlements generating less score (in wei) per unit of gas than this
+    /// are discarded, even if their absolute score is the highest.
+    #[serde(default)]
+    #[serde_as(as = "Option<serialize::U256>")]
+    min_surplus_per_gas: Option<eth::U256>,
+
+    /// URL to which a summary of the competition (participating solutions,
+    /// their scores and the winner) is POSTed after every solve.
+    #[serde(default)]
+    results_webhook: Option<url::Url>,
+
+    /// Maximum number of JIT orders a single solution may settle. Solutions
+    /// exceeding it are discarded. Unlimited by default.
+    #[serde(default)]
+    max_jit_orders: Option<usize>,
+
+    /// Pay for the gas of settlements in an ERC20 token by transferring it to
+    /// a relayer as part of the settlement. Disabled by default.
+    #[serde(default)]
+    gas_payment: Option<GasPayment>,
+
+    /// Orders whose sell amount is worth less than this (in wei) according to
+    /// the auction's reference prices are not sent to the solver.
+    #[serde(default)]
+    #[serde_as(as = "Option<serialize::U256>")]
+    min_order_value: Option<eth::U256>,
+
+    /// Solutions paying out to or calling into any of these addresses are
+    /// discarded, e.g. for sanctions screening.
+    #[serde(default)]
+    blocked_addresses: Vec<eth::H160>,
+
+    /// Solutions whose clearing prices deviate from the auction's reference
+    /// prices by more than this factor (e.g. 10 for 10x) are discarded.
+    /// Tokens without a reference price are not checked. Disabled by default.
+    #[serde(default)]
+    max_price_deviation: Option<f64>,
+
+    /// Orders whose limit price asks for more than this factor (e.g. 1000 for
+    /// 1000x) of the value of the sold tokens according to the auction's
+    /// reference prices are dropped from the auction before it is sent to the
+    /// solver. Orders without reference prices are kept. Disabled by default.
+    #[serde(default)]
+    max_limit_price_deviation: Option<f64>,
+
+    /// Orders and liquidity involving any of these tokens are dropped from
+    /// the auction before it is sent to the solver, e.g. for regulatory
+    /// reasons.
+    #[serde(default)]
+    denied_tokens: Vec<eth::H160>,
+
+    /// Settle through a smart contract account requiring the approval of an
+    /// external co-signer instead of directly from the solver's account.
+    #[serde(default)]
+    co_signer: Option<CoSigner>,
+
+    /// If enabled, a settlement which is still pending when the next auction
+    /// is solved is kept if it scores better than the new auction's best
+    /// settlement. Otherwise the new settlement always replaces it.
+    #[serde(default)]
+    keep_better_pending_settlement: bool,
+
+    /// If enabled, interactions with indexed liquidity only get approved the
+    /// amount they use instead of the maximal amount, so no allowance is left
+    /// behind for routers which aren't trusted.
+    #[serde(default)]
+    exact_approvals: bool,
+
+    /// Tokens which revert when changing a non-zero allowance to another
+    /// non-zero amount, e.g. USDT. Their allowances are approved down to zero
+    /// before being approved to the required amount.
+    #[serde(default)]
+    reset_approval_tokens: Vec<eth::H160>,
+
+    /// Auctions whose data was snapshotted longer ago than this are rejected
+    /// instead of being solved. Disabled by default.
+    #[serde(default, with = "humantime_serde")]
+    max_auction_age: Option<Duration>,
+
+    /// Reset every allowance granted by a settlement back to zero after its
+    /// core interactions, so that no dangling approvals are left behind.
+    /// Costs additional gas and is disabled by default.
+    #[serde(default)]
+    revoke_approvals: bool,
+
+    /// Reject solutions whose internalized interactions draw more of a token
+    /// from the settlement contract's buffers than is available, since their
+    /// internalized settlement would revert.
+    #[serde(default)]
+    reject_buffer_overdraws: bool,
+
+    /// Run the solver in shadow mode: its solutions are encoded, scored and
+    /// logged as usual, but never proposed to the protocol. Useful for
+    /// evaluating a new solver against real auctions.
+    #[serde(default)]
+    shadow: bool,
+
+    /// Orders expiring within this grace period are still solved but their
+    /// settlement flags them as expiring soon when it is submitted. Orders
+    /// which already expired are always dropped before solving. Disabled by
+    /// default.
+    #[serde(default, with = "humantime_serde")]
+    expiry_grace_period: Option<Duration>,
+
+    /// Maximum size in bytes of a response of the solver. Reading a larger
+    /// response is aborted and the request fails.
+    #[serde(default = "default_response_size_limit")]
+    response_size_limit: usize,
+
+    /// Bearer token for the `/pause` and `/resume` endpoints which stop and
+    /// restart the solver's participation in competitions at runtime. The
+    /// endpoints are disabled if not set.
+    #[serde(default)]
+    control_token: Option<String>,
+
+    /// Endpoint of a solver engine, e.g. the baseline solver, which is asked
+    /// for solutions at the same time as this solver. Its solutions are used
+    /// whenever this solver doesn't return any and are settled on behalf of
+    /// this solver.
+    #[serde(default)]
+    fallback_endpoint: Option<url::Url>,
+
+    /// Contract snapshotting the token balances of the settlement contract
+    /// before each settlement and asserting afterwards that they didn't
+    /// decrease by more than the settlement pays out, catching unexpected
+    /// drains by buggy interactions. Disabled by default.
+    #[serde(default)]
+    balance_checker: Option<eth::H160>,
+
+    /// Fixed gas estimates for custom interactions calling into these
+    /// contracts, used when scoring instead of the simulated gas of
+    /// interactions whose gas usage varies a lot between blocks.
+    #[serde(default)]
+    gas_overrides: HashMap<eth::H160, u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct GasPayment {
+    /// The token in which the gas is paid.
+    token: eth::H160,
+
+    /// The relayer submitting the settlement, which receives the payment.
+    relayer: eth::H160,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct CoSigner {
+    /// Endpoint to which settlement transactions are POSTed to be signed.
+    url: url::Url,
+
+    /// The smart contract account executing the settlements, e.g. a Safe.
+    account: eth::H160,
+
+    /// How long to wait for the co-signer to respond.
+    #[serde(with = "humantime_serde", default = "default_co_signer_timeout")]
+    timeout: Duration,
+}
+
+fn default_co_signer_timeout() -> Duration {
+    Duration::from_secs(5)
+}
+
+fn default_response_size_limit() -> usize {
+    10_000_000
+}
+
+#[serde_as]
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct DistinctTokenPenalty {
+    /// Number of distinct tokens in the clearing prices a settlement can have
+    /// before being penalized.
+    #[serde(default)]
+    threshold: usize,
+
+    /// Penalty in wei subtracted from the score for every distinct token above
+    /// the threshold.
+    #[serde(default)]
+    #[serde_as(as = "serialize::U256")]
+    per_token: eth::U256,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct GasBuffer {
+    /// Relative buffer (e.g. 0.2 for 20%) for settlements executing custom
+    /// interactions, whose gas usage is harder to predict.
+    #[serde(default = "default_gas_buffer")]
+    custom: f64,
+
+    /// Relative buffer for all other settlements, i.e. those only executing
+    /// interactions with indexed liquidity or no interactions at all.
+    #[serde(default = "default_gas_buffer")]
+    liquidity: f64,
+}
+
+impl Default for GasBuffer {
+    fn default() -> Self {
+        Self {
+            custom: default_gas_buffer(),
+            liquidity: default_gas_buffer(),
+        }
+    }
+}
+
+fn default_gas_buffer() -> f64 {
+    0.05
 }
 
 #[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
@@ -299,6 +576,26 @@ struct Slippage {
     #[serde(rename = "absolute-slippage")]
     #[serde_as(as = "Option<serialize::U256>")]
     absolute: Option<eth::U256>,
+
+    /// Relative slippage overrides for groups of tokens with similar
+    /// volatility, e.g. a tight tier for stablecoins and a wider one for
+    /// volatile tokens. Tokens which aren't part of any tier use the default
+    /// relative slippage.
+    #[serde(default, rename = "slippage-tiers")]
+    tiers: Vec<SlippageTier>,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct SlippageTier {
+    /// The relative slippage factor for interactions involving these tokens.
+    #[serde(rename = "relative-slippage")]
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    relative: bigdecimal::BigDecimal,
+
+    /// The tokens belonging to this tier.
+    tokens: Vec<eth::H160>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -350,6 +647,18 @@ struct TenderlyConfig {
     save_if_fails: bool,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct InclusionOffsetConfig {
+    /// How many blocks after the current one settlements are assumed to be
+    /// included.
+    blocks: u64,
+
+    /// The time between two blocks of the network.
+    #[serde(with = "humantime_serde")]
+    block_time: Duration,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 struct EnsoConfig {
@@ -388,13 +697,25 @@ struct LiquidityConfig {
     /// Liquidity provided by 0x API.
     #[serde(default)]
     zeroex: Option<ZeroExConfig>,
+
+    /// Skip liquidity sources which take longer than this to fetch liquidity
+    /// and solve with the liquidity of the remaining sources instead.
+    #[serde(default, with = "humantime_serde")]
+    source_timeout: Option<Duration>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
 #[serde(untagged, deny_unknown_fields)]
 enum UniswapV2Config {
     #[serde(rename_all = "kebab-case")]
-    Preset { preset: UniswapV2Preset },
+    Preset {
+        preset: UniswapV2Preset,
+
+        /// How many pool states are fetched concurrently, e.g. to stay within
+        /// the rate limits of the node. Unlimited by default.
+        #[serde(default)]
+        max_concurrent_fetches: Option<NonZeroUsize>,
+    },
 
     #[serde(rename_all = "kebab-case")]
     Manual {
@@ -409,6 +730,11 @@ enum UniswapV2Config {
         /// again.
         #[serde(with = "humantime_serde")]
         missing_pool_cache_time: Duration,
+
+        /// How many pool states are fetched concurrently, e.g. to stay within
+        /// the rate limits of the node. Unlimited by default.
+        #[serde(default)]
+        max_concurrent_fetches: Option<NonZeroUsize>,
     },
 }
 
@@ -573,4 +899,38 @@ pub enum GasEstimatorType {
     #[default]
     Native,
     Web3,
+    /// Smoothed EIP-1559 estimate computed from the fee history of the most
+    /// recent blocks.
+    FeeHistory(FeeHistoryConfig),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct FeeHistoryConfig {
+    /// Number of most recent blocks to take into account.
+    #[serde(default = "default_fee_history_blocks")]
+    pub blocks: u64,
+
+    /// The percentile of the priority fees paid in each block which should be
+    /// used for the estimate. Expected value [0, 100].
+    #[serde(default = "default_fee_history_reward_percentile")]
+    pub reward_percentile: f64,
+
+    /// The time between two blocks of the network. Estimates which should be
+    /// included within a time limit only take the blocks mined during that
+    /// time limit into account.
+    #[serde(default = "default_fee_history_block_time", with = "humantime_serde")]
+    pub block_time: Duration,
+}
+
+fn default_fee_history_blocks() -> u64 {
+    10
+}
+
+fn default_fee_history_reward_percentile() -> f64 {
+    50.
+}
+
+fn default_fee_history_block_time() -> Duration {
+    Duration::from_secs(12)
 }
@@ -10,6 +10,9 @@ pub mod file;
 pub struct Config {
     pub disable_access_list_simulation: bool,
     pub disable_gas_simulation: Option<eth::Gas>,
+    pub simulation_inclusion_offset: Option<simulator::InclusionOffset>,
+    pub trace_failed_simulations: bool,
+    pub isolate_simulation_reverts: bool,
     pub solvers: Vec<solver::Config>,
     pub liquidity: liquidity::Config,
     pub simulator: Option<simulator::Config>,
@@ -0,0 +1,222 @@
+//! Settles through a smart contract account (e.g. a Safe multisig) which
+//! requires an external co-signer to approve every transaction. The solver's
+//! own account only relays the transaction to the smart contract account,
+//! which executes the settlement once the co-signer's signatures check out.
+
+use {
+    crate::{
+        domain::eth,
+        util::{self, serialize},
+    },
+    serde::{Deserialize, Serialize},
+    serde_with::serde_as,
+    std::time::Duration,
+    thiserror::Error,
+};
+
+/// Responses of the co-signer only contain signatures, so larger ones are
+/// certainly wrong.
+const RESPONSE_MAX_BYTES: usize = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Endpoint to which transactions are POSTed for co-signing.
+    pub url: url::Url,
+    /// The smart contract account executing the settlements. Has to be
+    /// allowed to settle by the settlement contract.
+    pub account: eth::Address,
+    /// How long to wait for the co-signer to respond.
+    pub timeout: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct CoSigner {
+    client: reqwest::Client,
+    config: Config,
+}
+
+impl CoSigner {
+    pub fn new(config: Config) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(config.timeout)
+                .build()
+                .unwrap(),
+            config,
+        }
+    }
+
+    /// Waits for the co-signer to sign the transaction and wraps it into a
+    /// call of `execTransaction` on the smart contract account, which executes
+    /// the original call.
+    pub async fn co_sign(&self, tx: eth::Tx) -> Result<eth::Tx, Error> {
+        let body = serde_json::to_string(&Request {
+            account: self.config.account.into(),
+            to: tx.to.into(),
+            value: tx.value.0,
+            data: tx.input.0.clone(),
+        })
+        .unwrap();
+        let req = self
+            .client
+            .post(self.config.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+        let res = util::http::send(RESPONSE_MAX_BYTES, req).await?;
+        let res: Response = serde_json::from_str(&res)?;
+        Ok(exec_transaction(self.config.account, tx, res.signatures))
+    }
+}
+
+/// Encodes a call of the Safe's `execTransaction(address to, uint256 value,
+/// bytes data, uint8 operation, uint256 safeTxGas, uint256 baseGas, uint256
+/// gasPrice, address gasToken, address refundReceiver, bytes signatures)`
+/// executing the transaction without any refunds.
+fn exec_transaction(account: eth::Address, tx: eth::Tx, signatures: Vec<u8>) -> eth::Tx {
+    let selector = ethabi::short_signature(
+        "execTransaction",
+        &[
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Uint(256),
+            ethabi::ParamType::Bytes,
+            ethabi::ParamType::Uint(8),
+            ethabi::ParamType::Uint(256),
+            ethabi::ParamType::Uint(256),
+            ethabi::ParamType::Uint(256),
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Bytes,
+        ],
+    );
+    let params = ethabi::encode(&[
+        ethabi::Token::Address(tx.to.into()),
+        ethabi::Token::Uint(tx.value.0),
+        ethabi::Token::Bytes(tx.input.into()),
+        // `Call` rather than `DelegateCall`
+        ethabi::Token::Uint(0.into()),
+        ethabi::Token::Uint(0.into()),
+        ethabi::Token::Uint(0.into()),
+        ethabi::Token::Uint(0.into()),
+        ethabi::Token::Address(Default::default()),
+        ethabi::Token::Address(Default::default()),
+        ethabi::Token::Bytes(signatures),
+    ]);
+    eth::Tx {
+        to: account,
+        value: eth::U256::zero().into(),
+        input: [selector.as_slice(), params.as_slice()].concat().into(),
+        ..tx
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Request {
+    account: eth::H160,
+    to: eth::H160,
+    #[serde_as(as = "serialize::U256")]
+    value: eth::U256,
+    #[serde_as(as = "serialize::Hex")]
+    data: Vec<u8>,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Response {
+    /// The signatures in the format expected by the smart contract account,
+    /// e.g. the concatenated owner signatures for a Safe.
+    #[serde_as(as = "serialize::Hex")]
+    signatures: Vec<u8>,
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("co-signer request failed: {0}")]
+    Http(#[from] util::http::Error),
+    #[error("unable to parse co-signer response: {0}")]
+    Deserialize(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::sync::{Arc, Mutex},
+    };
+
+    #[tokio::test]
+    async fn waits_for_and_uses_co_signature() {
+        // Responds with a signature after a short delay and records the
+        // requests.
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let app = axum::Router::new().route(
+            "/sign",
+            axum::routing::post({
+                let requests = requests.clone();
+                move |axum::extract::Json(body): axum::extract::Json<serde_json::Value>| async move {
+                    requests.lock().unwrap().push(body);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    axum::Json(serde_json::json!({ "signatures": "0x0102030405" }))
+                }
+            }),
+        );
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(async move { server.await.unwrap() });
+
+        let account = eth::Address(eth::H160::from_low_u64_be(0x5afe));
+        let co_signer = CoSigner::new(Config {
+            url: format!("http://{addr}/sign").parse().unwrap(),
+            account,
+            timeout: Duration::from_secs(2),
+        });
+        let tx = eth::Tx {
+            from: eth::Address(eth::H160::from_low_u64_be(1)),
+            to: eth::Address(eth::H160::from_low_u64_be(2)),
+            value: eth::U256::from(3).into(),
+            input: vec![0xca, 0xfe].into(),
+            access_list: Default::default(),
+        };
+
+        let co_signed = co_signer.co_sign(tx.clone()).await.unwrap();
+
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![serde_json::json!({
+                "account": "0x0000000000000000000000000000000000005afe",
+                "to": "0x0000000000000000000000000000000000000002",
+                "value": "3",
+                "data": "0xcafe",
+            })]
+        );
+        assert_eq!(co_signed.from, tx.from);
+        assert_eq!(co_signed.to, account);
+        assert_eq!(co_signed.value, eth::U256::zero().into());
+        assert_eq!(
+            co_signed.input,
+            exec_transaction(account, tx, vec![1, 2, 3, 4, 5]).input
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_without_co_signature() {
+        let co_signer = CoSigner::new(Config {
+            // Nothing is listening on this port.
+            url: "http://127.0.0.1:1/sign".parse().unwrap(),
+            account: eth::Address(eth::H160::from_low_u64_be(0x5afe)),
+            timeout: Duration::from_secs(2),
+        });
+        let tx = eth::Tx {
+            from: Default::default(),
+            to: Default::default(),
+            value: eth::U256::zero().into(),
+            input: Default::default(),
+            access_list: Default::default(),
+        };
+
+        assert!(matches!(co_signer.co_sign(tx).await, Err(Error::Http(_))));
+    }
+}
@@ -3,7 +3,7 @@ use {
     derivative::Derivative,
     hex_literal::hex,
     reqwest::Url,
-    std::{collections::HashSet, time::Duration},
+    std::{collections::HashSet, num::NonZeroUsize, time::Duration},
 };
 
 /// Configuration options for liquidity fetching.
@@ -31,6 +31,11 @@ pub struct Config {
 
     /// 0x liquidity fetcher.
     pub zeroex: Option<ZeroEx>,
+
+    /// How long to wait for a single liquidity source. Sources which don't
+    /// respond in time are skipped and the liquidity of all other sources is
+    /// used. No timeout is applied if `None`.
+    pub source_timeout: Option<Duration>,
 }
 
 /// Uniswap V2 (and Uniswap V2 clone) liquidity fetching options.
@@ -44,6 +49,8 @@ pub struct UniswapV2 {
     /// How long liquidity should not be fetched for a token pair that didn't
     /// return useful liquidity before allowing to fetch it again.
     pub missing_pool_cache_time: Duration,
+    /// How many pool states are fetched concurrently. Unlimited if `None`.
+    pub max_concurrent_fetches: Option<NonZeroUsize>,
 }
 
 impl UniswapV2 {
@@ -55,6 +62,7 @@ impl UniswapV2 {
             pool_code: hex!("96e8ac4277198ff8b6f785478aa9a39f403cb768dd02cbee326c3e7da348845f")
                 .into(),
             missing_pool_cache_time: Duration::from_secs(60 * 60),
+            max_concurrent_fetches: None,
         })
     }
 
@@ -65,6 +73,7 @@ impl UniswapV2 {
             pool_code: hex!("e18a34eb0e04b04f7a0ac29a6e80748dca96319b42c54d679cb821dca90c6303")
                 .into(),
             missing_pool_cache_time: Duration::from_secs(60 * 60),
+            max_concurrent_fetches: None,
         })
     }
 
@@ -75,6 +84,7 @@ impl UniswapV2 {
             pool_code: hex!("3f88503e8580ab941773b59034fb4b2a63e86dbc031b3633a925533ad3ed2b93")
                 .into(),
             missing_pool_cache_time: Duration::from_secs(60 * 60),
+            max_concurrent_fetches: None,
         })
     }
 
@@ -85,6 +95,7 @@ impl UniswapV2 {
             pool_code: hex!("0bae3ead48c325ce433426d2e8e6b07dac10835baec21e163760682ea3d3520d")
                 .into(),
             missing_pool_cache_time: Duration::from_secs(60 * 60),
+            max_concurrent_fetches: None,
         })
     }
 
@@ -95,6 +106,7 @@ impl UniswapV2 {
             pool_code: hex!("57224589c67f3f30a6b0d7a1b54cf3153ab84563bc609ef41dfb34f8b2974d2d")
                 .into(),
             missing_pool_cache_time: Duration::from_secs(60 * 60),
+            max_concurrent_fetches: None,
         })
     }
 
@@ -106,6 +118,7 @@ impl UniswapV2 {
             pool_code: hex!("0efd7612822d579e24a8851501d8c2ad854264a1050e3dfcee8afcca08f80a86")
                 .into(),
             missing_pool_cache_time: Duration::from_secs(60 * 60),
+            max_concurrent_fetches: None,
         })
     }
 }
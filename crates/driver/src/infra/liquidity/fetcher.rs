@@ -59,6 +59,18 @@ impl Fetcher {
             }
         }
     }
+
+    /// Like [`Fetcher::fetch`], but additionally returns how many liquidity
+    /// sources were found for each of the token pairs.
+    pub async fn fetch_with_coverage(
+        &self,
+        pairs: &HashSet<liquidity::TokenPair>,
+        block: AtBlock,
+    ) -> (Vec<liquidity::Liquidity>, liquidity::Coverage) {
+        let liquidity = self.fetch(pairs, block).await;
+        let coverage = liquidity::Coverage::new(pairs, &liquidity);
+        (liquidity, coverage)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
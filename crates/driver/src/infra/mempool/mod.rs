@@ -5,6 +5,7 @@ use {
         infra,
     },
     ethcontract::dyns::DynWeb3,
+    std::future::Future,
 };
 
 #[derive(Debug, Clone)]
@@ -13,6 +14,15 @@ pub struct Config {
     pub gas_price_cap: eth::U256,
     pub target_confirm_time: std::time::Duration,
     pub retry_interval: std::time::Duration,
+    /// How much the gas price may increase relative to the gas price a
+    /// settlement was scored with before its submission is declined, e.g.
+    /// `0.1` for 10%. Disabled if `None`.
+    pub max_gas_price_increase: Option<f64>,
+    /// How often a settlement is resubmitted after a retryable failure.
+    pub max_retries: usize,
+    /// How many blocks need to be built on top of the block including a
+    /// settlement before it is considered final.
+    pub confirmations: u64,
     pub kind: Kind,
 }
 
@@ -27,6 +37,9 @@ pub enum Kind {
         additional_tip_percentage: f64,
         use_soft_cancellations: bool,
     },
+    /// The public mempool of the [`Ethereum`] node, but settling through a
+    /// relayer contract instead of calling the settlement contract directly.
+    Relayer(Relayer),
 }
 
 impl Kind {
@@ -35,6 +48,47 @@ impl Kind {
         match self {
             Kind::Public(_) => "PublicMempool",
             Kind::MEVBlocker { .. } => "MEVBlocker",
+            Kind::Relayer(_) => "Relayer",
+        }
+    }
+}
+
+/// A contract which forwards settlement calldata to the settlement contract and
+/// keeps a share of the settlement as its fee.
+#[derive(Debug, Clone, Copy)]
+pub struct Relayer {
+    /// The relayer contract.
+    pub address: eth::Address,
+    /// The account receiving the relayer's fee.
+    pub fee_recipient: eth::Address,
+    /// The relayer's fee in basis points.
+    pub fee_bps: u32,
+}
+
+impl Relayer {
+    /// Wraps a settlement transaction into a call of
+    /// `relay(address target, bytes data, address feeRecipient, uint256
+    /// feeBps)` on the relayer contract, which executes the original call.
+    pub fn wrap(&self, tx: eth::Tx) -> eth::Tx {
+        let selector = ethabi::short_signature(
+            "relay",
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Bytes,
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Uint(256),
+            ],
+        );
+        let params = ethabi::encode(&[
+            ethabi::Token::Address(tx.to.into()),
+            ethabi::Token::Bytes(tx.input.into()),
+            ethabi::Token::Address(self.fee_recipient.into()),
+            ethabi::Token::Uint(self.fee_bps.into()),
+        ]);
+        eth::Tx {
+            to: self.address,
+            input: [selector.as_slice(), params.as_slice()].concat().into(),
+            ..tx
         }
     }
 }
@@ -65,7 +119,7 @@ impl std::fmt::Display for Mempool {
 impl Mempool {
     pub fn new(config: Config, transport: DynWeb3) -> Self {
         let transport = match &config.kind {
-            Kind::Public(_) => transport,
+            Kind::Public(_) | Kind::Relayer(_) => transport,
             // Flashbots Protect RPC fallback doesn't support buffered transport
             Kind::MEVBlocker { url, .. } => unbuffered_web3_client(url),
         };
@@ -80,32 +134,198 @@ impl Mempool {
         gas: competition::solution::settlement::Gas,
         solver: &infra::Solver,
     ) -> Result<eth::TxId, mempools::Error> {
-        ethcontract::transaction::TransactionBuilder::new(self.transport.clone())
-            .from(solver.account().clone())
-            .to(tx.to.into())
-            .gas_price(ethcontract::GasPrice::Eip1559 {
-                max_fee_per_gas: gas.price.max().into(),
-                max_priority_fee_per_gas: gas.price.tip().into(),
-            })
-            .data(tx.input.into())
-            .value(tx.value.0)
-            .gas(gas.limit.0)
-            .access_list(web3::types::AccessList::from(tx.access_list))
-            .resolve(ethcontract::transaction::ResolveCondition::Pending)
-            .send()
-            .await
-            .map(|result| eth::TxId(result.hash()))
-            .map_err(|err| mempools::Error::Other(anyhow::Error::from(err)))
+        let send = |nonce: Option<eth::U256>| {
+            let builder = ethcontract::transaction::TransactionBuilder::new(self.transport.clone())
+                .from(solver.account().clone())
+                .to(tx.to.into())
+                .gas_price(ethcontract::GasPrice::Eip1559 {
+                    max_fee_per_gas: gas.price.max().into(),
+                    max_priority_fee_per_gas: gas.price.tip().into(),
+                })
+                .data(tx.input.clone().into())
+                .value(tx.value.0)
+                .gas(gas.limit.0)
+                .access_list(web3::types::AccessList::from(tx.access_list.clone()))
+                .resolve(ethcontract::transaction::ResolveCondition::Pending);
+            let builder = match nonce {
+                Some(nonce) => builder.nonce(nonce),
+                None => builder,
+            };
+            async move {
+                builder
+                    .send()
+                    .await
+                    .map(|result| eth::TxId(result.hash()))
+                    .map_err(anyhow::Error::from)
+            }
+        };
+        let pending_nonce = || async {
+            self.transport
+                .eth()
+                .transaction_count(
+                    solver.address().into(),
+                    Some(web3::types::BlockNumber::Pending),
+                )
+                .await
+                .map_err(anyhow::Error::from)
+        };
+        with_fresh_nonce(send, pending_nonce).await
     }
 
     pub fn config(&self) -> &Config {
         &self.config
     }
 
+    /// The transaction submitted to this mempool in order to execute the given
+    /// settlement transaction.
+    pub fn transaction(&self, tx: eth::Tx) -> eth::Tx {
+        match &self.config.kind {
+            Kind::Relayer(relayer) => relayer.wrap(tx),
+            Kind::Public(_) | Kind::MEVBlocker { .. } => tx,
+        }
+    }
+
     pub fn may_revert(&self) -> bool {
         match &self.config.kind {
-            Kind::Public(_) => true,
+            Kind::Public(_) | Kind::Relayer(_) => true,
             Kind::MEVBlocker { .. } => false,
         }
     }
 }
+
+/// Sends a transaction with `send`, which gets passed the nonce to use or
+/// `None` to let the node pick one. If the node rejects the nonce as stale,
+/// e.g. because another process used the solver account in the meantime, the
+/// transaction is sent once more with the account's pending nonce. Repeated
+/// conflicts mean the account is used concurrently and are not retried.
+async fn with_fresh_nonce<S, SF, N, NF>(
+    mut send: S,
+    pending_nonce: N,
+) -> Result<eth::TxId, mempools::Error>
+where
+    S: FnMut(Option<eth::U256>) -> SF,
+    SF: Future<Output = Result<eth::TxId, anyhow::Error>>,
+    N: FnOnce() -> NF,
+    NF: Future<Output = Result<eth::U256, anyhow::Error>>,
+{
+    match send(None).await {
+        Err(err) if is_nonce_conflict(&err) => {
+            let nonce = pending_nonce().await?;
+            tracing::info!(?err, ?nonce, "resubmitting with refetched nonce");
+            match send(Some(nonce)).await {
+                Err(err) if is_nonce_conflict(&err) => Err(mempools::Error::NonceConflict(nonce)),
+                result => Ok(result?),
+            }
+        }
+        result => Ok(result?),
+    }
+}
+
+/// Whether the node rejected a transaction because its nonce was already used.
+fn is_nonce_conflict(err: &anyhow::Error) -> bool {
+    let err = format!("{err:?}").to_lowercase();
+    ["nonce too low", "nonce is too low", "invalid nonce"]
+        .iter()
+        .any(|message| err.contains(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn resubmits_with_refetched_nonce() {
+        let hash = eth::TxId(eth::H256::from_low_u64_be(1));
+        let fresh = eth::U256::from(42);
+
+        let mut nonces = Vec::new();
+        let result = with_fresh_nonce(
+            |nonce| {
+                nonces.push(nonce);
+                let result = match nonce {
+                    None => Err(anyhow::anyhow!("RPC error: nonce too low")),
+                    Some(_) => Ok(hash.clone()),
+                };
+                async move { result }
+            },
+            || async move { Ok(fresh) },
+        )
+        .await;
+        assert_eq!(result.unwrap().0, hash.0);
+        assert_eq!(nonces, vec![None, Some(fresh)]);
+
+        // The account keeps getting used by someone else.
+        let mut attempts = 0;
+        let result = with_fresh_nonce(
+            |_| {
+                attempts += 1;
+                async { Err(anyhow::anyhow!("RPC error: nonce too low")) }
+            },
+            || async move { Ok(fresh) },
+        )
+        .await;
+        assert!(matches!(result, Err(mempools::Error::NonceConflict(nonce)) if nonce == fresh));
+        assert_eq!(attempts, 2);
+
+        // Other failures are not retried here.
+        let mut attempts = 0;
+        let result = with_fresh_nonce(
+            |_| {
+                attempts += 1;
+                async { Err(anyhow::anyhow!("RPC error: transaction underpriced")) }
+            },
+            || async move { Ok(fresh) },
+        )
+        .await;
+        assert!(matches!(result, Err(mempools::Error::Other(_))));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn relayer_wraps_settlement_call() {
+        let settlement = eth::H160::from_low_u64_be(0x9008);
+        let relayer = Relayer {
+            address: eth::H160::from_low_u64_be(0x4e1a).into(),
+            fee_recipient: eth::H160::from_low_u64_be(0xfee).into(),
+            fee_bps: 25,
+        };
+        let tx = eth::Tx {
+            from: eth::H160::from_low_u64_be(0x5017).into(),
+            to: settlement.into(),
+            value: eth::U256::from(1).into(),
+            input: vec![0x13, 0xd7, 0x9a, 0x0b, 0x01, 0x02].into(),
+            access_list: Default::default(),
+        };
+
+        let wrapped = relayer.wrap(tx.clone());
+
+        assert_eq!(wrapped.from, tx.from);
+        assert_eq!(wrapped.to, relayer.address);
+        assert_eq!(wrapped.value, tx.value);
+        // keccak256("relay(address,bytes,address,uint256)")[..4]
+        let (selector, params) = wrapped.input.0.split_at(4);
+        assert_eq!(
+            selector,
+            &web3::signing::keccak256(b"relay(address,bytes,address,uint256)")[..4]
+        );
+        let params = ethabi::decode(
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Bytes,
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Uint(256),
+            ],
+            params,
+        )
+        .unwrap();
+        assert_eq!(
+            params,
+            vec![
+                ethabi::Token::Address(settlement),
+                ethabi::Token::Bytes(tx.input.0),
+                ethabi::Token::Address(relayer.fee_recipient.0),
+                ethabi::Token::Uint(25.into()),
+            ]
+        );
+    }
+}
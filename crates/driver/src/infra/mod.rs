@@ -2,6 +2,7 @@ pub mod api;
 pub mod blockchain;
 pub mod cli;
 pub mod config;
+pub mod cosigner;
 pub mod liquidity;
 pub mod mempool;
 pub mod notify;
@@ -11,6 +12,7 @@ pub mod simulator;
 pub mod solver;
 pub mod time;
 pub mod tokens;
+pub mod webhook;
 
 pub use {
     self::solver::Solver,
@@ -74,6 +74,18 @@ pub fn encoding_failed(
             used.0, limit.0
         )),
         solution::Error::Encoding(_) => return,
+        solution::Error::BlockedAddress(address) => notification::Kind::DriverError(format!(
+            "Solution involves blocked address {:?}",
+            address.0
+        )),
+        solution::Error::BufferOverdrawn(tokens) => notification::Kind::DriverError(format!(
+            "Solution internalizes more than the buffered balance of {:?}",
+            tokens.iter().map(|token| token.0 .0).collect::<Vec<_>>()
+        )),
+        solution::Error::MispricedToken(token) => notification::Kind::DriverError(format!(
+            "Clearing price of {:?} deviates too much from its reference price",
+            token.0 .0
+        )),
     };
 
     solver.notify(auction_id, Some(solution_id.clone()), notification);
@@ -107,7 +119,14 @@ pub fn executed(
         Ok(hash) => notification::Settlement::Success(hash.clone()),
         Err(Error::Revert(hash)) => notification::Settlement::Revert(hash.clone()),
         Err(Error::SimulationRevert) => notification::Settlement::SimulationRevert,
-        Err(Error::Other(_) | Error::Expired | Error::Disabled) => notification::Settlement::Fail,
+        Err(
+            Error::Other(_)
+            | Error::Expired
+            | Error::Disabled
+            | Error::GasPriceIncreased
+            | Error::Reorged(_)
+            | Error::NonceConflict(_),
+        ) => notification::Settlement::Fail,
     };
 
     solver.notify(
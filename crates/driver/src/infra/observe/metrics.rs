@@ -19,6 +19,32 @@ pub struct Metrics {
     /// The results of the mempool submission.
     #[metric(labels("mempool", "result"))]
     pub mempool_submission: prometheus::IntCounterVec,
+    /// Number of attempts to merge two solutions.
+    #[metric(labels("solver"))]
+    pub merge_attempts: prometheus::IntCounterVec,
+    /// Number of solutions left after merging the solutions of a solve round.
+    #[metric(labels("solver"), buckets(1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 1024))]
+    pub merged_solutions: prometheus::HistogramVec,
+    /// Number of liquidity sources available per token pair of an auction.
+    #[metric(labels("solver"), buckets(0, 1, 2, 4, 8, 16, 32, 64))]
+    pub liquidity_coverage: prometheus::HistogramVec,
+    /// Number of token pairs of an auction without any liquidity, which can
+    /// only be traded in a CoW.
+    #[metric(labels("solver"))]
+    pub uncovered_token_pairs: prometheus::IntCounterVec,
+    /// Number of solutions provided by a solver's fallback solver engine.
+    #[metric(labels("solver"))]
+    pub fallback_solutions: prometheus::IntCounterVec,
+    /// Number of settlements which are only profitable because their
+    /// interactions are internalized.
+    #[metric(labels("solver"))]
+    pub internalization_required: prometheus::IntCounterVec,
+    /// Time spent in each phase of solving an auction.
+    #[metric(
+        labels("solver", "phase"),
+        buckets(0.01, 0.05, 0.1, 0.25, 0.5, 1, 2, 4, 8, 16)
+    )]
+    pub solve_phase_duration: prometheus::HistogramVec,
 }
 
 /// Setup the metrics registry.
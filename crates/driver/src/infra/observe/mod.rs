@@ -15,6 +15,7 @@ use {
                 Solved,
             },
             eth::{self, Gas},
+            liquidity,
             mempools,
             quote::{self, Quote},
             time::{Deadline, Remaining},
@@ -32,8 +33,8 @@ mod metrics;
 
 /// Setup the observability. The log argument configures the tokio tracing
 /// framework.
-pub fn init(log: &str) {
-    observe::tracing::initialize_reentrant(log);
+pub fn init(log: &str, enable_task_console: bool) {
+    observe::tracing::initialize_reentrant(log, enable_task_console);
     metrics::init();
 }
 
@@ -56,6 +57,24 @@ pub fn fetched_liquidity(liquidity: &[Liquidity]) {
     tracing::debug!(liquidity = ?grouped, "fetched liquidity sources");
 }
 
+/// Observe how many liquidity sources are available for the token pairs of
+/// an auction.
+pub fn liquidity_coverage(solver: &solver::Name, coverage: &liquidity::Coverage) {
+    let uncovered = coverage.uncovered().collect::<Vec<_>>();
+    tracing::debug!(?coverage, ?uncovered, "liquidity coverage");
+    let metrics = metrics::get();
+    for (_, total) in coverage.totals() {
+        metrics
+            .liquidity_coverage
+            .with_label_values(&[solver.as_str()])
+            .observe(total as f64);
+    }
+    metrics
+        .uncovered_token_pairs
+        .with_label_values(&[solver.as_str()])
+        .inc_by(uncovered.len() as u64);
+}
+
 /// Observe that fetching liquidity failed.
 pub fn fetching_liquidity_failed(err: &boundary::Error) {
     tracing::warn!(?err, "failed to fetch liquidity");
@@ -78,7 +97,11 @@ pub fn solutions(
         .iter()
         .any(|s| !s.is_empty(surplus_capturing_jit_order_owners))
     {
-        tracing::info!(?solutions, "computed solutions");
+        let content_ids = solutions
+            .iter()
+            .map(|solution| (solution.id().clone(), solution.content_id()))
+            .collect::<Vec<_>>();
+        tracing::info!(?solutions, ?content_ids, "computed solutions");
     } else {
         tracing::debug!("no solutions");
     }
@@ -93,6 +116,38 @@ pub fn empty_solution(solver: &solver::Name, id: &solution::Id) {
         .inc();
 }
 
+/// Observe that a solution was discarded because it settles more JIT orders
+/// than the solver is allowed to.
+pub fn too_many_jit_orders(solver: &solver::Name, id: &solution::Id) {
+    tracing::debug!(?id, "discarded solution: too many JIT orders");
+    metrics::get()
+        .dropped_solutions
+        .with_label_values(&[solver.as_str(), "TooManyJitOrders"])
+        .inc();
+}
+
+/// Observe that an order was dropped because it trades a denied token.
+pub fn order_denied(solver: &solver::Name, uid: competition::order::Uid) {
+    tracing::debug!(%solver, ?uid, "dropped order trading a denied token");
+}
+
+/// Observe that an order was dropped because its limit price is too far off
+/// the market to ever be filled.
+pub fn order_off_market(solver: &solver::Name, uid: competition::order::Uid) {
+    tracing::debug!(%solver, ?uid, "dropped order with off-market limit price");
+}
+
+/// Observe that an order of the settlement expired before it was settled.
+pub fn order_expired(solver: &solver::Name, uid: &competition::order::Uid) {
+    tracing::info!(%solver, ?uid, "order expired before settling");
+}
+
+/// Observe that a settlement about to be submitted contains orders expiring
+/// within the solver's grace period.
+pub fn orders_expiring_soon(solver: &solver::Name, uids: &HashSet<competition::order::Uid>) {
+    tracing::info!(%solver, ?uids, "settling orders expiring soon");
+}
+
 // Observe that postprocessing (encoding & merging) of solutions is about to
 // start.
 pub fn postprocessing(solutions: &[Solution], deadline: chrono::DateTime<chrono::Utc>) {
@@ -135,6 +190,71 @@ pub fn not_merged(first: &Solution, other: &Solution, err: solution::error::Merg
     tracing::debug!(?err, ?first, ?other, "solutions can't be merged");
 }
 
+/// Observe that merging solutions was stopped early to not exceed the deadline.
+pub fn merging_deadline_reached(solver: &solver::Name) {
+    tracing::debug!(%solver, "stopped merging solutions at the merge deadline");
+}
+
+/// Observe the report of a solved auction.
+pub fn solve_report(solver: &solver::Name, report: &competition::SolveReport) {
+    tracing::info!(
+        %solver,
+        auction_id = ?report.auction_id,
+        orders = report.orders,
+        solved_orders = report.solved_orders,
+        solutions = report.solutions,
+        skipped_orders = ?report.skipped_orders,
+        skipped_solutions = ?report.skipped_solutions,
+        merges = report.merges,
+        winner_score = ?report.winner_score,
+        duration = ?report.duration,
+        "solve report"
+    );
+}
+
+/// Observe that the fallback solver engine was asked for solutions because
+/// the solver didn't return any.
+pub fn fallback_solutions(solver: &solver::Name, solutions: usize) {
+    tracing::info!(%solver, solutions, "solutions provided by fallback solver");
+    metrics::get()
+        .fallback_solutions
+        .with_label_values(&[solver.as_str()])
+        .inc_by(solutions as u64);
+}
+
+/// Observe that the fallback solver engine failed to provide solutions.
+pub fn fallback_failed(solver: &solver::Name, err: &solver::Error) {
+    tracing::warn!(%solver, ?err, "fallback solver failed");
+}
+
+/// Observe how long each phase of solving an auction took.
+pub fn solve_phases(
+    solver: &solver::Name,
+    auction_id: Option<competition::auction::Id>,
+    phases: &[(&'static str, std::time::Duration)],
+) {
+    tracing::debug!(%solver, ?auction_id, ?phases, "solve phase durations");
+    for (phase, duration) in phases {
+        metrics::get()
+            .solve_phase_duration
+            .with_label_values(&[solver.as_str(), phase])
+            .observe(duration.as_secs_f64());
+    }
+}
+
+/// Observe the outcome of merging the solutions of a solve round.
+pub fn merging(solver: &solver::Name, attempts: usize, solutions: usize) {
+    tracing::debug!(attempts, solutions, "finished merging solutions");
+    metrics::get()
+        .merge_attempts
+        .with_label_values(&[solver.as_str()])
+        .inc_by(attempts as u64);
+    metrics::get()
+        .merged_solutions
+        .with_label_values(&[solver.as_str()])
+        .observe(solutions as f64);
+}
+
 /// Observe that scoring is about to start.
 pub fn scoring(settlement: &Settlement) {
     tracing::trace!(
@@ -158,16 +278,94 @@ pub fn score(settlement: &Settlement, score: &eth::Ether) {
     tracing::info!(
         solution = ?settlement.solution(),
         score = ?score,
+        cow_matches = settlement.cow_match_count(),
         "scored settlement"
     );
 }
 
+/// Observe that the settlement of a previous auction was kept because it
+/// scores better than the best settlement of the current auction.
+pub fn kept_pending_settlement(solver: &solver::Name, settlement: &Settlement, score: &eth::Ether) {
+    tracing::info!(
+        %solver,
+        solution = ?settlement.solution(),
+        ?score,
+        "kept better scoring pending settlement"
+    );
+}
+
+/// Observe how internalizing interactions affects the profitability of a
+/// scored settlement.
+pub fn profitability(
+    solver: &solver::Name,
+    settlement: &Settlement,
+    profitability: &solution::settlement::Profitability,
+) {
+    let delta = profitability.delta();
+    if profitability.requires_internalization() {
+        tracing::warn!(
+            solution = ?settlement.solution(),
+            internalized = %profitability.internalized,
+            uninternalized = %profitability.uninternalized,
+            %delta,
+            "settlement only profitable when internalized"
+        );
+        metrics::get()
+            .internalization_required
+            .with_label_values(&[solver.as_str()])
+            .inc();
+    } else {
+        tracing::debug!(
+            solution = ?settlement.solution(),
+            internalized = %profitability.internalized,
+            uninternalized = %profitability.uninternalized,
+            %delta,
+            "internalization profitability"
+        );
+    }
+}
+
+/// Observe the scored settlement of a shadow solver, which never competes for
+/// winning the auction.
+pub fn shadow_settlement(solver: &solver::Name, settlement: &Settlement, score: &eth::Ether) {
+    tracing::info!(
+        solution = ?settlement.solution(),
+        ?score,
+        gas = ?settlement.gas.estimate,
+        "discarded settlement: shadow solver"
+    );
+    metrics::get()
+        .dropped_solutions
+        .with_label_values(&[solver.as_str(), "Shadow"])
+        .inc();
+}
+
+/// Observe that a settlement was discarded because it doesn't generate enough
+/// surplus per unit of gas.
+pub fn inefficient_settlement(solver: &solver::Name, settlement: &Settlement, score: &eth::Ether) {
+    tracing::debug!(
+        solution = ?settlement.solution(),
+        ?score,
+        gas = ?settlement.gas.estimate,
+        "discarded settlement: too little surplus per gas"
+    );
+    metrics::get()
+        .dropped_solutions
+        .with_label_values(&[solver.as_str(), "InsufficientSurplusPerGas"])
+        .inc();
+}
+
 // Observe that the winning settlement started failing upon arrival of a new
 // block
 pub fn winner_voided(block: BlockInfo, err: &simulator::RevertError) {
     tracing::warn!(block = block.number, ?err, "solution reverts on new block");
 }
 
+/// Observe that a solver was paused or resumed through the API.
+pub fn paused(solver: &solver::Name, paused: bool) {
+    tracing::warn!(%solver, paused, "solver participation changed");
+}
+
 pub fn revealing() {
     tracing::trace!("revealing");
 }
@@ -343,6 +541,9 @@ pub fn mempool_executed(
         Err(mempools::Error::Expired) => "Expired",
         Err(mempools::Error::Other(_)) => "Other",
         Err(mempools::Error::Disabled) => "Disabled",
+        Err(mempools::Error::GasPriceIncreased) => "GasPriceIncreased",
+        Err(mempools::Error::Reorged(_)) => "Reorged",
+        Err(mempools::Error::NonceConflict(_)) => "NonceConflict",
     };
     metrics::get()
         .mempool_submission
@@ -368,6 +569,9 @@ fn competition_error(err: &competition::Error) -> &'static str {
         competition::Error::Solver(solver::Error::Deserialize(_)) => "SolverDeserializeError",
         competition::Error::Solver(solver::Error::Dto(_)) => "SolverDtoError",
         competition::Error::SubmissionError => "SubmissionError",
+        competition::Error::SolverPaused => "SolverPaused",
+        competition::Error::OrderExpired => "OrderExpired",
+        competition::Error::AuctionTooOld => "AuctionTooOld",
     }
 }
 
@@ -380,6 +584,7 @@ pub enum OrderExcludedFromAuctionReason {
     CouldNotFetchBalance,
     InsufficientBalance,
     OrderWithZeroAmountRemaining,
+    MissingPreSignature,
 }
 
 pub fn order_excluded_from_auction(
@@ -389,6 +594,17 @@ pub fn order_excluded_from_auction(
     tracing::trace!(uid=?order.uid, ?reason, "order excluded from auction");
 }
 
+/// Observe the result of isolating the interaction which makes a settlement
+/// revert.
+pub fn revert_isolated(id: &solution::Id, isolated: Option<(usize, String)>) {
+    match isolated {
+        Some((interaction, reason)) => {
+            tracing::warn!(?id, interaction, reason, "isolated reverting interaction")
+        }
+        None => tracing::warn!(?id, "could not isolate reverting interaction"),
+    }
+}
+
 /// Observe that a settlement was simulated
 pub fn simulated(eth: &Ethereum, tx: &eth::Tx, gas: &Result<Gas, simulator::Error>) {
     let block: eth::BlockNo = eth.current_block().borrow().number.into();
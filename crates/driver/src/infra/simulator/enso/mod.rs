@@ -1,4 +1,5 @@
 use {
+    super::Block,
     crate::domain::eth,
     ethrpc::current_block::CurrentBlockStream,
     reqwest::ClientBuilder,
@@ -40,12 +41,20 @@ impl Enso {
         }
     }
 
-    pub(super) async fn simulate(&self, tx: eth::Tx) -> Result<eth::Gas, Error> {
+    pub(super) async fn simulate(
+        &self,
+        tx: eth::Tx,
+        inclusion: Option<Block>,
+    ) -> Result<eth::Gas, Error> {
         let current_block = *self.current_block.borrow();
 
-        let (block_number, block_timestamp) = match self.network_block_interval {
-            None => (None, None), // use default values which result in simulation on `latest`
-            Some(duration) => {
+        let (block_number, block_timestamp) = match (inclusion, self.network_block_interval) {
+            // `enso` can only fork existing blocks so only the timestamp of the
+            // inclusion block can be applied.
+            (Some(inclusion), _) => (Some(current_block.number), Some(inclusion.timestamp)),
+            // use default values which result in simulation on `latest`
+            (None, None) => (None, None),
+            (None, Some(duration)) => {
                 // We would like to simulate on the `pending` block instead of the `latest`
                 // block. Unfortunately `enso` does not support that so to get closer to
                 // the actual behavior of the `pending` block we use the block number of
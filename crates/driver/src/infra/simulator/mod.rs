@@ -3,7 +3,9 @@ use {
         domain::eth,
         infra::blockchain::{self, Ethereum},
     },
+    ethrpc::current_block::BlockInfo,
     observe::future::Measure,
+    std::time::Duration,
 };
 
 pub mod enso;
@@ -18,6 +20,13 @@ pub struct Simulator {
     /// If this is [`Some`], every gas estimate will return this fixed
     /// gas value.
     disable_gas: Option<eth::Gas>,
+    /// If this is [`Some`], gas is simulated as if the transaction was
+    /// included this many blocks after the current one.
+    inclusion_offset: Option<InclusionOffset>,
+    /// Whether to attach the call trace to reverting simulations.
+    trace_reverts: bool,
+    /// Whether to isolate the interaction making a settlement revert.
+    isolate_reverts: bool,
 }
 
 /// Configuration of the transaction simulator.
@@ -36,6 +45,9 @@ impl Simulator {
             eth,
             disable_access_lists: false,
             disable_gas: None,
+            inclusion_offset: None,
+            trace_reverts: false,
+            isolate_reverts: false,
         }
     }
 
@@ -47,6 +59,9 @@ impl Simulator {
             eth,
             disable_access_lists: false,
             disable_gas: None,
+            inclusion_offset: None,
+            trace_reverts: false,
+            isolate_reverts: false,
         }
     }
 
@@ -63,6 +78,9 @@ impl Simulator {
             eth,
             disable_access_lists: false,
             disable_gas: None,
+            inclusion_offset: None,
+            trace_reverts: false,
+            isolate_reverts: false,
         }
     }
 
@@ -78,6 +96,31 @@ impl Simulator {
         self.disable_gas = Some(fixed_gas);
     }
 
+    /// Simulate gas as if transactions were included a number of blocks after
+    /// the current one. Useful for chains with specific sequencing, where the
+    /// state at inclusion differs from `latest`.
+    pub fn inclusion_offset(&mut self, offset: InclusionOffset) {
+        self.inclusion_offset = Some(offset);
+    }
+
+    /// Capture the call trace of reverting transactions and attach it to the
+    /// [`RevertError`]. This is expensive, so it should only be enabled for
+    /// diagnosing failing settlements.
+    pub fn trace_reverts(&mut self) {
+        self.trace_reverts = true;
+    }
+
+    /// Bisect the interactions of reverting settlements to find the one
+    /// causing the revert. This re-simulates the settlement many times, so it
+    /// should only be enabled for debugging.
+    pub fn isolate_reverts(&mut self) {
+        self.isolate_reverts = true;
+    }
+
+    pub fn isolates_reverts(&self) -> bool {
+        self.isolate_reverts
+    }
+
     /// Simulate the access list needed by a transaction. If the transaction
     /// already has an access list, the returned access list will be a
     /// superset of the existing one.
@@ -87,24 +130,23 @@ impl Simulator {
         }
         let block = self.eth.current_block().borrow().number.into();
         let access_list = match &self.inner {
-            Inner::Tenderly(tenderly) => {
-                tenderly
-                    .simulate(tx, tenderly::GenerateAccessList::Yes)
-                    .await
-                    .map_err(with(tx.clone(), block))?
-                    .access_list
-            }
+            Inner::Tenderly(tenderly) => tenderly
+                .simulate(tx, tenderly::GenerateAccessList::Yes, None)
+                .await
+                .map(|simulation| simulation.access_list)
+                .map_err(with(tx.clone(), block)),
             Inner::Ethereum => self
                 .eth
                 .create_access_list(tx.clone())
                 .await
-                .map_err(with(tx.clone(), block))?,
+                .map_err(with(tx.clone(), block)),
             Inner::Enso(_) => self
                 .eth
                 .create_access_list(tx.clone())
                 .await
-                .map_err(with(tx.clone(), block))?,
+                .map_err(with(tx.clone(), block)),
         };
+        let access_list = self.traced(access_list).await?;
         Ok(tx.access_list.clone().merge(access_list))
     }
 
@@ -113,30 +155,76 @@ impl Simulator {
         if let Some(gas) = self.disable_gas {
             return Ok(gas);
         }
-        let block = self.eth.current_block().borrow().number.into();
-        Ok(match &self.inner {
-            Inner::Tenderly(tenderly) => {
-                tenderly
-                    .simulate(tx, tenderly::GenerateAccessList::No)
-                    .measure("tenderly_simulate_gas")
-                    .await
-                    .map_err(with(tx.clone(), block))?
-                    .gas
-            }
-            Inner::Ethereum => self
-                .eth
-                .estimate_gas(tx)
+        let current_block = *self.eth.current_block().borrow();
+        let block = current_block.number.into();
+        let inclusion = self
+            .inclusion_offset
+            .map(|offset| offset.block(&current_block));
+        let gas = match &self.inner {
+            Inner::Tenderly(tenderly) => tenderly
+                .simulate(tx, tenderly::GenerateAccessList::No, inclusion)
+                .measure("tenderly_simulate_gas")
                 .await
-                .map_err(with(tx.clone(), block))?,
+                .map(|simulation| simulation.gas)
+                .map_err(with(tx.clone(), block)),
+            Inner::Ethereum => match inclusion {
+                Some(inclusion) => self.eth.estimate_gas_in_block(tx, inclusion).await,
+                None => self.eth.estimate_gas(tx).await,
+            }
+            .map_err(with(tx.clone(), block)),
             Inner::Enso(enso) => enso
-                .simulate(tx.clone())
+                .simulate(tx.clone(), inclusion)
                 .measure("enso_simulate_gas")
                 .await
-                .map_err(with(tx.clone(), block))?,
-        })
+                .map_err(with(tx.clone(), block)),
+        };
+        self.traced(gas).await
+    }
+
+    /// Attaches the call trace to a reverting simulation if tracing is
+    /// enabled. Tracing is best effort, the revert is returned either way.
+    async fn traced<T>(&self, result: Result<T, Error>) -> Result<T, Error> {
+        match result {
+            Err(Error::Revert(mut err)) if self.trace_reverts => {
+                match self.eth.trace_call(&err.tx).await {
+                    Ok(trace) => err.trace = Some(trace),
+                    Err(trace_err) => {
+                        tracing::warn!(?trace_err, "failed to trace reverting simulation")
+                    }
+                }
+                Err(Error::Revert(err))
+            }
+            result => result,
+        }
+    }
+}
+
+/// How many blocks after the current one transactions are assumed to be
+/// included when simulating them.
+#[derive(Debug, Clone, Copy)]
+pub struct InclusionOffset {
+    pub blocks: u64,
+    /// The time between two blocks of the network.
+    pub block_time: Duration,
+}
+
+impl InclusionOffset {
+    /// The block transactions are simulated in, given the current block.
+    fn block(&self, current: &BlockInfo) -> Block {
+        Block {
+            number: current.number + self.blocks,
+            timestamp: current.timestamp + self.block_time.as_secs() * self.blocks,
+        }
     }
 }
 
+/// Number and timestamp of the block a transaction is simulated in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block {
+    pub number: u64,
+    pub timestamp: u64,
+}
+
 #[derive(Debug, Clone)]
 enum Inner {
     Tenderly(tenderly::Tenderly),
@@ -157,11 +245,14 @@ pub enum SimulatorError {
 }
 
 #[derive(Debug, thiserror::Error)]
-#[error("block: {block:?},  err: {err:?}, tx: {tx:?}")]
+#[error("block: {block:?},  err: {err:?}, tx: {tx:?}, trace: {trace:?}")]
 pub struct RevertError {
     pub err: SimulatorError,
     pub tx: eth::Tx,
     pub block: eth::BlockNo,
+    /// The call trace of the reverting transaction, if tracing reverts is
+    /// enabled.
+    pub trace: Option<serde_json::Value>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -197,8 +288,53 @@ where
             SimulatorError::GasExceeded(..) => Some(tx),
         };
         match tx {
-            Some(tx) => Error::Revert(RevertError { err, tx, block }),
+            Some(tx) => Error::Revert(RevertError {
+                err,
+                tx,
+                block,
+                trace: None,
+            }),
             None => Error::Other(err),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inclusion_offset_advances_number_and_timestamp() {
+        let current = BlockInfo {
+            number: 100,
+            timestamp: 1_000,
+            ..Default::default()
+        };
+
+        let latest = InclusionOffset {
+            blocks: 0,
+            block_time: Duration::from_secs(12),
+        }
+        .block(&current);
+        assert_eq!(
+            latest,
+            Block {
+                number: 100,
+                timestamp: 1_000
+            }
+        );
+
+        let ahead = InclusionOffset {
+            blocks: 2,
+            block_time: Duration::from_secs(12),
+        }
+        .block(&current);
+        assert_eq!(
+            ahead,
+            Block {
+                number: 102,
+                timestamp: 1_024
+            }
+        );
+    }
+}
@@ -22,6 +22,15 @@ pub struct Request {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub access_list: Option<AccessList>,
     pub gas_price: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_header: Option<BlockHeader>,
+}
+
+/// Overrides of the header of the block the transaction is simulated in.
+#[derive(Debug, Serialize)]
+pub struct BlockHeader {
+    pub number: web3::types::U64,
+    pub timestamp: web3::types::U64,
 }
 
 #[derive(Debug, Deserialize)]
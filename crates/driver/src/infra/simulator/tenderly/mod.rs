@@ -1,4 +1,5 @@
 use {
+    super::Block,
     crate::{domain::eth, infra::Ethereum},
     thiserror::Error,
 };
@@ -68,6 +69,7 @@ impl Tenderly {
         &self,
         tx: &eth::Tx,
         generate_access_list: GenerateAccessList,
+        inclusion: Option<Block>,
     ) -> Result<Simulation, Error> {
         let gas_price = self.eth.simulation_gas_price().await;
 
@@ -89,6 +91,10 @@ impl Tenderly {
                     Some(tx.access_list.clone().into())
                 },
                 gas_price: gas_price.unwrap_or_default().as_u64(),
+                block_header: inclusion.map(|block| dto::BlockHeader {
+                    number: block.number.into(),
+                    timestamp: block.timestamp.into(),
+                }),
             })
             .send()
             .await?
@@ -33,6 +33,7 @@ impl Auction {
         weth: eth::WethAddress,
         fee_handler: FeeHandler,
         solver_native_token: ManageNativeToken,
+        min_order_value: Option<eth::Ether>,
     ) -> Self {
         let mut tokens: HashMap<eth::H160, _> = auction
             .tokens()
@@ -53,24 +54,7 @@ impl Auction {
 
         // Make sure that we have at least empty entries for all tokens for
         // which we are providing liquidity.
-        for token in liquidity
-            .iter()
-            .flat_map(|liquidity| match &liquidity.kind {
-                liquidity::Kind::UniswapV2(pool) => pool.reserves.iter().map(|r| r.token).collect(),
-                liquidity::Kind::UniswapV3(pool) => vec![pool.tokens.get().0, pool.tokens.get().1],
-                liquidity::Kind::BalancerV2Stable(pool) => pool.reserves.tokens().collect(),
-                liquidity::Kind::BalancerV2Weighted(pool) => pool.reserves.tokens().collect(),
-                liquidity::Kind::Swapr(pool) => {
-                    pool.base.reserves.iter().map(|r| r.token).collect()
-                }
-                liquidity::Kind::ZeroEx(limit_order) => {
-                    vec![
-                        limit_order.order.maker_token.into(),
-                        limit_order.order.taker_token.into(),
-                    ]
-                }
-            })
-        {
+        for token in liquidity.iter().flat_map(liquidity::Liquidity::tokens) {
             tokens.entry(token.into()).or_insert_with(Default::default);
         }
 
@@ -79,9 +63,41 @@ impl Auction {
             orders: auction
                 .orders()
                 .iter()
+                .filter(|order| {
+                    let Some(min) = min_order_value else {
+                        return true;
+                    };
+                    let sell = order.available().sell;
+                    let price = auction.tokens().get(sell.token.wrap(weth)).price;
+                    if is_below_min_value(sell.amount, price, min) {
+                        tracing::trace!(uid = ?order.uid, "dropping order below minimum value");
+                        return false;
+                    }
+                    true
+                })
                 .map(|order| {
                     let mut available = order.available();
 
+                    if let order::Kind::Limit {
+                        oracle: Some(oracle),
+                    } = order.kind
+                    {
+                        let tokens = auction.tokens();
+                        match (
+                            tokens.get(available.sell.token.wrap(weth)).price,
+                            tokens.get(available.buy.token.wrap(weth)).price,
+                        ) {
+                            (Some(sell_price), Some(buy_price)) => {
+                                available =
+                                    oracle.apply(order.side, available, sell_price, buy_price)
+                            }
+                            _ => tracing::debug!(
+                                uid = ?order.uid,
+                                "missing reference prices for oracle limit order"
+                            ),
+                        }
+                    }
+
                     if solver_native_token.wrap_address {
                         available.buy.token = available.buy.token.wrap(weth)
                     }
@@ -95,7 +111,7 @@ impl Auction {
                     // https://github.com/cowprotocol/services/issues/2440
                     if fee_handler == FeeHandler::Driver {
                         order.protocol_fees.iter().for_each(|protocol_fee| {
-                            if let fees::FeePolicy::Volume { factor } = protocol_fee {
+                            if let fees::FeePolicy::Volume { factor, .. } = protocol_fee {
                                 match order.side {
                                     Side::Buy => {
                                         // reduce sell amount by factor
@@ -406,7 +422,11 @@ pub enum FeePolicy {
         quote: Quote,
     },
     #[serde(rename_all = "camelCase")]
-    Volume { factor: f64 },
+    Volume {
+        factor: f64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        recipient: Option<eth::H160>,
+    },
 }
 
 impl From<fees::FeePolicy> for FeePolicy {
@@ -432,7 +452,10 @@ impl From<fees::FeePolicy> for FeePolicy {
                     fee: quote.fee.amount.into(),
                 },
             },
-            order::FeePolicy::Volume { factor } => FeePolicy::Volume { factor },
+            order::FeePolicy::Volume { factor, recipient } => FeePolicy::Volume {
+                factor,
+                recipient: recipient.map(Into::into),
+            },
         }
     }
 }
@@ -614,3 +637,35 @@ fn scaling_factor_to_decimal(
 ) -> bigdecimal::BigDecimal {
     bigdecimal::BigDecimal::new(scale.as_raw().to_big_int(), 18)
 }
+
+/// Whether the given sell amount is worth less than `min` according to the
+/// reference price of the sell token. Amounts of tokens without a reference
+/// price are never considered too small.
+fn is_below_min_value(
+    amount: eth::TokenAmount,
+    price: Option<competition::auction::Price>,
+    min: eth::Ether,
+) -> bool {
+    price.is_some_and(|price| price.in_eth(amount) < min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_below_min_value_are_dropped() {
+        let min = eth::Ether(eth::U256::exp10(16)); // 0.01 ETH
+                                                    // A token worth 0.001 ETH.
+        let price = competition::auction::Price::new(eth::U256::exp10(15).into()).unwrap();
+        let amount = |tokens: u64| eth::TokenAmount(eth::U256::from(tokens) * eth::U256::exp10(18));
+
+        // 5 tokens are worth 0.005 ETH.
+        assert!(is_below_min_value(amount(5), Some(price), min));
+        // 10 tokens are worth exactly the minimum.
+        assert!(!is_below_min_value(amount(10), Some(price), min));
+        assert!(!is_below_min_value(amount(50), Some(price), min));
+        // Without a reference price the value is unknown.
+        assert!(!is_below_min_value(amount(5), None, min));
+    }
+}
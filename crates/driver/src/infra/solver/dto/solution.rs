@@ -58,55 +58,66 @@ impl Solutions {
                                 .map(competition::solution::Trade::Fulfillment)
                                 .map_err(|err| super::Error(format!("invalid fulfillment: {err}")))
                             }
-                            Trade::Jit(jit) => Ok(competition::solution::Trade::Jit(
-                                competition::solution::trade::Jit::new(
-                                    competition::order::Jit {
-                                        uid: jit.order.uid(
-                                            solver.eth.contracts().settlement_domain_separator(),
-                                        )?,
-                                        sell: eth::Asset {
-                                            amount: jit.order.sell_amount.into(),
-                                            token: jit.order.sell_token.into(),
-                                        },
-                                        buy: eth::Asset {
-                                            amount: jit.order.buy_amount.into(),
-                                            token: jit.order.buy_token.into(),
-                                        },
-                                        receiver: jit.order.receiver.into(),
-                                        valid_to: jit.order.valid_to.into(),
-                                        app_data: jit.order.app_data.into(),
-                                        side: match jit.order.kind {
-                                            Kind::Sell => competition::order::Side::Sell,
-                                            Kind::Buy => competition::order::Side::Buy,
-                                        },
-                                        sell_token_balance: match jit.order.sell_token_balance {
-                                            SellTokenBalance::Erc20 => {
-                                                competition::order::SellTokenBalance::Erc20
-                                            }
-                                            SellTokenBalance::Internal => {
-                                                competition::order::SellTokenBalance::Internal
-                                            }
-                                            SellTokenBalance::External => {
-                                                competition::order::SellTokenBalance::External
-                                            }
-                                        },
-                                        buy_token_balance: match jit.order.buy_token_balance {
-                                            BuyTokenBalance::Erc20 => {
-                                                competition::order::BuyTokenBalance::Erc20
-                                            }
-                                            BuyTokenBalance::Internal => {
-                                                competition::order::BuyTokenBalance::Internal
-                                            }
-                                        },
-                                        signature: jit.order.signature(
-                                            solver.eth.contracts().settlement_domain_separator(),
-                                        )?,
+                            Trade::Jit(jit) => {
+                                let order = competition::order::Jit {
+                                    uid: jit.order.uid(
+                                        solver.eth.contracts().settlement_domain_separator(),
+                                    )?,
+                                    sell: eth::Asset {
+                                        amount: jit.order.sell_amount.into(),
+                                        token: jit.order.sell_token.into(),
                                     },
-                                    jit.executed_amount.into(),
-                                    jit.fee.into(),
-                                )
-                                .map_err(|err| super::Error(format!("invalid JIT trade: {err}")))?,
-                            )),
+                                    buy: eth::Asset {
+                                        amount: jit.order.buy_amount.into(),
+                                        token: jit.order.buy_token.into(),
+                                    },
+                                    receiver: jit.order.receiver.into(),
+                                    valid_to: jit.order.valid_to.into(),
+                                    app_data: jit.order.app_data.into(),
+                                    side: match jit.order.kind {
+                                        Kind::Sell => competition::order::Side::Sell,
+                                        Kind::Buy => competition::order::Side::Buy,
+                                    },
+                                    sell_token_balance: match jit.order.sell_token_balance {
+                                        SellTokenBalance::Erc20 => {
+                                            competition::order::SellTokenBalance::Erc20
+                                        }
+                                        SellTokenBalance::Internal => {
+                                            competition::order::SellTokenBalance::Internal
+                                        }
+                                        SellTokenBalance::External => {
+                                            competition::order::SellTokenBalance::External
+                                        }
+                                    },
+                                    buy_token_balance: match jit.order.buy_token_balance {
+                                        BuyTokenBalance::Erc20 => {
+                                            competition::order::BuyTokenBalance::Erc20
+                                        }
+                                        BuyTokenBalance::Internal => {
+                                            competition::order::BuyTokenBalance::Internal
+                                        }
+                                    },
+                                    signature: jit.order.signature(
+                                        solver.eth.contracts().settlement_domain_separator(),
+                                    )?,
+                                    partially_fillable: jit.order.partially_fillable,
+                                };
+                                check_jit_order(
+                                    &order,
+                                    &solution.pre_interactions,
+                                    solver.eth.contracts().settlement().address(),
+                                )?;
+                                Ok(competition::solution::Trade::Jit(
+                                    competition::solution::trade::Jit::new(
+                                        order,
+                                        jit.executed_amount.into(),
+                                        jit.fee.into(),
+                                    )
+                                    .map_err(|err| {
+                                        super::Error(format!("invalid JIT trade: {err}"))
+                                    })?,
+                                ))
+                            }
                         })
                         .try_collect()?,
                     solution
@@ -221,6 +232,31 @@ impl Solutions {
     }
 }
 
+/// JIT orders owned by the settlement contract, or set up by pre-interactions
+/// calling into it, could re-enter the settlement and confuse its accounting.
+fn check_jit_order(
+    order: &competition::order::Jit,
+    pre_interactions: &[InteractionData],
+    settlement: eth::H160,
+) -> Result<(), super::Error> {
+    if order.signature.signer.0 == settlement {
+        return Err(super::Error(format!(
+            "JIT order {:?} is owned by the settlement contract",
+            order.uid
+        )));
+    }
+    if pre_interactions
+        .iter()
+        .any(|interaction| interaction.target == settlement)
+    {
+        return Err(super::Error(format!(
+            "JIT order {:?} is set up by a pre-interaction calling the settlement contract",
+            order.uid
+        )));
+    }
+    Ok(())
+}
+
 #[serde_as]
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -299,6 +335,8 @@ struct JitOrder {
     signing_scheme: SigningScheme,
     #[serde_as(as = "serialize::Hex")]
     signature: Vec<u8>,
+    #[serde(default)]
+    partially_fillable: bool,
 }
 
 impl JitOrder {
@@ -316,7 +354,7 @@ impl JitOrder {
                 Kind::Sell => OrderKind::Sell,
                 Kind::Buy => OrderKind::Buy,
             },
-            partially_fillable: false,
+            partially_fillable: self.partially_fillable,
             sell_token_balance: match self.sell_token_balance {
                 SellTokenBalance::Erc20 => SellTokenSource::Erc20,
                 SellTokenBalance::Internal => SellTokenSource::Internal,
@@ -480,3 +518,96 @@ pub enum Score {
     #[serde(rename_all = "camelCase")]
     RiskAdjusted { success_probability: f64 },
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[test]
+    fn deserializes_engine_solutions() {
+        let solutions = serde_json::from_value::<Solutions>(json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": "6043910341261930467761",
+                    "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": "133700000000000000"
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a",
+                        "executedAmount": "133700000000000000"
+                    }
+                ],
+                "preInteractions": [],
+                "interactions": [
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "0",
+                        "inputToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                        "outputToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                        "inputAmount": "133700000000000000",
+                        "outputAmount": "6043910341261930467761"
+                    }
+                ],
+                "postInteractions": [],
+                "gas": 166391,
+            }]
+        }))
+        .unwrap();
+
+        assert_eq!(solutions.solutions.len(), 1);
+        let solution = &solutions.solutions[0];
+        assert_eq!(solution.prices.len(), 2);
+        assert_eq!(solution.gas, Some(166391));
+        assert!(matches!(
+            &solution.trades[..],
+            [Trade::Fulfillment(Fulfillment { order: uid, .. })] if uid == &[0x2a; order::UID_LEN]
+        ));
+        assert!(matches!(
+            &solution.interactions[..],
+            [Interaction::Liquidity(LiquidityInteraction { id: 0, .. })]
+        ));
+    }
+
+    #[test]
+    fn rejects_self_referential_jit_orders() {
+        let settlement = eth::H160::from_low_u64_be(0x5e77);
+        let jit = |owner: eth::H160| competition::order::Jit {
+            sell: eth::Asset {
+                token: eth::H160::from_low_u64_be(1).into(),
+                amount: eth::U256::from(1000).into(),
+            },
+            buy: eth::Asset {
+                token: eth::H160::from_low_u64_be(2).into(),
+                amount: eth::U256::from(500).into(),
+            },
+            receiver: owner.into(),
+            valid_to: u32::MAX.into(),
+            app_data: Default::default(),
+            side: order::Side::Sell,
+            sell_token_balance: order::SellTokenBalance::Erc20,
+            buy_token_balance: order::BuyTokenBalance::Erc20,
+            signature: order::Signature {
+                scheme: order::signature::Scheme::PreSign,
+                data: Default::default(),
+                signer: owner.into(),
+            },
+            uid: Default::default(),
+            partially_fillable: false,
+        };
+        let interaction = |target: eth::H160| InteractionData {
+            target,
+            value: Default::default(),
+            call_data: Default::default(),
+        };
+        let maker = eth::H160::from_low_u64_be(0x3a4e);
+
+        assert!(check_jit_order(&jit(maker), &[interaction(maker)], settlement).is_ok());
+        assert!(check_jit_order(&jit(settlement), &[], settlement).is_err());
+        assert!(check_jit_order(&jit(maker), &[interaction(settlement)], settlement).is_err());
+    }
+}
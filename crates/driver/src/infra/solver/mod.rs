@@ -3,6 +3,7 @@ use {
     crate::{
         domain::{
             competition::{
+                self,
                 auction::{self, Auction},
                 solution::{self, Solution},
             },
@@ -13,15 +14,21 @@ use {
         infra::{
             blockchain::Ethereum,
             config::file::FeeHandler,
+            cosigner,
             persistence::{Persistence, S3},
+            webhook,
         },
         util,
     },
     anyhow::Result,
     derive_more::{From, Into},
+    futures::future::{self, Either},
     num::BigRational,
     reqwest::header::HeaderName,
-    std::collections::HashMap,
+    std::{
+        collections::{HashMap, HashSet},
+        future::Future,
+    },
     tap::TapFallible,
     thiserror::Error,
     tracing::Instrument,
@@ -29,8 +36,6 @@ use {
 
 pub mod dto;
 
-const SOLVER_RESPONSE_MAX_BYTES: usize = 10_000_000;
-
 // TODO At some point I should be checking that the names are unique, I don't
 // think I'm doing that.
 /// The solver name. The user can configure this to be anything that they like.
@@ -55,6 +60,8 @@ impl std::fmt::Display for Name {
 pub struct Slippage {
     pub relative: BigRational,
     pub absolute: Option<eth::Ether>,
+    /// Relative slippage overrides for tokens in a volatility tier.
+    pub tiers: HashMap<eth::TokenAddress, BigRational>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -85,6 +92,129 @@ pub struct ManageNativeToken {
     pub insert_unwraps: bool,
 }
 
+/// Penalty subtracted from the score of settlements which touch more distinct
+/// tokens than the configured threshold. Settlements involving many tokens
+/// are more likely to revert and use more gas, so this nudges the selection
+/// towards simpler settlements when scores are otherwise close.
+#[derive(Clone, Copy, Debug)]
+pub struct DistinctTokenPenalty {
+    /// Number of distinct tokens a settlement can touch without penalty.
+    pub threshold: usize,
+    /// Penalty for every distinct token above the threshold.
+    pub per_token: eth::Ether,
+}
+
+impl DistinctTokenPenalty {
+    /// Returns the score reduced by the penalty for a settlement touching the
+    /// given number of distinct tokens. Never goes below zero.
+    pub fn apply(&self, score: eth::Ether, tokens: usize) -> eth::Ether {
+        let excess = tokens.saturating_sub(self.threshold);
+        let penalty = self.per_token.0.saturating_mul(excess.into());
+        eth::Ether(score.0.saturating_sub(penalty))
+    }
+}
+
+/// Relative safety buffers added to simulated gas estimates. The state may
+/// change between simulating and executing a settlement, so the raw simulation
+/// can under-estimate the gas actually used. The buffer depends on the kind of
+/// interactions a settlement executes.
+#[derive(Clone, Copy, Debug)]
+pub struct GasBuffer {
+    /// Buffer for settlements executing custom interactions.
+    pub custom: f64,
+    /// Buffer for settlements executing only interactions with indexed
+    /// liquidity, or no interactions at all.
+    pub liquidity: f64,
+}
+
+impl GasBuffer {
+    /// Returns the gas estimate increased by the largest buffer applying to
+    /// any of the interactions.
+    pub fn apply(&self, estimate: eth::Gas, interactions: &[solution::Interaction]) -> eth::Gas {
+        let buffer = interactions
+            .iter()
+            .map(|interaction| match interaction {
+                solution::Interaction::Custom(_) => self.custom,
+                solution::Interaction::Liquidity(_) => self.liquidity,
+            })
+            .reduce(f64::max)
+            .unwrap_or(self.liquidity);
+        eth::U256::from_f64_lossy(estimate.0.to_f64_lossy() * (1. + buffer)).into()
+    }
+}
+
+/// Fixed gas estimates for custom interactions calling into contracts whose
+/// gas usage is too volatile for a single simulation to be representative,
+/// e.g. because they loop over frequently changing state.
+#[derive(Clone, Debug, Default)]
+pub struct GasOverrides(pub HashMap<eth::ContractAddress, eth::Gas>);
+
+impl GasOverrides {
+    /// Returns the gas estimate raised to at least the sum of the fixed
+    /// estimates of all executed interactions with an overridden target.
+    pub fn apply<'a>(
+        &self,
+        estimate: eth::Gas,
+        interactions: impl IntoIterator<Item = &'a solution::Interaction>,
+    ) -> eth::Gas {
+        let fixed = interactions
+            .into_iter()
+            .filter_map(|interaction| match interaction {
+                solution::Interaction::Custom(custom) => self.0.get(&custom.target).copied(),
+                solution::Interaction::Liquidity(_) => None,
+            })
+            .fold(eth::Gas::default(), |total, gas| total + gas);
+        estimate.max(fixed)
+    }
+}
+
+/// HTTP headers added to every request sent to a solver, e.g. for
+/// authentication. The values commonly contain API keys, so they are redacted
+/// from debug output.
+#[derive(Clone, Default)]
+pub struct RequestHeaders(pub HashMap<String, String>);
+
+impl RequestHeaders {
+    /// The headers as sent by the HTTP client. Values are marked as sensitive
+    /// so the client doesn't log them either.
+    fn to_header_map(&self) -> Result<reqwest::header::HeaderMap> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (key, val) in self.0.iter() {
+            let header_name = HeaderName::try_from(key)?;
+            let mut header_value = reqwest::header::HeaderValue::try_from(val)?;
+            header_value.set_sensitive(true);
+            headers.insert(header_name, header_value);
+        }
+        Ok(headers)
+    }
+}
+
+impl std::fmt::Debug for RequestHeaders {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.0.keys().map(|key| (key, "REDACTED")))
+            .finish()
+    }
+}
+
+/// Bearer token authorizing requests to the `/pause` and `/resume` endpoints
+/// of a solver.
+#[derive(Clone)]
+pub struct ControlToken(pub String);
+
+impl ControlToken {
+    /// Whether the `Authorization` header value carries this token.
+    pub fn authorizes(&self, authorization: &str) -> bool {
+        authorization.strip_prefix("Bearer ") == Some(self.0.as_str())
+    }
+}
+
+impl std::fmt::Debug for ControlToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("REDACTED")
+    }
+}
+
 /// Solvers are controlled by the driver. Their job is to search for solutions
 /// to auctions. They do this in various ways, often by analyzing different AMMs
 /// on the Ethereum blockchain.
@@ -94,6 +224,8 @@ pub struct Solver {
     config: Config,
     eth: Ethereum,
     persistence: Persistence,
+    webhook: Option<webhook::Webhook>,
+    co_signer: Option<cosigner::CoSigner>,
 }
 
 #[derive(Debug, Clone)]
@@ -110,13 +242,16 @@ pub struct Config {
     /// How much time to spend for each step of the solving and competition.
     pub timeouts: Timeouts,
     /// HTTP headers that should be added to every request.
-    pub request_headers: HashMap<String, String>,
+    pub request_headers: RequestHeaders,
     /// Determines whether the `solver` or the `driver` handles the fees
     pub fee_handler: FeeHandler,
     /// Use limit orders for quoting
     /// TODO: Remove once all solvers are moved to use limit orders for quoting
     pub quote_using_limit_orders: bool,
     pub merge_solutions: SolutionMerging,
+    /// Share of the time remaining until the deadline which may be spent
+    /// merging solutions.
+    pub merge_time_share: Option<util::Percent>,
     /// S3 configuration for storing the auctions in the form they are sent to
     /// the solver engine
     pub s3: Option<S3>,
@@ -124,6 +259,75 @@ pub struct Config {
     pub solver_native_token: ManageNativeToken,
     /// Which `tx.origin` is required to make quote verification pass.
     pub quote_tx_origin: Option<eth::Address>,
+    /// Score penalty for settlements touching many distinct tokens.
+    pub distinct_token_penalty: DistinctTokenPenalty,
+    /// Safety buffers added to simulated gas estimates.
+    pub gas_buffer: GasBuffer,
+    /// Minimum score (in wei) per unit of gas a settlement has to generate.
+    pub min_surplus_per_gas: Option<eth::U256>,
+    /// Where to report the results of every competition to.
+    pub results_webhook: Option<url::Url>,
+    /// Maximum number of JIT orders a single solution may settle.
+    pub max_jit_orders: Option<usize>,
+    /// Pay for settlement gas in an ERC20 token instead of native ETH.
+    pub gas_payment: Option<GasPayment>,
+    /// Orders with a sell amount worth less than this are not sent to the
+    /// solver.
+    pub min_order_value: Option<eth::Ether>,
+    /// Solutions involving any of these addresses are not settled.
+    pub blocked_addresses: HashSet<eth::Address>,
+    /// Maximum factor by which clearing prices may deviate from the reference
+    /// prices.
+    pub max_price_deviation: Option<f64>,
+    /// Maximum factor by which the limit prices of orders may exceed the
+    /// reference prices for the orders to be sent to the solver.
+    pub max_limit_price_deviation: Option<f64>,
+    /// Orders and liquidity involving any of these tokens are not sent to the
+    /// solver.
+    pub denied_tokens: HashSet<eth::TokenAddress>,
+    /// Settle through a smart contract account approved by a co-signer.
+    pub co_signer: Option<cosigner::Config>,
+    /// How much is approved for interactions with indexed liquidity.
+    pub approval_mode: eth::allowance::ApprovalMode,
+    /// Tokens whose allowances need to be reset to zero before approving a
+    /// new amount.
+    pub reset_approval_tokens: HashSet<eth::TokenAddress>,
+    /// What to do with a settlement which is still pending when a new auction
+    /// is solved.
+    pub pending_settlement: PendingSettlement,
+    /// Auctions older than this are not solved.
+    pub max_auction_age: Option<std::time::Duration>,
+    /// Revoke the approvals granted by a settlement after its core
+    /// interactions.
+    pub revoke_approvals: bool,
+    /// Reject solutions internalizing more of a token than is buffered.
+    pub reject_buffer_overdraws: bool,
+    /// Score the solver's settlements without ever proposing them.
+    pub shadow: bool,
+    /// Orders expiring within this period are flagged on their settlement.
+    pub expiry_grace_period: Option<std::time::Duration>,
+    /// Maximum size in bytes of a response of the solver.
+    pub response_size_limit: usize,
+    /// Token required to pause and resume the solver at runtime. The
+    /// endpoints are disabled if `None`.
+    pub control_token: Option<ControlToken>,
+    /// Solver engine whose solutions are used when this solver returns none.
+    pub fallback_endpoint: Option<url::Url>,
+    /// Contract asserting that the settlement contract's balances don't
+    /// decrease by more than each settlement pays out.
+    pub balance_checker: Option<eth::ContractAddress>,
+    /// Fixed gas estimates for interactions with volatile gas usage, used
+    /// instead of the simulated gas when scoring.
+    pub gas_overrides: GasOverrides,
+}
+
+/// Settlements pay for their own gas by transferring an ERC20 token from the
+/// settlement contract to the relayer submitting them, e.g. for account
+/// abstraction setups where the solver doesn't hold native ETH.
+#[derive(Clone, Copy, Debug)]
+pub struct GasPayment {
+    pub token: eth::TokenAddress,
+    pub relayer: eth::Address,
 }
 
 impl Solver {
@@ -134,13 +338,11 @@ impl Solver {
             "application/json".parse().unwrap(),
         );
         headers.insert(reqwest::header::ACCEPT, "application/json".parse().unwrap());
-
-        for (key, val) in config.request_headers.iter() {
-            let header_name = HeaderName::try_from(key)?;
-            headers.insert(header_name, val.parse()?);
-        }
+        headers.extend(config.request_headers.to_header_map()?);
 
         let persistence = Persistence::build(&config).await;
+        let webhook = config.results_webhook.clone().map(webhook::Webhook::new);
+        let co_signer = config.co_signer.clone().map(cosigner::CoSigner::new);
 
         Ok(Self {
             client: reqwest::ClientBuilder::new()
@@ -149,6 +351,8 @@ impl Solver {
             config,
             eth,
             persistence,
+            webhook,
+            co_signer,
         })
     }
 
@@ -160,6 +364,14 @@ impl Solver {
         &self.config.name
     }
 
+    pub fn endpoint(&self) -> &url::Url {
+        &self.config.endpoint
+    }
+
+    pub fn request_headers(&self) -> &RequestHeaders {
+        &self.config.request_headers
+    }
+
     /// The slippage configuration of this solver.
     pub fn slippage(&self) -> &Slippage {
         &self.config.slippage
@@ -202,6 +414,111 @@ impl Solver {
         &self.config.quote_tx_origin
     }
 
+    pub fn distinct_token_penalty(&self) -> DistinctTokenPenalty {
+        self.config.distinct_token_penalty
+    }
+
+    pub fn gas_buffer(&self) -> GasBuffer {
+        self.config.gas_buffer
+    }
+
+    pub fn min_surplus_per_gas(&self) -> Option<eth::U256> {
+        self.config.min_surplus_per_gas
+    }
+
+    pub fn max_jit_orders(&self) -> Option<usize> {
+        self.config.max_jit_orders
+    }
+
+    pub fn merge_time_share(&self) -> Option<util::Percent> {
+        self.config.merge_time_share
+    }
+
+    pub fn control_token(&self) -> Option<&ControlToken> {
+        self.config.control_token.as_ref()
+    }
+
+    pub fn gas_payment(&self) -> Option<GasPayment> {
+        self.config.gas_payment
+    }
+
+    /// Whether solutions involving the given address must not be settled, e.g.
+    /// because it is sanctioned.
+    pub fn is_blocked(&self, address: &eth::Address) -> bool {
+        self.config.blocked_addresses.contains(address)
+    }
+
+    pub fn blocked_addresses(&self) -> &HashSet<eth::Address> {
+        &self.config.blocked_addresses
+    }
+
+    /// The co-signer which has to approve settlements before they can be
+    /// submitted, if any.
+    pub fn co_signer(&self) -> Option<&cosigner::CoSigner> {
+        self.co_signer.as_ref()
+    }
+
+    pub fn max_price_deviation(&self) -> Option<f64> {
+        self.config.max_price_deviation
+    }
+
+    pub fn max_limit_price_deviation(&self) -> Option<f64> {
+        self.config.max_limit_price_deviation
+    }
+
+    pub fn denied_tokens(&self) -> &HashSet<eth::TokenAddress> {
+        &self.config.denied_tokens
+    }
+
+    pub fn approval_mode(&self) -> eth::allowance::ApprovalMode {
+        self.config.approval_mode
+    }
+
+    pub fn reset_approval_tokens(&self) -> &HashSet<eth::TokenAddress> {
+        &self.config.reset_approval_tokens
+    }
+
+    pub fn pending_settlement(&self) -> PendingSettlement {
+        self.config.pending_settlement
+    }
+
+    pub fn max_auction_age(&self) -> Option<std::time::Duration> {
+        self.config.max_auction_age
+    }
+
+    pub fn revoke_approvals(&self) -> bool {
+        self.config.revoke_approvals
+    }
+
+    pub fn reject_buffer_overdraws(&self) -> bool {
+        self.config.reject_buffer_overdraws
+    }
+
+    pub fn balance_checker(&self) -> Option<eth::ContractAddress> {
+        self.config.balance_checker
+    }
+
+    pub fn gas_overrides(&self) -> &GasOverrides {
+        &self.config.gas_overrides
+    }
+
+    /// Whether the solver's settlements are only scored and logged but never
+    /// proposed to the protocol.
+    pub fn shadow(&self) -> bool {
+        self.config.shadow
+    }
+
+    pub fn expiry_grace_period(&self) -> Option<std::time::Duration> {
+        self.config.expiry_grace_period
+    }
+
+    /// Report the results of a competition to the configured webhook, if any.
+    pub fn report_results(&self, results: &competition::Results) {
+        if let Some(webhook) = &self.webhook {
+            webhook.report(self.name(), results);
+        }
+    }
+
     /// Make a POST request instructing the solver to solve an auction.
     /// Allocates at most `timeout` time for the solving.
     pub async fn solve(
@@ -217,6 +534,7 @@ impl Solver {
             weth,
             self.config.fee_handler,
             self.config.solver_native_token,
+            self.config.min_order_value,
         );
         // Only auctions with IDs are real auctions (/quote requests don't have an ID,
         // and it makes no sense to store them)
@@ -224,25 +542,94 @@ impl Solver {
             self.persistence.archive_auction(id, &auction_dto);
         };
         let body = serde_json::to_string(&auction_dto).unwrap();
-        let url = shared::url::join(&self.config.endpoint, "solve");
-        super::observe::solver_request(&url, &body);
+        let primary = self.request_solutions(&self.config.endpoint, &body, auction, liquidity);
+        let solutions = match &self.config.fallback_endpoint {
+            Some(fallback) => {
+                let fallback = self.request_solutions(fallback, &body, auction, liquidity);
+                self.with_fallback(primary, fallback).await?
+            }
+            None => primary.await?,
+        };
+
+        super::observe::solutions(&solutions, auction.surplus_capturing_jit_order_owners());
+        Ok(solutions)
+    }
+
+    /// Falls back to a simpler solver engine instead of giving up on the
+    /// auction if the primary engine returns no solutions. Both engines are
+    /// asked at the same time so that each of them gets the whole solving
+    /// time. Failures of the fallback don't fail the solve.
+    async fn with_fallback(
+        &self,
+        primary: impl Future<Output = Result<Vec<Solution>, Error>>,
+        fallback: impl Future<Output = Result<Vec<Solution>, Error>>,
+    ) -> Result<Vec<Solution>, Error> {
+        let (primary, fallback) = (std::pin::pin!(primary), std::pin::pin!(fallback));
+        let (solutions, fallback) = match future::select(primary, fallback).await {
+            Either::Left((solutions, fallback)) => {
+                let solutions = solutions?;
+                if !solutions.is_empty() {
+                    // Don't wait for solutions which won't be used anyway.
+                    return Ok(solutions);
+                }
+                (solutions, fallback.await)
+            }
+            Either::Right((fallback, primary)) => (primary.await?, fallback),
+        };
+        if !solutions.is_empty() {
+            return Ok(solutions);
+        }
+        match fallback {
+            Ok(fallback_solutions) => {
+                super::observe::fallback_solutions(self.name(), fallback_solutions.len());
+                Ok(fallback_solutions)
+            }
+            Err(err) => {
+                super::observe::fallback_failed(self.name(), &err);
+                Ok(solutions)
+            }
+        }
+    }
+
+    /// Requests solutions for the serialized auction from the solver engine
+    /// behind the given endpoint.
+    async fn request_solutions(
+        &self,
+        endpoint: &url::Url,
+        body: &str,
+        auction: &Auction,
+        liquidity: &[liquidity::Liquidity],
+    ) -> Result<Vec<Solution>, Error> {
+        let weth = self.eth.contracts().weth_address();
+        let url = shared::url::join(endpoint, "solve");
+        super::observe::solver_request(&url, body);
         let mut req = self
             .client
             .post(url.clone())
-            .body(body)
+            .body(body.to_owned())
             .timeout(auction.deadline().solvers().remaining().unwrap_or_default());
         if let Some(id) = observe::request_id::get_task_local_storage() {
             req = req.header("X-REQUEST-ID", id);
         }
-        let res = util::http::send(SOLVER_RESPONSE_MAX_BYTES, req).await;
+        let res = util::http::send(self.config.response_size_limit, req).await;
         super::observe::solver_response(&url, res.as_deref());
         let res = res?;
         let res: dto::Solutions = serde_json::from_str(&res)
             .tap_err(|err| tracing::warn!(res, ?err, "failed to parse solver response"))?;
-        let solutions = res.into_domain(auction, liquidity, weth, self.clone(), &self.config)?;
+        Ok(res.into_domain(auction, liquidity, weth, self.clone(), &self.config)?)
+    }
 
-        super::observe::solutions(&solutions, auction.surplus_capturing_jit_order_owners());
-        Ok(solutions)
+    /// Converts solutions in the JSON format returned by solver engines into
+    /// solutions of this solver, without making any requests.
+    pub fn solutions_from_json(
+        &self,
+        value: serde_json::Value,
+        auction: &Auction,
+        liquidity: &[liquidity::Liquidity],
+    ) -> Result<Vec<Solution>, Error> {
+        let weth = self.eth.contracts().weth_address();
+        let solutions: dto::Solutions = serde_json::from_value(value)?;
+        Ok(solutions.into_domain(auction, liquidity, weth, self.clone(), &self.config)?)
     }
 
     /// Make a fire and forget POST request to notify the solver about an event.
@@ -260,8 +647,9 @@ impl Solver {
         if let Some(id) = observe::request_id::get_task_local_storage() {
             req = req.header("X-REQUEST-ID", id);
         }
+        let limit = self.config.response_size_limit;
         let future = async move {
-            if let Err(error) = util::http::send(SOLVER_RESPONSE_MAX_BYTES, req).await {
+            if let Err(error) = util::http::send(limit, req).await {
                 tracing::warn!(?error, "failed to notify solver");
             }
         };
@@ -277,6 +665,16 @@ pub enum SolutionMerging {
     Forbidden,
 }
 
+/// Controls whether a settlement which hasn't been settled yet gets replaced
+/// by the best settlement of the next auction.
+#[derive(Debug, Clone, Copy)]
+pub enum PendingSettlement {
+    /// Always store the settlement of the most recent auction.
+    Replace,
+    /// Keep the pending settlement if it scores better than the new one.
+    KeepBetter,
+}
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("HTTP error: {0:?}")]
@@ -295,3 +693,124 @@ impl Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_headers() {
+        let headers = RequestHeaders(HashMap::from([
+            ("Authorization".to_owned(), "Bearer secret-token".to_owned()),
+            ("X-Route".to_owned(), "eu".to_owned()),
+        ]));
+
+        let map = headers.to_header_map().unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["authorization"], "Bearer secret-token");
+        assert_eq!(map["x-route"], "eu");
+        assert!(map.values().all(|value| value.is_sensitive()));
+
+        let debug = format!("{headers:?}");
+        assert!(debug.contains("Authorization"));
+        assert!(!debug.contains("secret-token"));
+
+        let invalid = RequestHeaders(HashMap::from([("in valid".to_owned(), "x".to_owned())]));
+        assert!(invalid.to_header_map().is_err());
+    }
+
+    #[test]
+    fn gas_buffer() {
+        let buffer = GasBuffer {
+            custom: 0.2,
+            liquidity: 0.05,
+        };
+        let estimate = eth::Gas(100_000.into());
+        let custom = solution::Interaction::Custom(solution::interaction::Custom {
+            target: eth::H160::zero().into(),
+            value: eth::U256::zero().into(),
+            call_data: Default::default(),
+            allowances: Default::default(),
+            inputs: Default::default(),
+            outputs: Default::default(),
+            internalize: false,
+        });
+
+        // Settlements without custom interactions get the liquidity buffer.
+        assert_eq!(buffer.apply(estimate, &[]), eth::Gas(105_000.into()));
+        // Custom interactions get the larger buffer.
+        assert_eq!(
+            buffer.apply(estimate, &[custom.clone()]),
+            eth::Gas(120_000.into())
+        );
+        // The largest buffer wins.
+        let buffer = GasBuffer {
+            custom: 0.1,
+            liquidity: 0.1,
+        };
+        assert_eq!(buffer.apply(estimate, &[custom]), eth::Gas(110_000.into()));
+    }
+
+    #[test]
+    fn gas_overrides() {
+        let volatile = eth::H160([1; 20]);
+        let overrides = GasOverrides(HashMap::from([(volatile.into(), eth::Gas(500_000.into()))]));
+        let custom = |target: eth::H160, internalize| {
+            solution::Interaction::Custom(solution::interaction::Custom {
+                target: target.into(),
+                value: eth::U256::zero().into(),
+                call_data: Default::default(),
+                allowances: Default::default(),
+                inputs: Default::default(),
+                outputs: Default::default(),
+                internalize,
+            })
+        };
+        let estimate = eth::Gas(200_000.into());
+
+        // Interactions without an override keep the simulated estimate.
+        assert_eq!(
+            overrides.apply(estimate, &[custom(eth::H160([2; 20]), false)]),
+            estimate
+        );
+        // An overridden target uses its fixed estimate instead.
+        assert_eq!(
+            overrides.apply(estimate, &[custom(volatile, false)]),
+            eth::Gas(500_000.into())
+        );
+        // Fixed estimates of several calls add up.
+        assert_eq!(
+            overrides.apply(estimate, &[custom(volatile, false), custom(volatile, true)]),
+            eth::Gas(1_000_000.into())
+        );
+        // Simulations exceeding the fixed estimate are kept.
+        let estimate = eth::Gas(2_000_000.into());
+        assert_eq!(
+            overrides.apply(estimate, &[custom(volatile, false)]),
+            estimate
+        );
+    }
+
+    #[test]
+    fn distinct_token_penalty() {
+        let penalty = DistinctTokenPenalty {
+            threshold: 2,
+            per_token: eth::Ether(100.into()),
+        };
+        let score = eth::Ether(1_000.into());
+
+        // Settlements within the threshold are not penalized.
+        assert_eq!(penalty.apply(score, 2), score);
+        // Each token above the threshold reduces the score.
+        assert_eq!(penalty.apply(score, 5), eth::Ether(700.into()));
+        assert!(penalty.apply(score, 5) < penalty.apply(score, 2));
+        // The penalty never makes the score negative.
+        assert_eq!(penalty.apply(score, 100), eth::Ether(0.into()));
+
+        let disabled = DistinctTokenPenalty {
+            threshold: 0,
+            per_token: eth::Ether(0.into()),
+        };
+        assert_eq!(disabled.apply(score, 10), score);
+    }
+}
@@ -27,6 +27,12 @@ pub struct Metadata {
 #[derive(Clone)]
 pub struct Fetcher(Arc<Inner>);
 
+impl std::fmt::Debug for Fetcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Fetcher").finish_non_exhaustive()
+    }
+}
+
 impl Fetcher {
     pub fn new(eth: &Ethereum) -> Self {
         let eth = eth.with_metric_label("tokenInfos".into());
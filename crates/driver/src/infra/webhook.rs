@@ -0,0 +1,171 @@
+//! Reports the outcome of every competition to an external HTTP endpoint, e.g.
+//! for monitoring. Reporting is fire-and-forget and never delays the
+//! competition.
+
+use {
+    crate::{
+        domain::{
+            competition::{self, solution},
+            eth,
+        },
+        infra::solver,
+        util::{self, serialize},
+    },
+    serde::Serialize,
+    serde_with::serde_as,
+    std::time::Duration,
+    tracing::Instrument,
+};
+
+/// How long to wait for the webhook to respond to a single request.
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Responses of the webhook are ignored, so don't bother reading large ones.
+const RESPONSE_MAX_BYTES: usize = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    client: reqwest::Client,
+    url: url::Url,
+}
+
+impl Webhook {
+    pub fn new(url: url::Url) -> Self {
+        Self {
+            client: reqwest::Client::builder().timeout(TIMEOUT).build().unwrap(),
+            url,
+        }
+    }
+
+    /// POST the competition results to the webhook in the background. Failed
+    /// requests are retried once.
+    pub fn report(&self, solver: &solver::Name, results: &competition::Results) {
+        let body = serde_json::to_string(&Results::new(solver, results)).unwrap();
+        let client = self.client.clone();
+        let url = self.url.clone();
+        let future = async move {
+            for attempt in 1..=2 {
+                let req = client
+                    .post(url.clone())
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(body.clone());
+                match util::http::send(RESPONSE_MAX_BYTES, req).await {
+                    Ok(_) => return,
+                    Err(error) => {
+                        tracing::warn!(?error, attempt, "failed to report competition results")
+                    }
+                }
+            }
+        };
+        tokio::task::spawn(future.in_current_span());
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Results {
+    auction_id: Option<String>,
+    solver: String,
+    participants: Vec<Participant>,
+    winner: Option<Vec<u64>>,
+}
+
+impl Results {
+    fn new(solver: &solver::Name, results: &competition::Results) -> Self {
+        Self {
+            auction_id: results.auction_id.as_ref().map(ToString::to_string),
+            solver: solver.to_string(),
+            participants: results
+                .participants
+                .iter()
+                .map(|(id, score)| Participant::new(id, *score))
+                .collect(),
+            winner: results.winner.as_ref().map(solution::Id::ids),
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Participant {
+    solution_ids: Vec<u64>,
+    #[serde_as(as = "serialize::U256")]
+    score: eth::U256,
+}
+
+impl Participant {
+    fn new(id: &solution::Id, score: eth::Ether) -> Self {
+        Self {
+            solution_ids: id.ids(),
+            score: score.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        serde_json::json,
+        std::sync::{Arc, Mutex},
+    };
+
+    #[tokio::test]
+    async fn retries_once_and_posts_results() {
+        // Fails the first request and records the bodies of all requests.
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let app = axum::Router::new().route(
+            "/results",
+            axum::routing::post({
+                let requests = requests.clone();
+                move |axum::extract::Json(body): axum::extract::Json<serde_json::Value>| async move {
+                    let mut requests = requests.lock().unwrap();
+                    requests.push(body);
+                    if requests.len() == 1 {
+                        hyper::StatusCode::INTERNAL_SERVER_ERROR
+                    } else {
+                        hyper::StatusCode::OK
+                    }
+                }
+            }),
+        );
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(async move { server.await.unwrap() });
+
+        let webhook = Webhook::new(format!("http://{addr}/results").parse().unwrap());
+        webhook.report(
+            &"solver".to_owned().into(),
+            &competition::Results {
+                auction_id: Some(competition::auction::Id(7)),
+                participants: vec![
+                    (solution::Id::Single(1), eth::U256::from(100).into()),
+                    (
+                        solution::Id::Merged(vec![2, 3]),
+                        eth::U256::from(200).into(),
+                    ),
+                ],
+                winner: Some(solution::Id::Merged(vec![2, 3])),
+            },
+        );
+
+        let expected = json!({
+            "auctionId": "7",
+            "solver": "solver",
+            "participants": [
+                { "solutionIds": [1], "score": "100" },
+                { "solutionIds": [2, 3], "score": "200" },
+            ],
+            "winner": [2, 3],
+        });
+        for _ in 0..50 {
+            if requests.lock().unwrap().len() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(*requests.lock().unwrap(), vec![expected.clone(), expected]);
+    }
+}
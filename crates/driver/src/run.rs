@@ -42,7 +42,7 @@ pub async fn run(
 /// Run the driver. This function exists to avoid multiple monomorphizations of
 /// the `run` code, which bloats the binaries and increases compile times.
 async fn run_with(args: cli::Args, addr_sender: Option<oneshot::Sender<SocketAddr>>) {
-    crate::infra::observe::init(&args.log);
+    crate::infra::observe::init(&args.log, args.enable_task_console);
 
     let ethrpc = ethrpc(&args).await;
     let web3 = ethrpc.web3().clone();
@@ -117,6 +117,15 @@ fn simulator(config: &infra::Config, eth: &Ethereum) -> Simulator {
     if let Some(gas) = config.disable_gas_simulation {
         simulator.disable_gas(gas)
     }
+    if let Some(offset) = config.simulation_inclusion_offset {
+        simulator.inclusion_offset(offset)
+    }
+    if config.trace_failed_simulations {
+        simulator.trace_reverts()
+    }
+    if config.isolate_simulation_reverts {
+        simulator.isolate_reverts()
+    }
     simulator
 }
 
@@ -0,0 +1,65 @@
+use crate::tests::{
+    self,
+    setup::{ab_order, ab_pool, ab_solution},
+};
+
+/// Checks that the auction passed to /solve can be fetched back from the
+/// /auction endpoint.
+#[tokio::test]
+#[ignore]
+async fn latest_auction() {
+    let test = tests::setup()
+        .name("latest auction")
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .done()
+        .await;
+
+    test.solve().await.ok();
+
+    let auction = test.auction().await;
+    assert_eq!(auction.get("id").unwrap().as_str().unwrap(), "1");
+    assert_eq!(auction.get("orders").unwrap().as_u64().unwrap(), 1);
+    assert!(auction.get("deadline").unwrap().is_string());
+}
+
+/// Checks that the /report endpoint summarizes what happened while solving
+/// the auction.
+#[tokio::test]
+#[ignore]
+async fn solve_report() {
+    let test = tests::setup()
+        .name("solve report")
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .done()
+        .await;
+
+    let score = test.solve().await.ok().score();
+
+    let report = test.report().await;
+    assert_eq!(report.get("auctionId").unwrap().as_str().unwrap(), "1");
+    assert_eq!(report.get("orders").unwrap().as_u64().unwrap(), 1);
+    assert_eq!(report.get("solvedOrders").unwrap().as_u64().unwrap(), 1);
+    assert_eq!(report.get("solutions").unwrap().as_u64().unwrap(), 1);
+    assert!(report
+        .get("skippedOrders")
+        .unwrap()
+        .as_object()
+        .unwrap()
+        .is_empty());
+    assert!(report
+        .get("skippedSolutions")
+        .unwrap()
+        .as_object()
+        .unwrap()
+        .is_empty());
+    assert_eq!(report.get("merges").unwrap().as_u64().unwrap(), 0);
+    assert_eq!(
+        report.get("winnerScore").unwrap().as_str().unwrap(),
+        score.to_string()
+    );
+    assert!(report.get("durationMs").unwrap().is_u64());
+}
@@ -0,0 +1,41 @@
+use {
+    crate::tests::{
+        setup,
+        setup::{ab_order, ab_pool, ab_solution, test_solver},
+    },
+    std::time::Duration,
+};
+
+/// Test that an auction which was created longer ago than the solver's
+/// maximum auction age is rejected without being solved.
+#[tokio::test]
+#[ignore]
+async fn stale_auction_is_rejected() {
+    let test = setup()
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .auction_age(chrono::Duration::seconds(10))
+        .solvers(vec![test_solver().max_auction_age(Duration::from_secs(5))])
+        .done()
+        .await;
+
+    test.solve().await.err().kind("AuctionTooOld");
+}
+
+/// Test that an auction younger than the solver's maximum auction age is
+/// solved as usual.
+#[tokio::test]
+#[ignore]
+async fn fresh_auction_is_solved() {
+    let test = setup()
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .auction_age(chrono::Duration::seconds(1))
+        .solvers(vec![test_solver().max_auction_age(Duration::from_secs(5))])
+        .done()
+        .await;
+
+    test.solve().await.ok().orders(&[ab_order()]);
+}
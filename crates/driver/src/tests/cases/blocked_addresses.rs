@@ -0,0 +1,40 @@
+use crate::{
+    domain::eth,
+    tests::{
+        setup,
+        setup::{ab_order, ab_pool, ab_solution, test_solver},
+    },
+};
+
+/// Test that solutions paying out to a blocked receiver are not settled.
+#[tokio::test]
+#[ignore]
+async fn blocked_receiver() {
+    let receiver = eth::H160::from_low_u64_be(0xb10c);
+    let test = setup()
+        .pool(ab_pool())
+        .order(ab_order().receiver(Some(receiver)))
+        .solution(ab_solution())
+        .solvers(vec![test_solver().blocked_addresses(vec![receiver])])
+        .done()
+        .await;
+
+    test.solve().await.ok().empty();
+}
+
+/// Test that blocking an address doesn't affect solutions not involving it.
+#[tokio::test]
+#[ignore]
+async fn unrelated_blocked_address() {
+    let test = setup()
+        .pool(ab_pool())
+        .order(ab_order().receiver(Some(eth::H160::from_low_u64_be(0x600d))))
+        .solution(ab_solution())
+        .solvers(vec![
+            test_solver().blocked_addresses(vec![eth::H160::from_low_u64_be(0xb10c)])
+        ])
+        .done()
+        .await;
+
+    test.solve().await.ok().orders(&[ab_order()]);
+}
@@ -0,0 +1,30 @@
+use crate::tests::{setup, setup::test_solver};
+
+/// Test that the /config endpoint lists the configured solvers without
+/// leaking their secrets.
+#[tokio::test]
+#[ignore]
+async fn lists_solvers_and_redacts_secrets() {
+    let test = setup()
+        .solvers(vec![
+            test_solver().control_token("secret"),
+            test_solver().name("other"),
+        ])
+        .done()
+        .await;
+
+    let config = test.config().await;
+
+    let solvers = config.get("solvers").unwrap().as_array().unwrap();
+    let names = solvers
+        .iter()
+        .map(|solver| solver.get("name").unwrap().as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["test-solver", "other"]);
+    assert_eq!(
+        solvers[0].get("controlToken").unwrap().as_str().unwrap(),
+        "REDACTED"
+    );
+    assert!(solvers[1].get("controlToken").unwrap().is_null());
+    assert!(!config.to_string().contains("secret"));
+}
@@ -0,0 +1,35 @@
+use crate::tests::{
+    setup,
+    setup::{ab_order, ab_pool, ab_solution, cd_order, cd_pool, test_solver},
+};
+
+/// Test that orders trading a denied token are not sent to the solver while
+/// the other orders of the auction are still solved.
+#[tokio::test]
+#[ignore]
+async fn denied_token_order_is_dropped() {
+    let test = setup()
+        .pool(ab_pool())
+        .pool(cd_pool())
+        .order(ab_order())
+        .order(cd_order().filtered())
+        .solution(ab_solution())
+        .solvers(vec![test_solver().denied_tokens(vec!["C"])])
+        .done()
+        .await;
+
+    test.solve().await.ok().orders(&[ab_order()]);
+
+    let report = test.report().await;
+    assert_eq!(report.get("orders").unwrap().as_u64().unwrap(), 1);
+    assert_eq!(
+        report
+            .get("skippedOrders")
+            .unwrap()
+            .get("TokenDenied")
+            .unwrap()
+            .as_u64()
+            .unwrap(),
+        1
+    );
+}
@@ -0,0 +1,112 @@
+use {
+    crate::{
+        domain::{
+            competition::{self, order},
+            eth,
+            time,
+        },
+        infra,
+        tests::{hex_address, setup},
+        util,
+    },
+    serde_json::json,
+};
+
+/// Test that solutions in the format returned by solver engines are parsed
+/// into solutions for an auction without calling the solver.
+#[tokio::test]
+#[ignore]
+async fn parses_engine_solutions() {
+    let test = setup().done().await;
+    let eth = test.ethereum().await;
+    let solver = test.solver(&eth).await;
+
+    let sell = eth::TokenAddress::from(eth::H160::from_low_u64_be(0x5e11));
+    let buy = eth::TokenAddress::from(eth::H160::from_low_u64_be(0xb0b));
+    let uid = order::Uid::from([0x2a; order::UID_LEN]);
+    let order = competition::Order {
+        uid,
+        receiver: None,
+        valid_to: util::Timestamp(u32::MAX),
+        buy: eth::Asset {
+            token: buy,
+            amount: eth::U256::from(90).into(),
+        },
+        sell: eth::Asset {
+            token: sell,
+            amount: eth::U256::from(100).into(),
+        },
+        side: order::Side::Sell,
+        kind: order::Kind::Market,
+        app_data: Default::default(),
+        partial: order::Partial::No,
+        pre_interactions: Default::default(),
+        post_interactions: Default::default(),
+        sell_token_balance: order::SellTokenBalance::Erc20,
+        buy_token_balance: order::BuyTokenBalance::Erc20,
+        signature: order::Signature {
+            scheme: order::signature::Scheme::PreSign,
+            data: Default::default(),
+            signer: Default::default(),
+        },
+        protocol_fees: Default::default(),
+    };
+    let tokens = [sell, buy].map(|address| competition::auction::Token {
+        decimals: Some(18),
+        symbol: None,
+        address,
+        price: None,
+        available_balance: Default::default(),
+        trusted: false,
+    });
+    let auction = competition::Auction::new(
+        Some(competition::auction::Id(1)),
+        vec![order],
+        tokens.into_iter(),
+        time::Deadline::new(
+            infra::time::now() + chrono::Duration::seconds(10),
+            solver.timeouts(),
+        ),
+        infra::time::now(),
+        &eth,
+        Default::default(),
+    )
+    .await
+    .unwrap();
+
+    let solutions = competition::Solution::from_engine_json(
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    (hex_address(sell.into())): "95",
+                    (hex_address(buy.into())): "100",
+                },
+                "trades": [{
+                    "kind": "fulfillment",
+                    "order": format!("0x{}", hex::encode([0x2a; order::UID_LEN])),
+                    "executedAmount": "100",
+                }],
+                "preInteractions": [],
+                "interactions": [],
+                "postInteractions": [],
+            }]
+        }),
+        &auction,
+        &[],
+        &solver,
+    )
+    .unwrap();
+
+    assert_eq!(solutions.len(), 1);
+    let solution = &solutions[0];
+    assert_eq!(solution.solver().name().0, "test-solver");
+    assert!(matches!(
+        solution.trades(),
+        [competition::solution::Trade::Fulfillment(fulfillment)]
+            if fulfillment.order().uid == uid
+                && fulfillment.executed() == order::TargetAmount(100.into())
+    ));
+    assert_eq!(solution.clearing_price(sell), Some(95.into()));
+    assert_eq!(solution.clearing_price(buy), Some(100.into()));
+}
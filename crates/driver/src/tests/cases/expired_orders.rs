@@ -0,0 +1,23 @@
+use crate::tests::{
+    setup,
+    setup::{ab_order, ab_pool, ab_solution},
+};
+
+/// Test that a settlement is declined if one of its orders expired between
+/// solving and settling.
+#[tokio::test]
+#[ignore]
+async fn order_expired_before_settling() {
+    let valid_to = u32::try_from(chrono::Utc::now().timestamp() + 60 * 60).unwrap();
+    let test = setup()
+        .pool(ab_pool())
+        .order(ab_order().valid_to(valid_to))
+        .solution(ab_solution())
+        .done()
+        .await;
+
+    test.solve().await.ok().orders(&[ab_order()]);
+    test.advance_time(2 * 60 * 60).await;
+
+    test.settle().await.err().kind("OrderExpired");
+}
@@ -0,0 +1,39 @@
+use crate::tests::{
+    self,
+    setup::{ab_order, ab_pool, ab_solution, test_solver},
+};
+
+/// Checks that the solutions of the fallback solver engine are used when the
+/// solver doesn't return any.
+#[tokio::test]
+#[ignore]
+async fn solutions_from_fallback() {
+    let test = tests::setup()
+        .solvers(vec![
+            test_solver().no_solutions().fallback("fallback"),
+            test_solver().name("fallback"),
+        ])
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .done()
+        .await;
+
+    test.solve().await.ok().orders(&[ab_order()]);
+}
+
+/// Checks that without a fallback the solver not returning any solutions
+/// results in no solution.
+#[tokio::test]
+#[ignore]
+async fn no_solution_without_fallback() {
+    let test = tests::setup()
+        .solvers(vec![test_solver().no_solutions()])
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .done()
+        .await;
+
+    test.solve().await.ok().empty();
+}
@@ -12,7 +12,7 @@ use crate::{
 async fn solver_fee() {
     for side in [order::Side::Buy, order::Side::Sell] {
         let order = ab_order()
-            .kind(order::Kind::Limit)
+            .kind(order::Kind::Limit { oracle: None })
             .side(side)
             .solver_fee(Some(500.into()));
         let test = tests::setup()
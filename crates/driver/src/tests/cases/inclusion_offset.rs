@@ -0,0 +1,41 @@
+use {
+    crate::{
+        infra::simulator::{self, InclusionOffset},
+        tests::{
+            setup,
+            setup::{ab_order, ab_pool, ab_solution},
+        },
+    },
+    std::time::Duration,
+};
+
+/// Checks that simulating a settlement as if it was included some blocks
+/// ahead affects time-dependent interactions, here an order expiring before
+/// that block.
+#[tokio::test]
+#[ignore]
+async fn order_expires_before_inclusion() {
+    let valid_to = u32::try_from(chrono::Utc::now().timestamp() + 60).unwrap();
+    let test = setup()
+        .pool(ab_pool())
+        .order(ab_order().valid_to(valid_to))
+        .solution(ab_solution())
+        .done()
+        .await;
+
+    test.solve().await.ok().orders(&[ab_order()]);
+    let calldata = test.reveal().await.ok().uninternalized_calldata();
+
+    // Included in the next block the order is still valid.
+    test.simulate_settlement(calldata.clone(), None)
+        .await
+        .unwrap();
+
+    // 10 blocks later the order expired and the settlement reverts.
+    let offset = InclusionOffset {
+        blocks: 10,
+        block_time: Duration::from_secs(12),
+    };
+    let result = test.simulate_settlement(calldata, Some(offset)).await;
+    assert!(matches!(result, Err(simulator::Error::Revert(_))));
+}
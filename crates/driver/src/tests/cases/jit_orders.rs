@@ -65,7 +65,7 @@ async fn protocol_fee_test_case(test_case: TestCase) {
 
     let jit_order = setup::JitOrder {
         order: ab_order()
-            .kind(order::Kind::Limit)
+            .kind(order::Kind::Limit { oracle: None })
             .sell_amount(test_case.solution.jit_order.order.sell_amount)
             .buy_amount(test_case.solution.jit_order.order.buy_amount)
             .solver_fee(Some(solver_fee))
@@ -74,7 +74,7 @@ async fn protocol_fee_test_case(test_case: TestCase) {
     };
 
     let order = ab_order()
-        .kind(order::Kind::Limit)
+        .kind(order::Kind::Limit { oracle: None })
         .sell_amount(test_case.order.sell_amount)
         .buy_amount(test_case.order.buy_amount)
         .solver_fee(Some(solver_fee))
@@ -181,3 +181,59 @@ async fn surplus_protocol_fee_jit_order_not_capped() {
 
     protocol_fee_test_case(test_case).await;
 }
+
+/// Sets up a test where the solver settles a user order together with a
+/// single JIT order, allowing at most `max_jit_orders` per solution.
+#[cfg(test)]
+async fn max_jit_orders_test(max_jit_orders: usize) -> Test {
+    let quote = ab_liquidity_quote()
+        .sell_amount(30.ether().into_wei())
+        .buy_amount(40.ether().into_wei());
+    let pool = ab_adjusted_pool(quote);
+    let solver_fee = 40.ether().into_wei() / 100;
+
+    let jit_order = setup::JitOrder {
+        order: ab_order()
+            .kind(order::Kind::Limit { oracle: None })
+            .sell_amount(50.ether().into_wei())
+            .buy_amount(40.ether().into_wei())
+            .solver_fee(Some(solver_fee))
+            .side(Side::Buy)
+            .no_surplus(),
+    };
+    let order = ab_order()
+        .kind(order::Kind::Limit { oracle: None })
+        .sell_amount(50.ether().into_wei())
+        .buy_amount(40.ether().into_wei())
+        .solver_fee(Some(solver_fee))
+        .side(Side::Buy)
+        .partial(0.into())
+        .no_surplus();
+
+    tests::setup()
+        .name(format!("max {max_jit_orders} JIT orders"))
+        .pool(pool)
+        .jit_order(jit_order)
+        .order(order)
+        .solution(ab_solution())
+        .solvers(vec![test_solver().max_jit_orders(max_jit_orders)])
+        .done()
+        .await
+}
+
+#[tokio::test]
+#[ignore]
+async fn solution_exceeding_max_jit_orders_is_discarded() {
+    let test = max_jit_orders_test(0).await;
+
+    test.solve().await.ok().empty();
+}
+
+#[tokio::test]
+#[ignore]
+async fn solution_at_max_jit_orders_is_accepted() {
+    let test = max_jit_orders_test(1).await;
+
+    let result = test.solve().await.ok();
+    assert!(!result.score().is_zero());
+}
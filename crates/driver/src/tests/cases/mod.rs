@@ -8,18 +8,31 @@ use {
     std::str::FromStr,
 };
 
+pub mod auction;
+pub mod auction_age;
+pub mod blocked_addresses;
 pub mod buy_eth;
+pub mod config;
+pub mod denied_tokens;
+pub mod engine_solutions;
 pub mod example_config;
+pub mod expired_orders;
+pub mod fallback_solver;
 pub mod fees;
+pub mod inclusion_offset;
 pub mod internalization;
 pub mod jit_orders;
 pub mod merge_settlements;
 pub mod multiple_drivers;
 pub mod multiple_solutions;
 pub mod order_prioritization;
+pub mod pause;
 pub mod protocol_fees;
 pub mod quote;
+pub mod response_size_limit;
 pub mod settle;
+pub mod shadow;
+pub mod simulation_trace;
 pub mod solver_balance;
 
 #[allow(dead_code)]
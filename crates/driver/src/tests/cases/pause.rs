@@ -0,0 +1,42 @@
+use crate::tests::{
+    setup,
+    setup::{ab_order, ab_pool, ab_solution, test_solver},
+};
+
+const TOKEN: &str = "secret";
+
+/// Test that a paused solver doesn't participate in competitions until it is
+/// resumed.
+#[tokio::test]
+#[ignore]
+async fn pause_and_resume() {
+    let test = setup()
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .solvers(vec![test_solver().control_token(TOKEN)])
+        .done()
+        .await;
+
+    assert_eq!(test.pause(TOKEN).await, hyper::StatusCode::OK);
+    test.solve().await.err().kind("SolverPaused");
+
+    assert_eq!(test.resume(TOKEN).await, hyper::StatusCode::OK);
+    test.solve().await.ok().orders(&[ab_order()]);
+}
+
+/// Test that the solver can't be paused without the correct token.
+#[tokio::test]
+#[ignore]
+async fn unauthorized_pause() {
+    let test = setup()
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .solvers(vec![test_solver().control_token(TOKEN)])
+        .done()
+        .await;
+
+    assert_eq!(test.pause("wrong").await, hyper::StatusCode::UNAUTHORIZED);
+    test.solve().await.ok().orders(&[ab_order()]);
+}
@@ -12,7 +12,7 @@ use crate::{
 #[ignore]
 async fn matrix() {
     for side in [order::Side::Buy, order::Side::Sell] {
-        for kind in [order::Kind::Market, order::Kind::Limit] {
+        for kind in [order::Kind::Market, order::Kind::Limit { oracle: None }] {
             let test = tests::setup()
                 .name(format!("{side:?} {kind:?}"))
                 .pool(ab_pool())
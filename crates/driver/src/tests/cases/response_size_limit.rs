@@ -0,0 +1,35 @@
+use crate::tests::{
+    setup,
+    setup::{ab_order, ab_pool, ab_solution, test_solver},
+};
+
+/// Test that a solver response exceeding the configured size limit is not
+/// read and the solve request fails.
+#[tokio::test]
+#[ignore]
+async fn oversized_response_is_rejected() {
+    let test = setup()
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .solvers(vec![test_solver().response_size_limit(10)])
+        .done()
+        .await;
+
+    test.solve().await.err().kind("SolverFailed");
+}
+
+/// Test that responses within the size limit are processed as usual.
+#[tokio::test]
+#[ignore]
+async fn response_within_limit_is_solved() {
+    let test = setup()
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .solvers(vec![test_solver().response_size_limit(1_000_000)])
+        .done()
+        .await;
+
+    test.solve().await.ok().orders(&[ab_order()]);
+}
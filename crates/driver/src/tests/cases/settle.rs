@@ -16,7 +16,7 @@ use {
 #[ignore]
 async fn matrix() {
     for side in [order::Side::Buy, order::Side::Sell] {
-        for kind in [order::Kind::Market, order::Kind::Limit] {
+        for kind in [order::Kind::Market, order::Kind::Limit { oracle: None }] {
             let solver_fee = match kind {
                 order::Kind::Market => None,
                 order::Kind::Limit { .. } => Some(DEFAULT_SOLVER_FEE.ether().into_wei()),
@@ -36,6 +36,24 @@ async fn matrix() {
     }
 }
 
+/// Checks that the calldata revealed for a solution simulates and executes
+/// against the forked chain without reverting and settles the order.
+#[tokio::test]
+#[ignore]
+async fn revealed_calldata_executes() {
+    let test = tests::setup()
+        .name("revealed calldata executes")
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .done()
+        .await;
+
+    test.solve().await.ok();
+    let calldata = test.reveal().await.ok().uninternalized_calldata();
+    test.execute(calldata).await.ab_order_executed().await;
+}
+
 /// Checks that settling without a solution returns an error.
 #[tokio::test]
 #[ignore]
@@ -0,0 +1,34 @@
+use crate::tests::{
+    setup,
+    setup::{ab_order, ab_pool, ab_solution, test_solver},
+};
+
+/// Test that the settlements of a shadow solver are scored but never
+/// proposed as the solution to the auction.
+#[tokio::test]
+#[ignore]
+async fn shadow_solver_never_wins() {
+    let test = setup()
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .solvers(vec![test_solver().shadow()])
+        .done()
+        .await;
+
+    test.solve().await.ok().empty();
+
+    let report = test.report().await;
+    assert_eq!(report.get("solutions").unwrap().as_u64().unwrap(), 1);
+    assert_eq!(
+        report
+            .get("skippedSolutions")
+            .unwrap()
+            .get("Shadow")
+            .unwrap()
+            .as_u64()
+            .unwrap(),
+        1
+    );
+    assert!(report.get("winnerScore").unwrap().is_null());
+}
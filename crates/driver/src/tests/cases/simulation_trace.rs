@@ -0,0 +1,38 @@
+use crate::{
+    infra::simulator,
+    tests::{
+        self,
+        setup::{ab_order, ab_pool, ab_solution},
+    },
+};
+
+/// Calldata with a selector the settlement contract doesn't implement, so
+/// calling it reverts.
+const REVERTING_CALLDATA: [u8; 4] = [0xde, 0xad, 0xbe, 0xef];
+
+/// Checks that the call trace of a reverting simulation is only captured when
+/// tracing reverts is enabled.
+#[tokio::test]
+#[ignore]
+async fn captures_trace_of_reverting_simulation() {
+    let test = tests::setup()
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .done()
+        .await;
+
+    let Err(simulator::Error::Revert(untraced)) =
+        test.simulate(REVERTING_CALLDATA.to_vec(), false).await
+    else {
+        panic!("simulation did not revert");
+    };
+    assert!(untraced.trace.is_none());
+
+    let Err(simulator::Error::Revert(traced)) =
+        test.simulate(REVERTING_CALLDATA.to_vec(), true).await
+    else {
+        panic!("simulation did not revert");
+    };
+    assert!(traced.trace.is_some());
+}
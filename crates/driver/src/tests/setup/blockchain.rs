@@ -5,13 +5,14 @@ use {
             competition::order,
             eth::{self, ContractAddress},
         },
+        infra::{self, blockchain::contracts::Addresses, Ethereum},
         tests::{self, boundary, cases::EtherExt},
     },
     ethcontract::{dyns::DynWeb3, transport::DynTransport, PrivateKey, Web3},
     futures::Future,
     secp256k1::SecretKey,
     serde_json::json,
-    std::collections::HashMap,
+    std::{collections::HashMap, sync::Arc},
     web3::signing::Key,
 };
 
@@ -857,6 +858,41 @@ impl Blockchain {
             _ => self.tokens.get(token).unwrap().address(),
         }
     }
+
+    /// Returns an [`Ethereum`] connected to this blockchain, configured like
+    /// the driver's.
+    pub async fn ethereum(&self) -> Ethereum {
+        let url = self.web3_url.parse().unwrap();
+        let rpc = infra::blockchain::Rpc::new(&url).await.unwrap();
+        let gas = Arc::new(
+            infra::blockchain::GasPriceEstimator::new(
+                rpc.web3(),
+                &Default::default(),
+                &[infra::mempool::Config {
+                    min_priority_fee: Default::default(),
+                    gas_price_cap: eth::U256::MAX,
+                    target_confirm_time: Default::default(),
+                    retry_interval: Default::default(),
+                    max_gas_price_increase: None,
+                    max_retries: 0,
+                    confirmations: 0,
+                    kind: infra::mempool::Kind::Public(infra::mempool::RevertProtection::Disabled),
+                }],
+            )
+            .await
+            .unwrap(),
+        );
+        Ethereum::new(
+            rpc,
+            Addresses {
+                settlement: Some(self.settlement.address().into()),
+                weth: Some(self.weth.address().into()),
+                cow_amms: vec![],
+            },
+            gas,
+        )
+        .await
+    }
 }
 
 async fn primary_address(web3: &DynWeb3) -> ethcontract::H160 {
@@ -20,6 +20,8 @@ pub struct Config {
 
 pub struct Driver {
     pub addr: SocketAddr,
+    /// The config file the driver was started with.
+    pub config_file: PathBuf,
     _delete_on_drop: Option<tempfile::TempPath>,
 }
 
@@ -51,6 +53,7 @@ impl Driver {
         let addr = addr_receiver.await.unwrap();
         Self {
             addr,
+            config_file,
             _delete_on_drop: config_temp_path,
         }
     }
@@ -140,6 +143,7 @@ pub fn solve_req(test: &Test) -> serde_json::Value {
         "tokens": tokens_json,
         "orders": orders_json,
         "deadline": test.deadline,
+        "created": test.created,
         "surplusCapturingJitOrderOwners": test.surplus_capturing_jit_order_owners,
     })
 }
@@ -265,6 +269,49 @@ async fn create_config_file(
             solver.merge_solutions,
         )
         .unwrap();
+        if let Some(max) = solver.max_jit_orders {
+            writeln!(file, "max-jit-orders = {max}").unwrap();
+        }
+        if !solver.blocked_addresses.is_empty() {
+            writeln!(
+                file,
+                "blocked-addresses = {}",
+                serde_json::to_string(&solver.blocked_addresses).unwrap()
+            )
+            .unwrap();
+        }
+        if !solver.denied_tokens.is_empty() {
+            let tokens = solver
+                .denied_tokens
+                .iter()
+                .map(|token| hex_address(blockchain.get_token(token)))
+                .collect::<Vec<_>>();
+            writeln!(
+                file,
+                "denied-tokens = {}",
+                serde_json::to_string(&tokens).unwrap()
+            )
+            .unwrap();
+        }
+        if let Some(token) = &solver.control_token {
+            writeln!(file, "control-token = \"{token}\"").unwrap();
+        }
+        if let Some(age) = solver.max_auction_age {
+            writeln!(file, "max-auction-age = \"{}ms\"", age.as_millis()).unwrap();
+        }
+        if solver.shadow {
+            writeln!(file, "shadow = true").unwrap();
+        }
+        if let Some(limit) = solver.response_size_limit {
+            writeln!(file, "response-size-limit = {limit}").unwrap();
+        }
+        if let Some(fallback) = &solver.fallback {
+            let (_, addr) = solvers
+                .iter()
+                .find(|(solver, _)| &solver.name == fallback)
+                .expect("fallback solver is configured");
+            writeln!(file, "fallback-endpoint = \"http://{addr}\"").unwrap();
+        }
     }
     file.into_temp_path()
 }
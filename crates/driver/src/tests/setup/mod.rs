@@ -11,6 +11,7 @@ use {
                 default_solving_share_of_deadline,
                 FeeHandler,
             },
+            simulator::{self, Simulator},
         },
         tests::{
             cases::{
@@ -192,7 +193,7 @@ impl Order {
     /// Make this a limit order.
     pub fn limit(self) -> Self {
         Self {
-            kind: order::Kind::Limit,
+            kind: order::Kind::Limit { oracle: None },
             ..self
         }
     }
@@ -206,6 +207,11 @@ impl Order {
         }
     }
 
+    /// Set the timestamp until which the order is valid.
+    pub fn valid_to(self, valid_to: u32) -> Self {
+        Self { valid_to, ..self }
+    }
+
     /// Mark that the trader should not be funded with tokens that are needed to
     /// place this order.
     pub fn unfunded(self) -> Self {
@@ -255,12 +261,12 @@ impl Order {
 
     fn surplus_fee(&self) -> eth::U256 {
         match self.kind {
-            order::Kind::Limit => self.solver_fee.unwrap_or_default(),
+            order::Kind::Limit { .. } => self.solver_fee.unwrap_or_default(),
             _ => 0.into(),
         }
     }
 
-    fn receiver(self, receiver: Option<H160>) -> Self {
+    pub fn receiver(self, receiver: Option<H160>) -> Self {
         Self { receiver, ..self }
     }
 }
@@ -315,6 +321,25 @@ pub struct Solver {
     /// Whether or not solver is allowed to combine multiple solutions into a
     /// new one.
     merge_solutions: bool,
+    /// Maximum number of JIT orders a solution may settle.
+    max_jit_orders: Option<usize>,
+    /// Addresses which solutions must not involve.
+    blocked_addresses: Vec<H160>,
+    /// Names of the tokens the solver must not trade.
+    denied_tokens: Vec<&'static str>,
+    /// Token for pausing and resuming the solver.
+    control_token: Option<String>,
+    /// Auctions older than this are rejected.
+    max_auction_age: Option<std::time::Duration>,
+    /// Whether the solver's settlements are never proposed.
+    shadow: bool,
+    /// Maximum size in bytes of a response of the solver.
+    response_size_limit: Option<usize>,
+    /// Name of another test solver whose solutions are used when this one
+    /// returns none.
+    fallback: Option<String>,
+    /// Whether the solver returns no solutions at all.
+    no_solutions: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -342,6 +367,15 @@ pub fn test_solver() -> Solver {
         },
         fee_handler: FeeHandler::default(),
         merge_solutions: false,
+        max_jit_orders: None,
+        blocked_addresses: Default::default(),
+        denied_tokens: Default::default(),
+        control_token: None,
+        max_auction_age: None,
+        shadow: false,
+        response_size_limit: None,
+        fallback: None,
+        no_solutions: false,
     }
 }
 
@@ -380,6 +414,51 @@ impl Solver {
         self.merge_solutions = true;
         self
     }
+
+    pub fn max_jit_orders(mut self, max: usize) -> Self {
+        self.max_jit_orders = Some(max);
+        self
+    }
+
+    pub fn blocked_addresses(mut self, addresses: Vec<H160>) -> Self {
+        self.blocked_addresses = addresses;
+        self
+    }
+
+    pub fn denied_tokens(mut self, tokens: Vec<&'static str>) -> Self {
+        self.denied_tokens = tokens;
+        self
+    }
+
+    pub fn control_token(mut self, token: &str) -> Self {
+        self.control_token = Some(token.to_owned());
+        self
+    }
+
+    pub fn max_auction_age(mut self, age: std::time::Duration) -> Self {
+        self.max_auction_age = Some(age);
+        self
+    }
+
+    pub fn shadow(mut self) -> Self {
+        self.shadow = true;
+        self
+    }
+
+    pub fn response_size_limit(mut self, limit: usize) -> Self {
+        self.response_size_limit = Some(limit);
+        self
+    }
+
+    pub fn fallback(mut self, solver: &str) -> Self {
+        self.fallback = Some(solver.to_owned());
+        self
+    }
+
+    pub fn no_solutions(mut self) -> Self {
+        self.no_solutions = true;
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -484,6 +563,7 @@ pub fn setup() -> Setup {
         rpc_args: vec!["--gas-limit".into(), "10000000".into()],
         jit_orders: Default::default(),
         surplus_capturing_jit_order_owners: Default::default(),
+        auction_age: Default::default(),
     }
 }
 
@@ -511,6 +591,8 @@ pub struct Setup {
     jit_orders: Vec<JitOrder>,
     /// List of surplus capturing JIT-order owners
     surplus_capturing_jit_order_owners: Vec<H160>,
+    /// How long ago the auction was created when it is sent to the driver.
+    auction_age: chrono::Duration,
 }
 
 /// The validity of a solution.
@@ -811,6 +893,7 @@ impl Setup {
     pub async fn done(self) -> Test {
         observe::tracing::initialize_reentrant(
             "driver=trace,driver::tests::setup::blockchain=debug",
+            false,
         );
 
         if let Some(name) = self.name.as_ref() {
@@ -818,6 +901,7 @@ impl Setup {
         }
 
         let deadline = self.deadline();
+        let created = crate::infra::time::now() - self.auction_age;
         let Self {
             pools,
             orders,
@@ -887,7 +971,7 @@ impl Setup {
         let solvers_with_address = join_all(self.solvers.iter().map(|solver| async {
             let instance = SolverInstance::new(solver::Config {
                 blockchain: &blockchain,
-                solutions: &solutions,
+                solutions: if solver.no_solutions { &[] } else { &solutions },
                 trusted: &trusted,
                 quoted_orders: &quotes,
                 deadline: time::Deadline::new(deadline, solver.timeouts),
@@ -921,9 +1005,11 @@ impl Setup {
             trades: solutions.into_iter().flat_map(|s| s.trades).collect(),
             trusted,
             deadline,
+            created,
             quoted_orders: quotes,
             quote: self.quote,
             surplus_capturing_jit_order_owners,
+            solvers: self.solvers,
         }
     }
 
@@ -935,6 +1021,14 @@ impl Setup {
         }
     }
 
+    /// Send an auction which was created this long ago.
+    pub fn auction_age(self, age: chrono::Duration) -> Self {
+        Self {
+            auction_age: age,
+            ..self
+        }
+    }
+
     /// Solver send the solution as JIT order
     pub fn jit_order(mut self, jit_order: JitOrder) -> Self {
         self.jit_orders.push(jit_order);
@@ -955,10 +1049,14 @@ pub struct Test {
     trades: Vec<Trade>,
     trusted: HashSet<&'static str>,
     deadline: chrono::DateTime<chrono::Utc>,
+    /// When the auction sent to the driver was created.
+    created: chrono::DateTime<chrono::Utc>,
     /// Is this testing the /quote endpoint?
     quote: bool,
     /// List of surplus capturing JIT-order owners
     surplus_capturing_jit_order_owners: Vec<H160>,
+    /// The solvers the driver was configured with.
+    solvers: Vec<Solver>,
 }
 
 impl Test {
@@ -1005,6 +1103,92 @@ impl Test {
         Reveal { status, body }
     }
 
+    /// Call the /auction endpoint.
+    pub async fn auction(&self) -> serde_json::Value {
+        self.get("auction").await
+    }
+
+    /// Call the /report endpoint.
+    pub async fn report(&self) -> serde_json::Value {
+        self.get("report").await
+    }
+
+    /// Returns an [`infra::Ethereum`] connected to the test blockchain.
+    pub async fn ethereum(&self) -> infra::Ethereum {
+        self.blockchain.ethereum().await
+    }
+
+    /// Returns the test solver as configured in the driver.
+    pub async fn solver(&self, eth: &infra::Ethereum) -> infra::Solver {
+        let config = infra::config::file::load(eth.network(), &self.driver.config_file).await;
+        let config = config
+            .solvers
+            .into_iter()
+            .find(|config| config.name.0 == solver::NAME)
+            .unwrap();
+        infra::Solver::new(config, eth.clone()).await.unwrap()
+    }
+
+    /// Call the driver-wide /config endpoint.
+    pub async fn config(&self) -> serde_json::Value {
+        let res = self
+            .client
+            .get(format!("http://{}/config", self.driver.addr))
+            .send()
+            .await
+            .unwrap();
+        let status = res.status();
+        let body = res.text().await.unwrap();
+        tracing::debug!(?status, ?body, "got a response from /config");
+        assert_eq!(status, hyper::StatusCode::OK);
+        serde_json::from_str(&body).unwrap()
+    }
+
+    /// GET a JSON response from the given endpoint, which must succeed.
+    async fn get(&self, endpoint: &str) -> serde_json::Value {
+        let res = self
+            .client
+            .get(format!(
+                "http://{}/{}/{endpoint}",
+                self.driver.addr,
+                solver::NAME
+            ))
+            .send()
+            .await
+            .unwrap();
+        let status = res.status();
+        let body = res.text().await.unwrap();
+        tracing::debug!(?status, ?body, "got a response from /{endpoint}");
+        assert_eq!(status, hyper::StatusCode::OK);
+        serde_json::from_str(&body).unwrap()
+    }
+
+    /// Call the /pause endpoint with the given bearer token.
+    pub async fn pause(&self, token: &str) -> StatusCode {
+        self.control("pause", token).await
+    }
+
+    /// Call the /resume endpoint with the given bearer token.
+    pub async fn resume(&self, token: &str) -> StatusCode {
+        self.control("resume", token).await
+    }
+
+    async fn control(&self, endpoint: &str, token: &str) -> StatusCode {
+        let res = self
+            .client
+            .post(format!(
+                "http://{}/{}/{endpoint}",
+                self.driver.addr,
+                solver::NAME
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .unwrap();
+        tracing::debug!(status = ?res.status(), "got a response from /{endpoint}");
+        res.status()
+    }
+
     /// Call the /quote endpoint.
     pub async fn quote(&self) -> Quote {
         if !self.quote {
@@ -1071,6 +1255,40 @@ impl Test {
         }
     }
 
+    /// Execute settlement calldata, as returned by the /reveal endpoint,
+    /// directly against the forked chain on behalf of the default solver.
+    /// Unlike /settle this bypasses the driver's submission logic, so it
+    /// asserts that the exact settlement the driver computed doesn't revert,
+    /// both in the driver's [`Simulator`] and onchain.
+    pub async fn execute(&self, calldata: Vec<u8>) -> SettleOk {
+        let solver = self
+            .solvers
+            .iter()
+            .find(|solver| solver.name == solver::NAME)
+            .expect("default solver is configured");
+        self.simulate_settlement(calldata.clone(), None)
+            .await
+            .expect("settlement calldata reverts in simulation");
+        let old_balances = self.balances().await;
+        blockchain::wait_for(
+            self.web3(),
+            ethcontract::transaction::TransactionBuilder::new(self.web3().clone())
+                .from(ethcontract::Account::Offline(
+                    solver.private_key.clone(),
+                    None,
+                ))
+                .to(self.blockchain.settlement.address())
+                .data(calldata.into())
+                .send(),
+        )
+        .await
+        .expect("settlement calldata reverted");
+        SettleOk {
+            test: self,
+            old_balances,
+        }
+    }
+
     async fn balances(&self) -> HashMap<&'static str, eth::U256> {
         let mut balances = HashMap::new();
         for (token, contract) in self.blockchain.tokens.iter() {
@@ -1102,10 +1320,72 @@ impl Test {
         balances
     }
 
-    #[allow(dead_code)]
+    /// Simulate calldata against the settlement contract on behalf of the
+    /// trader, attaching the call trace to reverts if `trace` is set.
+    pub async fn simulate(
+        &self,
+        calldata: Vec<u8>,
+        trace: bool,
+    ) -> Result<eth::Gas, simulator::Error> {
+        let mut simulator = Simulator::ethereum(self.blockchain.ethereum().await);
+        if trace {
+            simulator.trace_reverts();
+        }
+        simulator
+            .gas(&eth::Tx {
+                from: self.trader_address.into(),
+                to: self.blockchain.settlement.address().into(),
+                value: eth::U256::zero().into(),
+                input: calldata.into(),
+                access_list: Default::default(),
+            })
+            .await
+    }
+
+    /// Simulate settlement calldata on behalf of the default solver, as if it
+    /// was included with the given offset to the current block if set.
+    pub async fn simulate_settlement(
+        &self,
+        calldata: Vec<u8>,
+        inclusion_offset: Option<simulator::InclusionOffset>,
+    ) -> Result<eth::Gas, simulator::Error> {
+        let solver = self
+            .solvers
+            .iter()
+            .find(|solver| solver.name == solver::NAME)
+            .expect("default solver is configured");
+        let mut simulator = Simulator::ethereum(self.blockchain.ethereum().await);
+        if let Some(offset) = inclusion_offset {
+            simulator.inclusion_offset(offset);
+        }
+        simulator
+            .gas(&eth::Tx {
+                from: solver.address().into(),
+                to: self.blockchain.settlement.address().into(),
+                value: eth::U256::zero().into(),
+                input: calldata.into(),
+                access_list: Default::default(),
+            })
+            .await
+    }
+
     pub fn web3(&self) -> &web3::Web3<DynTransport> {
         &self.blockchain.web3
     }
+
+    /// Mine a block the given number of seconds in the future and wait for
+    /// the driver to see it.
+    pub async fn advance_time(&self, seconds: u64) {
+        use web3::Transport;
+
+        let transport = self.web3().transport();
+        transport
+            .execute("evm_increaseTime", vec![serde_json::json!(seconds)])
+            .await
+            .unwrap();
+        transport.execute("evm_mine", vec![]).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
 }
 
 /// A /solve response.
@@ -1122,6 +1402,10 @@ pub struct SolveOk<'a> {
     blockchain: &'a Blockchain,
 }
 
+pub struct SolveErr {
+    body: String,
+}
+
 impl<'a> Solve<'a> {
     /// Expect the /solve endpoint to have returned a 200 OK response.
     pub fn ok(self) -> SolveOk<'a> {
@@ -1132,6 +1416,19 @@ impl<'a> Solve<'a> {
             blockchain: self.blockchain,
         }
     }
+
+    /// Expect the /solve endpoint to have returned a 400 BAD REQUEST response.
+    pub fn err(self) -> SolveErr {
+        assert_eq!(self.status, hyper::StatusCode::BAD_REQUEST);
+        SolveErr { body: self.body }
+    }
+}
+
+impl SolveErr {
+    /// Check the kind field in the error response.
+    pub fn kind(self, expected_kind: &str) {
+        assert_error_kind(&self.body, expected_kind);
+    }
 }
 
 impl<'a> SolveOk<'a> {
@@ -1266,6 +1563,20 @@ impl RevealOk {
             .is_empty());
         self
     }
+
+    /// The uninternalized settlement calldata, i.e. the calldata which
+    /// executes every interaction of the solution onchain.
+    pub fn uninternalized_calldata(&self) -> Vec<u8> {
+        let result: serde_json::Value = serde_json::from_str(&self.body).unwrap();
+        let calldata = result
+            .get("calldata")
+            .unwrap()
+            .get("uninternalized")
+            .unwrap()
+            .as_str()
+            .unwrap();
+        hex::decode(calldata.trim_start_matches("0x")).unwrap()
+    }
 }
 
 /// A /quote response.
@@ -1441,12 +1752,17 @@ impl<'a> SettleOk<'a> {
 impl SettleErr {
     /// Check the kind field in the error response.
     pub fn kind(self, expected_kind: &str) {
-        let result: serde_json::Value = serde_json::from_str(&self.body).unwrap();
-        assert!(result.is_object());
-        assert_eq!(result.as_object().unwrap().len(), 2);
-        assert!(result.get("kind").is_some());
-        assert!(result.get("description").is_some());
-        let kind = result.get("kind").unwrap().as_str().unwrap();
-        assert_eq!(kind, expected_kind);
+        assert_error_kind(&self.body, expected_kind);
     }
 }
+
+/// Check the kind field of an error response body.
+fn assert_error_kind(body: &str, expected_kind: &str) {
+    let result: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert!(result.is_object());
+    assert_eq!(result.as_object().unwrap().len(), 2);
+    assert!(result.get("kind").is_some());
+    assert!(result.get("description").is_some());
+    let kind = result.get("kind").unwrap().as_str().unwrap();
+    assert_eq!(kind, expected_kind);
+}
@@ -10,7 +10,7 @@ use {
             eth,
             time::{self},
         },
-        infra::{self, blockchain::contracts::Addresses, config::file::FeeHandler, Ethereum},
+        infra::config::file::FeeHandler,
         tests::{hex_address, setup::blockchain::Trade},
     },
     ethereum_types::H160,
@@ -370,33 +370,7 @@ impl Solver {
             .into_iter()
             .collect::<HashMap<_, _>>();
 
-        let url = config.blockchain.web3_url.parse().unwrap();
-        let rpc = infra::blockchain::Rpc::new(&url).await.unwrap();
-        let gas = Arc::new(
-            infra::blockchain::GasPriceEstimator::new(
-                rpc.web3(),
-                &Default::default(),
-                &[infra::mempool::Config {
-                    min_priority_fee: Default::default(),
-                    gas_price_cap: eth::U256::MAX,
-                    target_confirm_time: Default::default(),
-                    retry_interval: Default::default(),
-                    kind: infra::mempool::Kind::Public(infra::mempool::RevertProtection::Disabled),
-                }],
-            )
-            .await
-            .unwrap(),
-        );
-        let eth = Ethereum::new(
-            rpc,
-            Addresses {
-                settlement: Some(config.blockchain.settlement.address().into()),
-                weth: Some(config.blockchain.weth.address().into()),
-                cow_amms: vec![],
-            },
-            gas,
-        )
-        .await;
+        let eth = config.blockchain.ethereum().await;
 
         let state = Arc::new(Mutex::new(StateInner { called: false }));
         let app = axum::Router::new()
@@ -15,8 +15,12 @@ use {
 /// Initializes tracing setup that is shared between the binaries.
 /// `env_filter` has similar syntax to env_logger. It is documented at
 /// https://docs.rs/tracing-subscriber/0.2.15/tracing_subscriber/filter/struct.EnvFilter.html
-pub fn initialize(env_filter: &str, stderr_threshold: LevelFilter) {
-    set_tracing_subscriber(env_filter, stderr_threshold);
+///
+/// If `enable_task_console` is set, the `tokio-console` subscriber layer gets
+/// installed as well, which allows inspecting the state of all tokio tasks
+/// live. This requires the binary to be compiled with `tokio_unstable`.
+pub fn initialize(env_filter: &str, stderr_threshold: LevelFilter, enable_task_console: bool) {
+    set_tracing_subscriber(env_filter, stderr_threshold, enable_task_console);
     std::panic::set_hook(Box::new(tracing_panic_hook));
 }
 
@@ -24,17 +28,21 @@ pub fn initialize(env_filter: &str, stderr_threshold: LevelFilter) {
 /// are ignored.
 ///
 /// Useful for tests.
-pub fn initialize_reentrant(env_filter: &str) {
+pub fn initialize_reentrant(env_filter: &str, enable_task_console: bool) {
     // The tracing subscriber below is global object so initializing it again in the
     // same process by a different thread would fail.
     static ONCE: Once = Once::new();
     ONCE.call_once(|| {
-        set_tracing_subscriber(env_filter, LevelFilter::ERROR);
+        set_tracing_subscriber(env_filter, LevelFilter::ERROR, enable_task_console);
         std::panic::set_hook(Box::new(tracing_panic_hook));
     });
 }
 
-fn set_tracing_subscriber(env_filter: &str, stderr_threshold: LevelFilter) {
+fn set_tracing_subscriber(
+    env_filter: &str,
+    stderr_threshold: LevelFilter,
+    enable_task_console: bool,
+) {
     let initial_filter = env_filter.to_string();
 
     // The `tracing` APIs are heavily generic to enable zero overhead. Unfortunately
@@ -70,11 +78,11 @@ fn set_tracing_subscriber(env_filter: &str, stderr_threshold: LevelFilter) {
         }};
     }
 
-    let enable_tokio_console: bool = std::env::var("TOKIO_CONSOLE")
-        .unwrap_or("false".to_string())
-        .parse()
-        .unwrap();
-
+    let enable_tokio_console: bool = enable_task_console
+        || std::env::var("TOKIO_CONSOLE")
+            .unwrap_or("false".to_string())
+            .parse()
+            .unwrap();
     if cfg!(tokio_unstable) && enable_tokio_console {
         let (env_filter, reload_handle) =
             tracing_subscriber::reload::Layer::new(EnvFilter::new(&initial_filter));
@@ -99,6 +107,11 @@ fn set_tracing_subscriber(env_filter: &str, stderr_threshold: LevelFilter) {
             .with(fmt_layer!(env_filter, stderr_threshold))
             .init();
         tracing::info!("started programm without support for tokio-console");
+        if enable_tokio_console {
+            tracing::warn!(
+                "tokio-console requires compiling with `--cfg tokio_unstable`, ignoring"
+            );
+        }
 
         if cfg!(unix) {
             spawn_reload_handler(initial_filter, reload_handle);
@@ -116,3 +129,15 @@ fn tracing_panic_hook(panic: &PanicInfo) {
     let backtrace = std::backtrace::Backtrace::force_capture();
     tracing::error!("thread '{name}' {panic}\nstack backtrace:\n{backtrace}");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(tokio_unstable)]
+    #[tokio::test]
+    async fn initialize_with_task_console() {
+        initialize("info", LevelFilter::OFF, true);
+        tracing::info!("logging still works with tokio-console enabled");
+    }
+}
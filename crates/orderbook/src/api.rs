@@ -23,6 +23,7 @@ mod get_user_orders;
 mod post_order;
 mod post_quote;
 mod put_app_data;
+mod readonly;
 mod version;
 
 pub fn handle_all_routes(
@@ -31,12 +32,13 @@ pub fn handle_all_routes(
     quotes: Arc<QuoteHandler>,
     app_data: Arc<app_data::Registry>,
     native_price_estimator: Arc<dyn NativePriceEstimating>,
+    readonly: bool,
 ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
     // Note that we add a string with endpoint's name to all responses.
     // This string will be used later to report metrics.
     // It is not used to form the actual server response.
 
-    let routes = vec![
+    let mut routes = vec![
         (
             "v1/create_order",
             box_filter(post_order::post_order(orderbook.clone())),
@@ -103,5 +105,11 @@ pub fn handle_all_routes(
         ),
     ];
 
+    if readonly {
+        // Matched before any other route so that mutating requests never reach
+        // their handlers.
+        routes.insert(0, ("v1/readonly", box_filter(readonly::filter())));
+    }
+
     finalize_router(routes, "orderbook::api::request_summary")
 }
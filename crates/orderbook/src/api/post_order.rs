@@ -43,6 +43,15 @@ impl IntoWarpReply for PartialValidationErrorWrapper {
                 error("UnsupportedSellTokenSource", format!("Type {src:?}")),
                 StatusCode::BAD_REQUEST,
             ),
+            PartialValidationError::UnsupportedSigningScheme(scheme) => with_status(
+                error(
+                    "UnsupportedSigningScheme",
+                    format!(
+                        "Signing scheme {scheme:?} is not supported by the settlement contract"
+                    ),
+                ),
+                StatusCode::BAD_REQUEST,
+            ),
             PartialValidationError::UnsupportedOrderType => with_status(
                 error(
                     "UnsupportedOrderType",
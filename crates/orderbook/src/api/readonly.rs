@@ -0,0 +1,48 @@
+use warp::{http::Method, hyper::StatusCode, reply, Filter, Rejection};
+
+/// Rejects every request which could mutate state with `405 Method Not
+/// Allowed`. Read-only requests are not matched by this filter, so they fall
+/// through to the actual routes.
+pub fn filter() -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    warp::method().and_then(|method: Method| async move {
+        if method == Method::GET || method == Method::HEAD {
+            return Err(warp::reject());
+        }
+        Ok(reply::with_status(
+            super::error(
+                "ReadOnly",
+                "the orderbook is running in read-only mode and does not accept mutations",
+            ),
+            StatusCode::METHOD_NOT_ALLOWED,
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        warp::{test::request, Reply},
+    };
+
+    #[tokio::test]
+    async fn rejects_mutations() {
+        let filter = filter()
+            .or(warp::get().map(reply::reply))
+            .map(Reply::into_response);
+
+        let response = request()
+            .path("/v1/orders")
+            .method("POST")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+        let response = request()
+            .path("/v1/orders/0x00")
+            .method("GET")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -147,6 +147,11 @@ pub struct Arguments {
     /// The maximum gas amount a single order can use for getting settled.
     #[clap(long, env, default_value = "8000000")]
     pub max_gas_per_order: u64,
+
+    /// Run the API in read-only mode, rejecting all requests that would create
+    /// or modify data. Useful for safely inspecting a production database.
+    #[clap(long, env, action = clap::ArgAction::Set, default_value = "false")]
+    pub readonly: bool,
 }
 
 impl std::fmt::Display for Arguments {
@@ -178,6 +183,7 @@ impl std::fmt::Display for Arguments {
             app_data_size_limit,
             db_url,
             max_gas_per_order,
+            readonly,
         } = self;
 
         write!(f, "{}", shared)?;
@@ -243,6 +249,7 @@ impl std::fmt::Display for Arguments {
         )?;
         writeln!(f, "app_data_size_limit: {}", app_data_size_limit)?;
         writeln!(f, "max_gas_per_order: {}", max_gas_per_order)?;
+        writeln!(f, "readonly: {}", readonly)?;
 
         Ok(())
     }
@@ -1,5 +1,6 @@
 use {
     super::order::Order,
+    chrono::{DateTime, Utc},
     number::serialization::HexOrDecimalU256,
     primitive_types::{H160, U256},
     serde::{Deserialize, Serialize},
@@ -19,6 +20,8 @@ pub struct Auction {
     pub prices: BTreeMap<H160, U256>,
     #[serde(default)]
     pub surplus_capturing_jit_order_owners: Vec<H160>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<DateTime<Utc>>,
 }
 
 pub type AuctionId = i64;
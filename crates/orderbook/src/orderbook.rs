@@ -21,6 +21,7 @@ use {
             SignedOrderCancellations,
         },
         quote::QuoteId,
+        signature::SigningScheme,
         solver_competition,
         DomainSeparator,
     },
@@ -40,6 +41,10 @@ struct Metrics {
     /// Counter for measuring order statistics.
     #[metric(labels("kind", "operation"))]
     orders: prometheus::IntCounterVec,
+
+    /// Number of created orders by order class and signing scheme.
+    #[metric(labels("class", "signing_scheme"))]
+    created_orders: prometheus::IntCounterVec,
 }
 
 enum OrderOperation {
@@ -62,6 +67,15 @@ fn order_class_label(class: &OrderClass) -> &'static str {
     }
 }
 
+fn signing_scheme_label(scheme: &SigningScheme) -> &'static str {
+    match scheme {
+        SigningScheme::Eip712 => "eip712",
+        SigningScheme::EthSign => "ethsign",
+        SigningScheme::Eip1271 => "eip1271",
+        SigningScheme::PreSign => "presign",
+    }
+}
+
 impl Metrics {
     fn get() -> &'static Self {
         Self::instance(observe::metrics::get_storage_registry())
@@ -71,7 +85,15 @@ impl Metrics {
     fn on_order_operation(order: &Order, operation: OrderOperation) {
         let class = order_class_label(&order.metadata.class);
         let op = operation_label(&operation);
-        Self::get().orders.with_label_values(&[class, op]).inc();
+        let metrics = Self::get();
+        metrics.orders.with_label_values(&[class, op]).inc();
+        if let OrderOperation::Created = operation {
+            let scheme = signing_scheme_label(&order.signature.scheme());
+            metrics
+                .created_orders
+                .with_label_values(&[class, scheme])
+                .inc();
+        }
     }
 
     // Resets all the counters to 0 so we can always use them in Grafana queries.
@@ -84,6 +106,21 @@ impl Metrics {
                 metrics.orders.with_label_values(&[class, op]).reset();
             }
         }
+        for class in &[OrderClass::Market, OrderClass::Liquidity, OrderClass::Limit] {
+            let class = order_class_label(class);
+            for scheme in &[
+                SigningScheme::Eip712,
+                SigningScheme::EthSign,
+                SigningScheme::Eip1271,
+                SigningScheme::PreSign,
+            ] {
+                let scheme = signing_scheme_label(scheme);
+                metrics
+                    .created_orders
+                    .with_label_values(&[class, scheme])
+                    .reset();
+            }
+        }
     }
 }
 
@@ -624,4 +661,30 @@ mod tests {
             .unwrap();
         assert_eq!(order_id, new_order_uid,);
     }
+
+    #[test]
+    fn counts_created_orders_by_class_and_signing_scheme() {
+        let order = Order {
+            metadata: OrderMetadata {
+                class: OrderClass::Limit,
+                ..Default::default()
+            },
+            signature: Signature::PreSign,
+            ..Default::default()
+        };
+        let created = || {
+            Metrics::get()
+                .created_orders
+                .with_label_values(&["limit", "presign"])
+                .get()
+        };
+        let before = created();
+
+        Metrics::on_order_operation(&order, OrderOperation::Created);
+        assert_eq!(created(), before + 1);
+
+        // Cancellations don't count as created orders.
+        Metrics::on_order_operation(&order, OrderOperation::Cancelled);
+        assert_eq!(created(), before + 1);
+    }
 }
@@ -53,6 +53,7 @@ pub async fn start(args: impl Iterator<Item = String>) {
     observe::tracing::initialize(
         args.shared.logging.log_filter.as_str(),
         args.shared.logging.log_stderr_threshold,
+        args.shared.logging.enable_task_console,
     );
     tracing::info!("running order book with validated arguments:\n{}", args);
     observe::panic_hook::install();
@@ -136,9 +137,10 @@ pub async fn run(args: Arguments) {
             .expect("load hooks trampoline contract"),
     };
 
-    verify_deployed_contract_constants(&settlement_contract, chain_id)
+    let capabilities = verify_deployed_contract_constants(&settlement_contract, chain_id)
         .await
         .expect("Deployed contract constants don't match the ones in this binary");
+    tracing::info!(?capabilities, "settlement contract capabilities");
     let domain_separator = DomainSeparator::new(chain_id, settlement_contract.address());
     let postgres = Postgres::new(args.db_url.as_str()).expect("failed to create database");
 
@@ -354,6 +356,7 @@ pub async fn run(args: Arguments) {
         Arc::new(CachedCodeFetcher::new(Arc::new(web3.clone()))),
         app_data_validator.clone(),
         args.max_gas_per_order,
+        capabilities.pre_sign,
     ));
     let ipfs = args
         .ipfs_gateway
@@ -396,6 +399,7 @@ pub async fn run(args: Arguments) {
             let _ = shutdown_receiver.await;
         },
         native_price_estimator,
+        args.readonly,
     );
 
     let mut metrics_address = args.bind_address;
@@ -461,6 +465,7 @@ fn serve_api(
     address: SocketAddr,
     shutdown_receiver: impl Future<Output = ()> + Send + 'static,
     native_price_estimator: Arc<dyn NativePriceEstimating>,
+    readonly: bool,
 ) -> JoinHandle<()> {
     let filter = api::handle_all_routes(
         database,
@@ -468,6 +473,7 @@ fn serve_api(
         quotes,
         app_data,
         native_price_estimator,
+        readonly,
     )
     .boxed();
     tracing::info!(%address, "serving order book");
@@ -480,22 +486,59 @@ fn serve_api(
     task::spawn(server)
 }
 
+/// Features which differ between versions of the settlement contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Capabilities {
+    /// Orders can be signed on-chain with `setPreSignature`. Pre-signed orders
+    /// are rejected otherwise.
+    pre_sign: bool,
+    /// Single orders can be settled directly against Balancer pools with
+    /// `swap`.
+    swap: bool,
+    /// Storage of filled orders and pre-signatures can be freed for gas
+    /// refunds with `freeFilledAmountStorage` and `freePreSignatureStorage`.
+    storage_refunds: bool,
+}
+
+impl Capabilities {
+    /// Detects the capabilities of a contract from its deployed bytecode, in
+    /// which the function dispatcher pushes the selector of every external
+    /// function (`PUSH4 <selector>`).
+    fn detect(bytecode: &[u8]) -> Self {
+        let implements = |selector: [u8; 4]| {
+            bytecode
+                .windows(5)
+                .any(|window| window[0] == PUSH4 && window[1..] == selector)
+        };
+        Self {
+            pre_sign: implements(hex_literal::hex!("ec6cb13f")),
+            swap: implements(hex_literal::hex!("845a101f")),
+            storage_refunds: implements(hex_literal::hex!("ed9f35ce"))
+                && implements(hex_literal::hex!("a2a7d51b")),
+        }
+    }
+}
+
+/// The `PUSH4` EVM opcode.
+const PUSH4: u8 = 0x63;
+
 /// Check that important constants such as the EIP 712 Domain Separator and
 /// Order Type Hash used in this binary match the ones on the deployed
 /// contract instance. Signature inconsistencies due to a mismatch of these
-/// constants are hard to debug.
+/// constants are hard to debug. Returns the features supported by the
+/// deployed contract.
 async fn verify_deployed_contract_constants(
     contract: &GPv2Settlement,
     chain_id: u64,
-) -> Result<()> {
+) -> Result<Capabilities> {
     let web3 = contract.raw_instance().web3();
-    let bytecode = hex::encode(
-        web3.eth()
-            .code(contract.address(), None)
-            .await
-            .context("Could not load deployed bytecode")?
-            .0,
-    );
+    let code = web3
+        .eth()
+        .code(contract.address(), None)
+        .await
+        .context("Could not load deployed bytecode")?
+        .0;
+    let bytecode = hex::encode(&code);
 
     let domain_separator = DomainSeparator::new(chain_id, contract.address());
     if !bytecode.contains(&hex::encode(domain_separator.0)) {
@@ -505,5 +548,51 @@ async fn verify_deployed_contract_constants(
     if !bytecode.contains(&hex::encode(model::order::OrderData::TYPE_HASH)) {
         return Err(anyhow!("Bytecode did not contain order type hash"));
     }
-    Ok(())
+    Ok(Capabilities::detect(&code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_capabilities_from_selectors() {
+        let dispatcher = |selectors: &[&str]| {
+            selectors
+                .iter()
+                .flat_map(|selector| {
+                    // PUSH4 <selector> DUP2 EQ
+                    let mut code = vec![PUSH4];
+                    code.extend(hex::decode(selector).unwrap());
+                    code.extend([0x81, 0x14]);
+                    code
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let current = dispatcher(&["13d79a0b", "ec6cb13f", "845a101f", "ed9f35ce", "a2a7d51b"]);
+        assert_eq!(
+            Capabilities::detect(&current),
+            Capabilities {
+                pre_sign: true,
+                swap: true,
+                storage_refunds: true,
+            }
+        );
+
+        // An older version only supporting settlements and pre-signatures.
+        let older = dispatcher(&["13d79a0b", "ec6cb13f"]);
+        assert_eq!(
+            Capabilities::detect(&older),
+            Capabilities {
+                pre_sign: true,
+                swap: false,
+                storage_refunds: false,
+            }
+        );
+
+        // Selectors only count when pushed by the dispatcher.
+        let data = hex::decode("00845a101f").unwrap();
+        assert!(!Capabilities::detect(&data).swap);
+    }
 }
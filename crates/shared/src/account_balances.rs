@@ -6,12 +6,15 @@ use {
         order::{Order, SellTokenSource},
     },
     primitive_types::{H160, U256},
-    std::sync::Arc,
+    std::{sync::Arc, time::Duration},
 };
 
 mod cached;
+mod concurrent;
 mod simulation;
 
+pub use concurrent::Config as ConcurrencyConfig;
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Query {
     pub owner: H160,
@@ -83,13 +86,18 @@ pub fn fetcher(web3: &Web3, contracts: Contracts) -> Arc<dyn BalanceFetching> {
     ))
 }
 
-/// Create a cached [`BalanceFetching`] instance.
+/// Create a cached [`BalanceFetching`] instance. Balances stay cached until
+/// they haven't been requested for `ttl`. Cache misses are deduplicated and
+/// fetched in chunks with bounded concurrency according to `config`.
 pub fn cached(
     web3: &Web3,
     contracts: Contracts,
     blocks: CurrentBlockStream,
+    ttl: Duration,
+    config: ConcurrencyConfig,
 ) -> Arc<dyn BalanceFetching> {
-    let cached = Arc::new(cached::Balances::new(fetcher(web3, contracts)));
+    let concurrent = Arc::new(concurrent::Balances::new(fetcher(web3, contracts), config));
+    let cached = Arc::new(cached::Balances::new(concurrent, ttl));
     cached.spawn_background_task(blocks);
     cached
 }
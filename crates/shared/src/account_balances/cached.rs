@@ -8,19 +8,18 @@ use {
     std::{
         collections::HashMap,
         sync::{Arc, Mutex},
+        time::Duration,
     },
     tracing::Instrument,
 };
 
 type BlockNumber = u64;
-
-/// Balances get removed from the cache after this many blocks without being
-/// requested.
-const EVICTION_TIME: BlockNumber = 5;
+type Timestamp = u64;
 
 #[derive(Default)]
 struct BalanceCache {
     last_seen_block: BlockNumber,
+    last_seen_timestamp: Timestamp,
     data: HashMap<Query, BalanceEntry>,
 }
 
@@ -29,7 +28,7 @@ impl BalanceCache {
     fn get_cached_balance(&mut self, query: &Query) -> Option<U256> {
         match self.data.get_mut(query) {
             Some(entry) => {
-                entry.requested_at = self.last_seen_block;
+                entry.requested_at = self.last_seen_timestamp;
                 Some(entry.balance)
             }
             None => None,
@@ -52,12 +51,12 @@ impl BalanceCache {
 
     /// Only inserts new balances. This should always be used when we needed to
     /// fetch a balance because it was requested by a backend component.
-    fn insert_balance(&mut self, query: Query, balance: U256, requested_at: BlockNumber) {
+    fn insert_balance(&mut self, query: Query, balance: U256, requested_at: CacheTime) {
         self.data.insert(
             query,
             BalanceEntry {
-                requested_at,
-                updated_at: requested_at,
+                requested_at: requested_at.timestamp,
+                updated_at: requested_at.block,
                 balance,
             },
         );
@@ -66,7 +65,8 @@ impl BalanceCache {
 
 #[derive(Debug, Clone)]
 struct BalanceEntry {
-    requested_at: BlockNumber,
+    /// Timestamp of the last block seen when the balance was requested.
+    requested_at: Timestamp,
     updated_at: BlockNumber,
     balance: U256,
 }
@@ -74,23 +74,33 @@ struct BalanceEntry {
 pub struct Balances {
     inner: Arc<dyn BalanceFetching>,
     balance_cache: Arc<Mutex<BalanceCache>>,
+    /// Balances get removed from the cache after they haven't been requested
+    /// for this long.
+    ttl: Duration,
 }
 
 impl Balances {
-    pub fn new(inner: Arc<dyn BalanceFetching>) -> Self {
+    pub fn new(inner: Arc<dyn BalanceFetching>, ttl: Duration) -> Self {
         Self {
             inner,
             balance_cache: Default::default(),
+            ttl,
         }
     }
 }
 
+#[derive(Clone, Copy)]
+struct CacheTime {
+    block: BlockNumber,
+    timestamp: Timestamp,
+}
+
 struct CacheResponse {
     // The indices and results of queries that were in the cache.
     cached: Vec<(usize, Result<U256>)>,
     // Indices of queries that were not in the cache.
     missing: Vec<usize>,
-    requested_at: BlockNumber,
+    requested_at: CacheTime,
 }
 
 impl Balances {
@@ -106,7 +116,10 @@ impl Balances {
         CacheResponse {
             cached,
             missing,
-            requested_at: cache.last_seen_block,
+            requested_at: CacheTime {
+                block: cache.last_seen_block,
+                timestamp: cache.last_seen_timestamp,
+            },
         }
     }
 
@@ -114,6 +127,7 @@ impl Balances {
     pub fn spawn_background_task(&self, block_stream: CurrentBlockStream) {
         let inner = self.inner.clone();
         let cache = self.balance_cache.clone();
+        let ttl = self.ttl.as_secs();
         let mut stream = into_stream(block_stream);
 
         let task = async move {
@@ -121,13 +135,13 @@ impl Balances {
                 let balances_to_update = {
                     let mut cache = cache.lock().unwrap();
                     cache.last_seen_block = block.number;
+                    cache.last_seen_timestamp = block.timestamp;
+                    // Only update balances that have been requested recently.
+                    let oldest_allowed_request = block.timestamp.saturating_sub(ttl);
                     cache
                         .data
                         .iter()
                         .filter_map(|(query, entry)| {
-                            // Only update balances that have been requested recently.
-                            let oldest_allowed_request =
-                                cache.last_seen_block.saturating_sub(EVICTION_TIME);
                             (entry.requested_at >= oldest_allowed_request).then_some(query.clone())
                         })
                         .collect_vec()
@@ -206,6 +220,8 @@ mod tests {
         model::order::SellTokenSource,
     };
 
+    const TTL: Duration = Duration::from_secs(60);
+
     fn query(token: u8) -> Query {
         Query {
             owner: H160([1; 20]),
@@ -224,7 +240,7 @@ mod tests {
             .withf(|arg| arg == [query(1)])
             .returning(|_| vec![Ok(1.into())]);
 
-        let fetcher = Balances::new(Arc::new(inner));
+        let fetcher = Balances::new(Arc::new(inner), TTL);
         // 1st call to `inner`.
         let result = fetcher.get_balances(&[query(1)]).await;
         assert_eq!(result[0].as_ref().unwrap(), &1.into());
@@ -242,7 +258,7 @@ mod tests {
             .withf(|arg| arg == [query(1)])
             .returning(|_| vec![Err(anyhow::anyhow!("some error"))]);
 
-        let fetcher = Balances::new(Arc::new(inner));
+        let fetcher = Balances::new(Arc::new(inner), TTL);
         // 1st call to `inner`.
         assert!(fetcher.get_balances(&[query(1)]).await[0].is_err());
         // 2nd call to `inner`.
@@ -261,7 +277,7 @@ mod tests {
             .withf(|arg| arg == [query(1)])
             .returning(|_| vec![Ok(U256::one())]);
 
-        let fetcher = Balances::new(Arc::new(inner));
+        let fetcher = Balances::new(Arc::new(inner), TTL);
         fetcher.spawn_background_task(receiver);
 
         // 1st call to `inner`. Balance gets cached.
@@ -298,7 +314,7 @@ mod tests {
             .withf(|arg| arg == [query(2)])
             .returning(|_| vec![Ok(2.into())]);
 
-        let fetcher = Balances::new(Arc::new(inner));
+        let fetcher = Balances::new(Arc::new(inner), TTL);
         // 1st call to `inner` putting balance 1 into the cache.
         let result = fetcher.get_balances(&[query(1)]).await;
         assert_eq!(result[0].as_ref().unwrap(), &1.into());
@@ -324,7 +340,7 @@ mod tests {
             .times(7)
             .returning(|_| vec![Ok(U256::one())]);
 
-        let fetcher = Balances::new(Arc::new(inner));
+        let fetcher = Balances::new(Arc::new(inner), TTL);
         fetcher.spawn_background_task(receiver);
 
         let cached_entry = || {
@@ -337,12 +353,14 @@ mod tests {
         let result = fetcher.get_balances(&[query(1)]).await;
         assert_eq!(result[0].as_ref().unwrap(), &1.into());
 
-        for block in 1..=EVICTION_TIME + 1 {
+        // Blocks every 12 seconds, so the balance is kept for 5 more blocks.
+        for block in 1..=6 {
             assert!(cached_entry().is_some());
             // New block gets detected.
             sender
                 .send(BlockInfo {
                     number: block,
+                    timestamp: block * 12,
                     ..Default::default()
                 })
                 .unwrap();
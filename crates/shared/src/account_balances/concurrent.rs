@@ -0,0 +1,158 @@
+//! A balance fetching layer that deduplicates queries and fetches them in
+//! chunks with a bounded number of concurrent requests. This avoids querying
+//! the same balance once per order when many orders share the same owner and
+//! sell token.
+
+use {
+    crate::account_balances::{BalanceFetching, Query, TransferSimulationError},
+    anyhow::Result,
+    futures::{stream, StreamExt},
+    itertools::Itertools,
+    primitive_types::U256,
+    std::{collections::HashMap, num::NonZeroUsize, sync::Arc},
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    /// Maximum number of chunks of balance queries in flight at the same time.
+    pub parallelism: NonZeroUsize,
+    /// Maximum number of balance queries passed to the inner fetcher at once.
+    pub chunk_size: NonZeroUsize,
+}
+
+pub struct Balances {
+    inner: Arc<dyn BalanceFetching>,
+    config: Config,
+}
+
+impl Balances {
+    pub fn new(inner: Arc<dyn BalanceFetching>, config: Config) -> Self {
+        Self { inner, config }
+    }
+}
+
+#[async_trait::async_trait]
+impl BalanceFetching for Balances {
+    async fn get_balances(&self, queries: &[Query]) -> Vec<Result<U256>> {
+        let unique = queries.iter().unique().cloned().collect_vec();
+        let fetched: HashMap<_, _> = stream::iter(unique.chunks(self.config.chunk_size.get()))
+            .map(|chunk| async move {
+                let results = self.inner.get_balances(chunk).await;
+                chunk.iter().zip(results).collect_vec()
+            })
+            .buffer_unordered(self.config.parallelism.get())
+            .flat_map(stream::iter)
+            .collect()
+            .await;
+
+        queries
+            .iter()
+            .map(|query| match fetched.get(query) {
+                Some(Ok(balance)) => Ok(*balance),
+                // Errors can't be cloned, so every query sharing the failed
+                // request gets its own copy of the message.
+                Some(Err(err)) => Err(anyhow::anyhow!("{err:#}")),
+                None => Err(anyhow::anyhow!("missing balance result")),
+            })
+            .collect()
+    }
+
+    async fn can_transfer(
+        &self,
+        query: &Query,
+        amount: U256,
+    ) -> Result<(), TransferSimulationError> {
+        self.inner.can_transfer(query, amount).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::account_balances::MockBalanceFetching,
+        ethcontract::H160,
+        model::order::SellTokenSource,
+    };
+
+    fn query(token: u8) -> Query {
+        Query {
+            owner: H160([1; 20]),
+            token: H160([token; 20]),
+            source: SellTokenSource::Erc20,
+            interactions: vec![],
+        }
+    }
+
+    fn config(chunk_size: usize) -> Config {
+        Config {
+            parallelism: NonZeroUsize::new(4).unwrap(),
+            chunk_size: NonZeroUsize::new(chunk_size).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn shared_owner_and_token_is_fetched_once() {
+        let mut inner = MockBalanceFetching::new();
+        inner
+            .expect_get_balances()
+            .times(1)
+            .withf(|arg| arg == [query(1)])
+            .returning(|_| vec![Ok(1.into())]);
+
+        let fetcher = Balances::new(Arc::new(inner), config(100));
+        let result = fetcher.get_balances(&vec![query(1); 10]).await;
+        assert_eq!(result.len(), 10);
+        assert!(result.iter().all(|r| r.as_ref().unwrap() == &1.into()));
+    }
+
+    #[tokio::test]
+    async fn fetches_deduplicated_chunks() {
+        let mut inner = MockBalanceFetching::new();
+        inner
+            .expect_get_balances()
+            .times(2)
+            .withf(|arg| arg.len() == 2)
+            .returning(|queries| {
+                queries
+                    .iter()
+                    .map(|query| Ok(query.token.0[0].into()))
+                    .collect()
+            });
+        inner
+            .expect_get_balances()
+            .times(1)
+            .withf(|arg| arg.len() == 1)
+            .returning(|queries| {
+                queries
+                    .iter()
+                    .map(|query| Ok(query.token.0[0].into()))
+                    .collect()
+            });
+
+        let fetcher = Balances::new(Arc::new(inner), config(2));
+        let queries = [1, 2, 3, 1, 4, 5, 2].map(query);
+        let result = fetcher.get_balances(&queries).await;
+        let balances = result
+            .into_iter()
+            .map(|r| r.unwrap().as_u64())
+            .collect_vec();
+        assert_eq!(balances, [1, 2, 3, 1, 4, 5, 2]);
+    }
+
+    #[tokio::test]
+    async fn shares_errors_between_duplicate_queries() {
+        let mut inner = MockBalanceFetching::new();
+        inner
+            .expect_get_balances()
+            .times(1)
+            .withf(|arg| arg == [query(1), query(2)])
+            .returning(|_| vec![Err(anyhow::anyhow!("some error")), Ok(2.into())]);
+
+        let fetcher = Balances::new(Arc::new(inner), config(100));
+        let result = fetcher.get_balances(&[query(1), query(2), query(1)]).await;
+        assert!(result[0].is_err());
+        assert_eq!(result[1].as_ref().unwrap(), &2.into());
+        assert!(result[2].is_err());
+    }
+}
@@ -34,6 +34,11 @@ macro_rules! logging_args_with_default_filter {
 
             #[clap(long, env, default_value = "error")]
             pub log_stderr_threshold: LevelFilter,
+
+            /// Enables `tokio-console` instrumentation so that the state of
+            /// all tokio tasks can be inspected live.
+            #[clap(long, env, action = clap::ArgAction::Set, default_value = "false")]
+            pub enable_task_console: bool,
         }
 
         impl ::std::fmt::Display for $struct_name {
@@ -41,10 +46,12 @@ macro_rules! logging_args_with_default_filter {
                 let Self {
                     log_filter,
                     log_stderr_threshold,
+                    enable_task_console,
                 } = self;
 
                 writeln!(f, "log_filter: {}", log_filter)?;
                 writeln!(f, "log_stderr_threshold: {}", log_stderr_threshold)?;
+                writeln!(f, "enable_task_console: {}", enable_task_console)?;
                 Ok(())
             }
         }
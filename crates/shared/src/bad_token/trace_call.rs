@@ -448,7 +448,7 @@ mod tests {
     #[ignore]
     async fn mainnet_tokens() {
         // observe::tracing::initialize("orderbook::bad_token=debug,
-        // shared::transport=debug", tracing::level_filters::LevelFilter::OFF);
+        // shared::transport=debug", tracing::level_filters::LevelFilter::OFF, false);
         let http = create_env_test_transport();
         let web3 = Web3::new(http);
         let version = web3.eth().chain_id().await.unwrap().to_string();
@@ -693,7 +693,7 @@ mod tests {
     #[tokio::test]
     #[ignore]
     async fn mainnet_univ3() {
-        observe::tracing::initialize_reentrant("shared=debug");
+        observe::tracing::initialize_reentrant("shared=debug", false);
         let http = create_env_test_transport();
         let web3 = Web3::new(http);
         let base_tokens = vec![testlib::tokens::WETH];
@@ -108,6 +108,7 @@ pub enum PartialValidationError {
     UnsupportedBuyTokenDestination(BuyTokenDestination),
     UnsupportedSellTokenSource(SellTokenSource),
     UnsupportedOrderType,
+    UnsupportedSigningScheme(SigningScheme),
     UnsupportedToken { token: H160, reason: String },
     Other(anyhow::Error),
 }
@@ -252,6 +253,8 @@ pub struct OrderValidator {
     pub code_fetcher: Arc<dyn CodeFetching>,
     app_data_validator: Validator,
     max_gas_per_order: u64,
+    /// Whether the settlement contract supports pre-signed orders.
+    presign_supported: bool,
 }
 
 #[derive(Debug, Eq, PartialEq, Default)]
@@ -323,6 +326,7 @@ impl OrderValidator {
         code_fetcher: Arc<dyn CodeFetching>,
         app_data_validator: Validator,
         max_gas_per_order: u64,
+        presign_supported: bool,
     ) -> Self {
         Self {
             native_token,
@@ -339,6 +343,7 @@ impl OrderValidator {
             code_fetcher,
             app_data_validator,
             max_gas_per_order,
+            presign_supported,
         }
     }
 
@@ -407,6 +412,12 @@ impl OrderValidating for OrderValidator {
             return Err(PartialValidationError::UnsupportedOrderType);
         }
 
+        if order.signing_scheme == SigningScheme::PreSign && !self.presign_supported {
+            return Err(PartialValidationError::UnsupportedSigningScheme(
+                order.signing_scheme,
+            ));
+        }
+
         if order.buy_token_balance != BuyTokenDestination::Erc20 {
             return Err(PartialValidationError::UnsupportedBuyTokenDestination(
                 order.buy_token_balance,
@@ -1057,6 +1068,7 @@ mod tests {
             Arc::new(MockCodeFetching::new()),
             Default::default(),
             u64::MAX,
+            false,
         );
         let result = validator
             .partial_validate(PreOrderData {
@@ -1068,6 +1080,17 @@ mod tests {
             matches!(result, Err(PartialValidationError::UnsupportedOrderType)),
             "{result:?}"
         );
+        assert!(matches!(
+            validator
+                .partial_validate(PreOrderData {
+                    signing_scheme: SigningScheme::PreSign,
+                    ..Default::default()
+                })
+                .await,
+            Err(PartialValidationError::UnsupportedSigningScheme(
+                SigningScheme::PreSign
+            ))
+        ));
         assert!(matches!(
             validator
                 .partial_validate(PreOrderData {
@@ -1204,6 +1227,7 @@ mod tests {
             Arc::new(MockCodeFetching::new()),
             Default::default(),
             u64::MAX,
+            true,
         );
         let order = || PreOrderData {
             valid_to: time::now_in_epoch_seconds()
@@ -1292,6 +1316,7 @@ mod tests {
             Arc::new(MockCodeFetching::new()),
             Default::default(),
             u64::MAX,
+            true,
         );
 
         let creation = OrderCreation {
@@ -1496,6 +1521,7 @@ mod tests {
             Arc::new(MockCodeFetching::new()),
             Default::default(),
             u64::MAX,
+            true,
         );
 
         let creation = OrderCreation {
@@ -1567,6 +1593,7 @@ mod tests {
             Arc::new(MockCodeFetching::new()),
             Default::default(),
             u64::MAX,
+            true,
         );
 
         let creation = OrderCreation {
@@ -1623,6 +1650,7 @@ mod tests {
             Arc::new(MockCodeFetching::new()),
             Default::default(),
             u64::MAX,
+            true,
         );
         let order = OrderCreation {
             valid_to: time::now_in_epoch_seconds() + 2,
@@ -1674,6 +1702,7 @@ mod tests {
             Arc::new(MockCodeFetching::new()),
             Default::default(),
             u64::MAX,
+            true,
         );
         let order = OrderCreation {
             valid_to: time::now_in_epoch_seconds() + 2,
@@ -1726,6 +1755,7 @@ mod tests {
             Arc::new(MockCodeFetching::new()),
             Default::default(),
             u64::MAX,
+            true,
         );
         let order = OrderCreation {
             valid_to: time::now_in_epoch_seconds() + 2,
@@ -1780,6 +1810,7 @@ mod tests {
             Arc::new(MockCodeFetching::new()),
             Default::default(),
             u64::MAX,
+            true,
         );
         let order = OrderCreation {
             valid_to: time::now_in_epoch_seconds() + 2,
@@ -1837,6 +1868,7 @@ mod tests {
             Arc::new(MockCodeFetching::new()),
             Default::default(),
             u64::MAX,
+            true,
         );
         let order = OrderCreation {
             valid_to: time::now_in_epoch_seconds() + 2,
@@ -1893,6 +1925,7 @@ mod tests {
             Arc::new(MockCodeFetching::new()),
             Default::default(),
             u64::MAX,
+            true,
         );
 
         let creation = OrderCreation {
@@ -1956,6 +1989,7 @@ mod tests {
                 Arc::new(MockCodeFetching::new()),
                 Default::default(),
                 u64::MAX,
+                true,
             );
 
             let order = OrderCreation {
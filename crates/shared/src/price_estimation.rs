@@ -164,6 +164,13 @@ pub struct Arguments {
     #[clap(long, env, default_value = "1")]
     pub native_price_cache_concurrent_requests: usize,
 
+    /// Aggregate the native price estimators of each stage into the median of
+    /// their estimates instead of letting them compete. Estimates deviating
+    /// from the median by more than this factor (e.g. 0.1 for 10%) are
+    /// discarded as outliers.
+    #[clap(long, env)]
+    pub native_price_estimation_median_max_deviation: Option<f64>,
+
     /// The amount in native tokens atoms to use for price estimation. Should be
     /// reasonably large so that small pools do not influence the prices. If
     /// not set a reasonable default is used based on network id.
@@ -244,6 +251,7 @@ impl Display for Arguments {
             native_price_prefetch_time,
             native_price_cache_max_update_size,
             native_price_cache_concurrent_requests,
+            native_price_estimation_median_max_deviation,
             amount_to_estimate_prices_with,
             balancer_sor_url,
             one_inch_api_key,
@@ -285,6 +293,11 @@ impl Display for Arguments {
             "native_price_cache_concurrent_requests: {}",
             native_price_cache_concurrent_requests
         )?;
+        display_option(
+            f,
+            "native_price_estimation_median_max_deviation",
+            native_price_estimation_median_max_deviation,
+        )?;
         display_option(
             f,
             "amount_to_estimate_prices_with: {}",
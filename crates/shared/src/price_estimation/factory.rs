@@ -311,6 +311,12 @@ impl<'a> PriceEstimatorFactory<'a> {
             for source in stage {
                 stages.push(self.create_native_estimator(source, &weth).await?);
             }
+            if let Some(max_deviation) = self.args.native_price_estimation_median_max_deviation {
+                let median: Arc<dyn NativePriceEstimating> = Arc::new(
+                    native::MedianNativePriceEstimator::new(stages, max_deviation),
+                );
+                stages = vec![("median".to_owned(), median)];
+            }
             estimators.push(stages);
         }
 
@@ -8,9 +8,10 @@ use {
 };
 
 mod coingecko;
+mod median;
 mod oneinch;
 
-pub use self::{coingecko::CoinGecko, oneinch::OneInch};
+pub use self::{coingecko::CoinGecko, median::MedianNativePriceEstimator, oneinch::OneInch};
 
 pub type NativePrice = f64;
 pub type NativePriceEstimateResult = Result<NativePrice, PriceEstimationError>;
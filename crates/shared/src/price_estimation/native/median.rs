@@ -0,0 +1,136 @@
+use {
+    super::{NativePrice, NativePriceEstimateResult, NativePriceEstimating},
+    crate::price_estimation::PriceEstimationError,
+    futures::{future::BoxFuture, FutureExt},
+    primitive_types::H160,
+    std::sync::Arc,
+};
+
+/// Aggregates the native prices of multiple estimators into their median,
+/// discarding estimates which deviate too much from it. This makes the price
+/// robust against a single misbehaving source.
+pub struct MedianNativePriceEstimator {
+    estimators: Vec<(String, Arc<dyn NativePriceEstimating>)>,
+    max_deviation: f64,
+}
+
+impl MedianNativePriceEstimator {
+    /// Creates a new estimator. Estimates deviating from the median of all
+    /// estimates by more than `max_deviation` (e.g. 0.1 for 10%) are treated
+    /// as outliers.
+    pub fn new(
+        estimators: Vec<(String, Arc<dyn NativePriceEstimating>)>,
+        max_deviation: f64,
+    ) -> Self {
+        assert!(!estimators.is_empty());
+        Self {
+            estimators,
+            max_deviation,
+        }
+    }
+}
+
+impl NativePriceEstimating for MedianNativePriceEstimator {
+    fn estimate_native_price(&self, token: H160) -> BoxFuture<'_, NativePriceEstimateResult> {
+        async move {
+            let results = futures::future::join_all(
+                self.estimators
+                    .iter()
+                    .map(|(_, estimator)| estimator.estimate_native_price(token)),
+            )
+            .await;
+
+            let mut error = None;
+            let mut prices = Vec::with_capacity(results.len());
+            for ((name, _), result) in self.estimators.iter().zip(results) {
+                match result {
+                    Ok(price) => prices.push((name.as_str(), price)),
+                    Err(err) => {
+                        tracing::debug!(estimator = %name, ?token, ?err, "native price failed");
+                        error.get_or_insert(err);
+                    }
+                }
+            }
+
+            let Some(median) = median(prices.iter().map(|(_, price)| *price)) else {
+                return Err(error.unwrap_or(PriceEstimationError::NoLiquidity));
+            };
+            let prices = prices
+                .into_iter()
+                .filter(|(name, price)| {
+                    let outlier = ((price - median) / median).abs() > self.max_deviation;
+                    if outlier {
+                        tracing::debug!(
+                            estimator = %name,
+                            ?token,
+                            price,
+                            median,
+                            "discarding outlier native price"
+                        );
+                    }
+                    !outlier
+                })
+                .map(|(_, price)| price);
+            // The median itself never deviates from the median, so there is
+            // always at least one price left.
+            Ok(median(prices).unwrap())
+        }
+        .boxed()
+    }
+}
+
+/// The median of the prices, or the mean of the two middle prices for an even
+/// number of prices.
+fn median(prices: impl Iterator<Item = NativePrice>) -> Option<NativePrice> {
+    let mut prices = prices.collect::<Vec<_>>();
+    prices.sort_by(f64::total_cmp);
+    let mid = prices.len() / 2;
+    match prices.len() {
+        0 => None,
+        len if len % 2 == 0 => Some((prices[mid - 1] + prices[mid]) / 2.),
+        _ => Some(prices[mid]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::price_estimation::native::MockNativePriceEstimating};
+
+    fn estimator(estimate: NativePriceEstimateResult) -> (String, Arc<dyn NativePriceEstimating>) {
+        let mut estimator = MockNativePriceEstimating::new();
+        estimator
+            .expect_estimate_native_price()
+            .times(1)
+            .return_once(move |_| async move { estimate }.boxed());
+        ("estimator".to_owned(), Arc::new(estimator))
+    }
+
+    #[tokio::test]
+    async fn median_ignores_outliers() {
+        let estimator = MedianNativePriceEstimator::new(
+            vec![
+                estimator(Ok(1.0)),
+                estimator(Ok(20.0)),
+                estimator(Ok(2.0)),
+                estimator(Err(PriceEstimationError::NoLiquidity)),
+            ],
+            0.5,
+        );
+
+        // The median of all estimates is 2.0, so 20.0 is an outlier and the
+        // price is the median of the remaining estimates.
+        let price = estimator.estimate_native_price(H160::zero()).await.unwrap();
+        assert_eq!(price, 1.5);
+    }
+
+    #[tokio::test]
+    async fn median_fails_without_estimates() {
+        let estimator = MedianNativePriceEstimator::new(
+            vec![estimator(Err(PriceEstimationError::RateLimited))],
+            0.5,
+        );
+
+        let result = estimator.estimate_native_price(H160::zero()).await;
+        assert_eq!(result.unwrap_err(), PriceEstimationError::RateLimited);
+    }
+}
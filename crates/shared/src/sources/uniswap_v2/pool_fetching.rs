@@ -9,13 +9,10 @@ use {
     anyhow::Result,
     contracts::{IUniswapLikePair, ERC20},
     ethcontract::{errors::MethodError, BlockId, H160, U256},
-    futures::{
-        future::{self, BoxFuture},
-        FutureExt as _,
-    },
+    futures::{future::BoxFuture, stream, FutureExt as _, StreamExt as _, TryStreamExt as _},
     model::TokenPair,
     num::rational::Ratio,
-    std::{collections::HashSet, sync::RwLock, time::Duration},
+    std::{collections::HashSet, num::NonZeroUsize, sync::RwLock, time::Duration},
     ttl_cache::TtlCache,
 };
 
@@ -189,6 +186,8 @@ pub struct PoolFetcher<Reader> {
     pub web3: Web3,
     pub cache_time: Duration,
     pub non_existent_pools: RwLock<TtlCache<TokenPair, ()>>,
+    /// How many pool states are read concurrently. Unlimited if `None`.
+    pub max_concurrent_reads: Option<NonZeroUsize>,
 }
 
 impl<Reader> PoolFetcher<Reader> {
@@ -198,6 +197,16 @@ impl<Reader> PoolFetcher<Reader> {
             web3,
             cache_time,
             non_existent_pools: RwLock::new(TtlCache::new(usize::MAX)),
+            max_concurrent_reads: None,
+        }
+    }
+
+    /// Limits how many pool states are read concurrently, e.g. to stay within
+    /// the rate limits of the node.
+    pub fn with_max_concurrent_reads(self, max_concurrent_reads: Option<NonZeroUsize>) -> Self {
+        Self {
+            max_concurrent_reads,
+            ..self
         }
     }
 }
@@ -214,12 +223,18 @@ where
             token_pairs.retain(|pair| !non_existent_pools.contains_key(pair));
         }
         let block = BlockId::Number(at_block.into());
-        let futures = token_pairs
-            .iter()
-            .map(|pair| self.pool_reader.read_state(*pair, block))
-            .collect::<Vec<_>>();
-
-        let results = future::try_join_all(futures).await?;
+        let concurrency = self
+            .max_concurrent_reads
+            .map_or(token_pairs.len(), NonZeroUsize::get)
+            .max(1);
+        let results = stream::iter(
+            token_pairs
+                .iter()
+                .map(|pair| self.pool_reader.read_state(*pair, block)),
+        )
+        .buffered(concurrency)
+        .try_collect::<Vec<_>>()
+        .await?;
 
         let mut new_missing_pairs = vec![];
         let mut pools = vec![];
@@ -496,6 +511,42 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn pool_fetcher_limits_concurrent_reads() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Default)]
+        struct Reader {
+            running: AtomicUsize,
+            max_running: AtomicUsize,
+        }
+
+        impl PoolReading for Reader {
+            fn read_state(&self, _: TokenPair, _: BlockId) -> BoxFuture<'_, Result<Option<Pool>>> {
+                async move {
+                    let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+                    self.max_running.fetch_max(running, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    self.running.fetch_sub(1, Ordering::SeqCst);
+                    Ok(None)
+                }
+                .boxed()
+            }
+        }
+
+        let web3 = Web3::new(crate::ethrpc::create_test_transport(
+            "http://localhost:8545",
+        ));
+        let fetcher = PoolFetcher::new(Reader::default(), web3, Duration::from_secs(60))
+            .with_max_concurrent_reads(NonZeroUsize::new(3));
+        let pairs = (1..=10)
+            .map(|i| TokenPair::new(H160::from_low_u64_be(i), H160::zero()).unwrap())
+            .collect();
+
+        fetcher.fetch(pairs, Block::Number(1)).await.unwrap();
+        assert_eq!(fetcher.pool_reader.max_running.load(Ordering::SeqCst), 3);
+    }
+
     #[test]
     fn pool_fetcher_forwards_node_error() {
         let fetched_pool = FetchedPool {
@@ -125,6 +125,42 @@ where
     }
 }
 
+/// A liquidity source which gives up fetching liquidity after a timeout.
+/// Since [`LiquidityCollector`] skips sources that fail, this allows using the
+/// liquidity of all other sources when one of them is slow or unavailable.
+pub struct TimeoutLiquiditySource {
+    label: String,
+    inner: Box<dyn LiquidityCollecting>,
+    timeout: Duration,
+}
+
+impl TimeoutLiquiditySource {
+    pub fn new(label: &str, inner: Box<dyn LiquidityCollecting>, timeout: Duration) -> Self {
+        Self {
+            label: label.to_owned(),
+            inner,
+            timeout,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl LiquidityCollecting for TimeoutLiquiditySource {
+    async fn get_liquidity(
+        &self,
+        pairs: HashSet<TokenPair>,
+        at_block: Block,
+    ) -> Result<Vec<Liquidity>> {
+        let result = tokio::time::timeout(self.timeout, self.inner.get_liquidity(pairs, at_block))
+            .await
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("timed out after {:?}", self.timeout)));
+        if let Err(err) = &result {
+            tracing::warn!(source = self.label, ?err, "liquidity source unavailable");
+        }
+        result
+    }
+}
+
 #[derive(prometheus_metric_storage::MetricStorage)]
 struct Metrics {
     /// Tracks whether or not the graph based liquidity is currently enabled.
@@ -205,4 +241,45 @@ mod test {
         assert_eq!(liquidity.unwrap_err().to_string(), "I am initialised");
         assert_eq!(gauge.get(), 1);
     }
+
+    #[tokio::test]
+    async fn skips_sources_that_time_out() {
+        struct FakeSource(Duration);
+        #[async_trait::async_trait]
+        impl LiquidityCollecting for FakeSource {
+            async fn get_liquidity(
+                &self,
+                _pairs: HashSet<TokenPair>,
+                _at_block: Block,
+            ) -> Result<Vec<Liquidity>> {
+                tokio::time::sleep(self.0).await;
+                Ok(vec![Liquidity::LimitOrder(Default::default())])
+            }
+        }
+
+        let timeout = Duration::from_millis(50);
+        let collector = LiquidityCollector {
+            liquidity_sources: vec![
+                Box::new(TimeoutLiquiditySource::new(
+                    "slow",
+                    Box::new(FakeSource(Duration::from_secs(60))),
+                    timeout,
+                )),
+                Box::new(TimeoutLiquiditySource::new(
+                    "fast",
+                    Box::new(FakeSource(Duration::ZERO)),
+                    timeout,
+                )),
+            ],
+            base_tokens: Arc::new(BaseTokens::new(Default::default(), &[])),
+        };
+
+        let liquidity = collector
+            .get_liquidity(Default::default(), Block::Recent)
+            .await
+            .unwrap();
+        // Only the liquidity of the source which responded in time is used.
+        assert_eq!(liquidity.len(), 1);
+        assert!(matches!(liquidity[0], Liquidity::LimitOrder(_)));
+    }
 }
@@ -127,7 +127,11 @@ pub enum FeePolicy {
         quote: Quote,
     },
     #[serde(rename_all = "camelCase")]
-    Volume { factor: f64 },
+    Volume {
+        factor: f64,
+        #[serde(default)]
+        recipient: Option<H160>,
+    },
 }
 
 #[serde_as]
@@ -13,6 +13,7 @@ use {
 
 pub struct Solver<'a> {
     base_tokens: BaseTokens,
+    preferences: &'a baseline::Preferences,
     onchain_liquidity: HashMap<TokenPair, Vec<OnchainLiquidity>>,
     liquidity: HashMap<liquidity::Id, &'a liquidity::Liquidity>,
 }
@@ -21,11 +22,18 @@ impl<'a> Solver<'a> {
     pub fn new(
         weth: &eth::WethAddress,
         base_tokens: &HashSet<eth::TokenAddress>,
+        preferences: &'a baseline::Preferences,
+        min_pools_per_pair: Option<usize>,
         liquidity: &'a [liquidity::Liquidity],
     ) -> Self {
+        let mut onchain_liquidity = to_boundary_liquidity(liquidity);
+        if let Some(min_pools) = min_pools_per_pair {
+            onchain_liquidity.retain(|_, pools| pools.len() >= min_pools);
+        }
         Self {
             base_tokens: to_boundary_base_tokens(weth, base_tokens),
-            onchain_liquidity: to_boundary_liquidity(liquidity),
+            preferences,
+            onchain_liquidity,
             liquidity: liquidity
                 .iter()
                 .map(|liquidity| (liquidity.id.clone(), liquidity))
@@ -44,7 +52,7 @@ impl<'a> Solver<'a> {
             max_hops,
         );
 
-        let (segments, _) = match request.side {
+        let candidates = match request.side {
             order::Side::Buy => candidates
                 .iter()
                 .filter_map(|path| {
@@ -66,9 +74,9 @@ impl<'a> Solver<'a> {
                         return None;
                     }
 
-                    (sell.value <= request.sell.amount).then_some((segments, sell))
+                    (sell.value <= request.sell.amount).then_some((segments, sell.value))
                 })
-                .min_by_key(|(_, sell)| sell.value)?,
+                .collect(),
             order::Side::Sell => candidates
                 .iter()
                 .filter_map(|path| {
@@ -90,12 +98,12 @@ impl<'a> Solver<'a> {
                         return None;
                     }
 
-                    (buy.value >= request.buy.amount).then_some((segments, buy))
+                    (buy.value >= request.buy.amount).then_some((segments, buy.value))
                 })
-                .max_by_key(|(_, buy)| buy.value)?,
+                .collect(),
         };
 
-        baseline::Route::new(segments)
+        baseline::Route::new(self.preferences.select(candidates, request.side)?)
     }
 
     fn traverse_path(
@@ -6,7 +6,11 @@ pub mod limit_order;
 pub mod stable;
 pub mod weighted_product;
 
-use {crate::domain::eth, ethereum_types::H160, std::cmp::Ordering};
+use {
+    crate::domain::eth,
+    ethereum_types::{H160, U256},
+    std::{cmp::Ordering, collections::HashMap},
+};
 
 /// A source of liquidity which can be used by the solver.
 #[derive(Clone, Debug)]
@@ -18,9 +22,82 @@ pub struct Liquidity {
     pub state: State,
 }
 
+impl Liquidity {
+    /// How much liquidity a pool holds, used to choose between pools reported
+    /// at the same address. Returns `None` for limit orders, as many of them
+    /// share the address of the contract settling them.
+    fn depth(&self) -> Option<U256> {
+        match &self.state {
+            State::ConstantProduct(pool) => Some(pool.k()),
+            State::WeightedProduct(pool) => {
+                Some(pool.reserves.iter().fold(U256::zero(), |sum, reserve| {
+                    sum.saturating_add(reserve.asset.amount)
+                }))
+            }
+            State::Stable(pool) => Some(pool.reserves.iter().fold(U256::zero(), |sum, reserve| {
+                sum.saturating_add(reserve.asset.amount)
+            })),
+            State::Concentrated(pool) => Some(pool.liquidity.0.into()),
+            State::LimitOrder(_) => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Id(pub String);
 
+/// How to handle multiple pools reported at the same address.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Duplicates {
+    /// Keep the pool holding the most liquidity and drop the others.
+    #[default]
+    KeepDeepest,
+    /// Refuse to use the liquidity at all.
+    Reject,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("multiple liquidity pools at address {0:?}")]
+pub struct DuplicatePool(pub H160);
+
+/// Removes pools which share their address with another pool according to the
+/// specified policy. Duplicate pools indicate a bug in whatever provided the
+/// liquidity, so silently using either of them could hide it.
+pub fn deduplicate(
+    liquidity: Vec<Liquidity>,
+    duplicates: Duplicates,
+) -> Result<Vec<Liquidity>, DuplicatePool> {
+    let mut deduplicated = Vec::<Liquidity>::with_capacity(liquidity.len());
+    let mut pools = HashMap::<H160, usize>::new();
+    for liquidity in liquidity {
+        let Some(depth) = liquidity.depth() else {
+            deduplicated.push(liquidity);
+            continue;
+        };
+        let Some(&index) = pools.get(&liquidity.address) else {
+            pools.insert(liquidity.address, deduplicated.len());
+            deduplicated.push(liquidity);
+            continue;
+        };
+        let existing = &mut deduplicated[index];
+        tracing::warn!(
+            address = ?liquidity.address,
+            existing = ?existing.id,
+            duplicate = ?liquidity.id,
+            "duplicate liquidity pool"
+        );
+        match duplicates {
+            Duplicates::Reject => return Err(DuplicatePool(liquidity.address)),
+            Duplicates::KeepDeepest => {
+                if existing.depth().is_some_and(|existing| depth > existing) {
+                    *existing = liquidity;
+                }
+            }
+        }
+    }
+    Ok(deduplicated)
+}
+
 /// The liquidity state, specific to the type of liquidity.
 #[derive(Clone, Debug)]
 pub enum State {
@@ -31,6 +108,29 @@ pub enum State {
     LimitOrder(limit_order::LimitOrder),
 }
 
+impl State {
+    /// The kind of liquidity source this state belongs to.
+    pub fn kind(&self) -> Kind {
+        match self {
+            State::ConstantProduct(_) => Kind::ConstantProduct,
+            State::WeightedProduct(_) => Kind::WeightedProduct,
+            State::Stable(_) => Kind::Stable,
+            State::Concentrated(_) => Kind::Concentrated,
+            State::LimitOrder(_) => Kind::LimitOrder,
+        }
+    }
+}
+
+/// The kind of a liquidity source, independent of its state.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Kind {
+    ConstantProduct,
+    WeightedProduct,
+    Stable,
+    Concentrated,
+    LimitOrder,
+}
+
 /// An ordered token pair.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct TokenPair(eth::TokenAddress, eth::TokenAddress);
@@ -203,10 +203,32 @@ impl Single {
 pub struct ClearingPrices(pub HashMap<eth::TokenAddress, U256>);
 
 impl ClearingPrices {
+    /// The price of the reference token after [`ClearingPrices::normalize`].
+    pub const NORMALIZED_PRICE: U256 = U256([1_000_000_000_000_000_000, 0, 0, 0]);
+
     /// Creates a new set of clearing prices.
     pub fn new(prices: impl IntoIterator<Item = (eth::TokenAddress, U256)>) -> Self {
         Self(prices.into_iter().collect())
     }
+
+    /// Rescales all prices such that the reference token is priced at
+    /// [`ClearingPrices::NORMALIZED_PRICE`]. This preserves the ratios between
+    /// prices (up to rounding), so the normalized prices settle the same
+    /// trades, but makes prices of different solutions comparable.
+    ///
+    /// Returns `None` if the reference token has no or a zero price, or if a
+    /// rescaled price doesn't fit into 256 bits.
+    pub fn normalize(&self, reference: eth::TokenAddress) -> Option<Self> {
+        let reference = *self.0.get(&reference).filter(|price| !price.is_zero())?;
+        self.0
+            .iter()
+            .map(|(token, price)| {
+                let normalized = price.full_mul(Self::NORMALIZED_PRICE) / reference;
+                Some((*token, U256::try_from(normalized).ok()?))
+            })
+            .collect::<Option<_>>()
+            .map(Self)
+    }
 }
 
 /// A trade which executes an order as part of this solution.
@@ -378,3 +400,34 @@ pub const SETTLEMENT: u64 = 7365;
 /// Value was computed by taking 52 percentile median of `transfer()` costs
 /// of the 90% most traded tokens by volume in the month of Oct. 2021.
 pub const ERC20_TRANSFER: u64 = 27_513;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizing_clearing_prices_preserves_ratios() {
+        let token = |address: u64| eth::TokenAddress(Address::from_low_u64_be(address));
+        let prices = ClearingPrices::new([
+            (token(1), U256::from(2_000_000)),
+            (token(2), U256::exp10(30) * 5_u64),
+            (token(3), U256::from(4_000_000_000_u64)),
+        ]);
+
+        let normalized = prices.normalize(token(1)).unwrap();
+        assert_eq!(normalized.0[&token(1)], ClearingPrices::NORMALIZED_PRICE);
+        assert_eq!(normalized.0[&token(2)], U256::exp10(41) * 25_u64);
+        assert_eq!(normalized.0[&token(3)], U256::exp10(21) * 2_u64);
+        for (a, b) in [(1, 2), (1, 3), (2, 3)] {
+            assert_eq!(
+                prices.0[&token(a)] * normalized.0[&token(b)],
+                prices.0[&token(b)] * normalized.0[&token(a)],
+            );
+        }
+
+        // The reference token needs a non-zero price.
+        assert!(prices.normalize(token(4)).is_none());
+        let prices = ClearingPrices::new([(token(1), U256::zero())]);
+        assert!(prices.normalize(token(1)).is_none());
+    }
+}
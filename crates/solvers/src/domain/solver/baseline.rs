@@ -17,7 +17,11 @@ use {
         },
     },
     ethereum_types::U256,
-    std::{cmp, collections::HashSet, sync::Arc},
+    std::{
+        cmp,
+        collections::{HashMap, HashSet},
+        sync::Arc,
+    },
 };
 
 pub struct Baseline(Arc<Inner>);
@@ -33,6 +37,10 @@ pub struct Config {
     pub max_partial_attempts: usize,
     pub solution_gas_offset: eth::SignedGas,
     pub native_token_price_estimation_amount: eth::U256,
+    pub preferences: Preferences,
+    pub min_pools_per_pair: Option<usize>,
+    pub duplicate_pools: liquidity::Duplicates,
+    pub hard_pairs: HardPairs,
 }
 
 struct Inner {
@@ -64,6 +72,19 @@ struct Inner {
     /// The amount of the native token to use to estimate native price of a
     /// token
     native_token_price_estimation_amount: eth::U256,
+
+    /// Which liquidity sources to prefer when routing.
+    preferences: Preferences,
+
+    /// Minimum number of independent liquidity sources a token pair needs to
+    /// be routed through. Thin pairs are easy to manipulate.
+    min_pools_per_pair: Option<usize>,
+
+    /// How to handle multiple pools reported at the same address.
+    duplicate_pools: liquidity::Duplicates,
+
+    /// Token pairs which get a longer solving budget.
+    hard_pairs: HardPairs,
 }
 
 impl Baseline {
@@ -76,6 +97,10 @@ impl Baseline {
             max_partial_attempts: config.max_partial_attempts,
             solution_gas_offset: config.solution_gas_offset,
             native_token_price_estimation_amount: config.native_token_price_estimation_amount,
+            preferences: config.preferences,
+            min_pools_per_pair: config.min_pools_per_pair,
+            duplicate_pools: config.duplicate_pools,
+            hard_pairs: config.hard_pairs,
         }))
     }
 
@@ -90,7 +115,7 @@ impl Baseline {
         let deadline = auction
             .deadline
             .clone()
-            .reduce(DEADLINE_SLACK)
+            .reduce(self.0.hard_pairs.deadline_slack(&auction.orders))
             .remaining()
             .unwrap_or_default();
 
@@ -122,8 +147,20 @@ impl Inner {
         auction: auction::Auction,
         sender: tokio::sync::mpsc::UnboundedSender<solution::Solution>,
     ) {
-        let boundary_solver =
-            boundary::baseline::Solver::new(&self.weth, &self.base_tokens, &auction.liquidity);
+        let liquidity = match liquidity::deduplicate(auction.liquidity, self.duplicate_pools) {
+            Ok(liquidity) => liquidity,
+            Err(err) => {
+                tracing::error!(?err, "rejecting auction liquidity");
+                return;
+            }
+        };
+        let boundary_solver = boundary::baseline::Solver::new(
+            &self.weth,
+            &self.base_tokens,
+            &self.preferences,
+            self.min_pools_per_pair,
+            &liquidity,
+        );
 
         for (i, order) in auction.orders.into_iter().enumerate() {
             let Some(user_order) = UserOrder::new(&order) else {
@@ -283,6 +320,97 @@ fn to_normalized_price(price: f64) -> Option<U256> {
     }
 }
 
+/// Preferences for liquidity sources when choosing between routes. A route
+/// over more preferred sources is chosen over the one with the best amounts
+/// as long as its amounts are within the tolerance of the best ones.
+#[derive(Debug, Default)]
+pub struct Preferences {
+    /// Weights of the liquidity sources. Sources without a weight have a
+    /// weight of 1. A route is weighted by its least preferred source.
+    pub weights: HashMap<liquidity::Kind, f64>,
+    /// Share of the best route's output (or input, for buy orders) which may
+    /// be given up to route over preferred sources, e.g. 0.001 for 0.1%.
+    pub tolerance: f64,
+}
+
+impl Preferences {
+    /// Selects a route from the candidates and the amounts they trade: the
+    /// bought amount for sell orders and the sold amount for buy orders.
+    pub fn select<'a>(
+        &self,
+        candidates: Vec<(Vec<Segment<'a>>, U256)>,
+        side: order::Side,
+    ) -> Option<Vec<Segment<'a>>> {
+        let acceptable = match side {
+            order::Side::Sell => {
+                let best = candidates.iter().map(|(_, amount)| *amount).max()?;
+                best.to_f64_lossy() * (1. - self.tolerance)
+            }
+            order::Side::Buy => {
+                let best = candidates.iter().map(|(_, amount)| *amount).min()?;
+                best.to_f64_lossy() * (1. + self.tolerance)
+            }
+        };
+        let candidates = candidates.into_iter().filter(|(_, amount)| match side {
+            order::Side::Sell => amount.to_f64_lossy() >= acceptable,
+            order::Side::Buy => amount.to_f64_lossy() <= acceptable,
+        });
+        match side {
+            order::Side::Sell => candidates.max_by(|(a, a_amount), (b, b_amount)| {
+                self.weight(a)
+                    .total_cmp(&self.weight(b))
+                    .then(a_amount.cmp(b_amount))
+            }),
+            order::Side::Buy => candidates.min_by(|(a, a_amount), (b, b_amount)| {
+                self.weight(b)
+                    .total_cmp(&self.weight(a))
+                    .then(a_amount.cmp(b_amount))
+            }),
+        }
+        .map(|(segments, _)| segments)
+    }
+
+    fn weight(&self, segments: &[Segment]) -> f64 {
+        segments
+            .iter()
+            .map(|segment| {
+                self.weights
+                    .get(&segment.liquidity.state.kind())
+                    .copied()
+                    .unwrap_or(1.)
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+/// Token pairs which are hard to route, e.g. because they need many hops or
+/// partial fill attempts. Auctions with orders on these pairs stop solving
+/// closer to the deadline.
+#[derive(Debug, Default)]
+pub struct HardPairs {
+    /// The token pairs, in either direction.
+    pub pairs: HashSet<(eth::TokenAddress, eth::TokenAddress)>,
+    /// The amount of time to finish before the deadline for auctions with an
+    /// order on a hard pair. Only used when it is less than the default slack.
+    pub deadline_slack: chrono::Duration,
+}
+
+impl HardPairs {
+    /// Returns the amount of time to finish solving the orders before the
+    /// auction deadline.
+    pub fn deadline_slack(&self, orders: &[order::Order]) -> chrono::Duration {
+        let hard = orders.iter().any(|order| {
+            let (sell, buy) = (order.sell.token, order.buy.token);
+            self.pairs.contains(&(sell.min(buy), sell.max(buy)))
+        });
+        if hard {
+            cmp::min(self.deadline_slack, DEADLINE_SLACK)
+        } else {
+            DEADLINE_SLACK
+        }
+    }
+}
+
 /// A baseline routing request.
 #[derive(Debug)]
 pub struct Request {
@@ -337,3 +465,46 @@ impl<'a> Route<'a> {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hard_pairs_get_longer_solving_budget() {
+        let token = |address: u64| eth::TokenAddress(eth::H160::from_low_u64_be(address));
+        let order = |sell: u64, buy: u64| order::Order {
+            uid: order::Uid([0; 56]),
+            sell: eth::Asset {
+                token: token(sell),
+                amount: U256::exp10(18),
+            },
+            buy: eth::Asset {
+                token: token(buy),
+                amount: U256::exp10(18),
+            },
+            side: order::Side::Sell,
+            class: order::Class::Market,
+            partially_fillable: false,
+        };
+        let hard_pairs = HardPairs {
+            pairs: HashSet::from([(token(1), token(2))]),
+            deadline_slack: chrono::Duration::milliseconds(100),
+        };
+        let deadline = auction::Deadline(chrono::Utc::now() + chrono::Duration::seconds(10));
+        let budget = |orders: &[order::Order]| {
+            deadline
+                .clone()
+                .reduce(hard_pairs.deadline_slack(orders))
+                .remaining()
+                .unwrap()
+        };
+
+        assert_eq!(hard_pairs.deadline_slack(&[order(3, 4)]), DEADLINE_SLACK);
+        assert_eq!(
+            hard_pairs.deadline_slack(&[order(3, 4), order(2, 1)]),
+            chrono::Duration::milliseconds(100)
+        );
+        assert!(budget(&[order(1, 2)]) > budget(&[order(3, 4)]));
+    }
+}
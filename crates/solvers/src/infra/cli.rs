@@ -17,6 +17,11 @@ pub struct Args {
     )]
     pub log: String,
 
+    /// Enables `tokio-console` instrumentation so that the state of all tokio
+    /// tasks can be inspected live.
+    #[arg(long, env, action = clap::ArgAction::Set, default_value = "false")]
+    pub enable_task_console: bool,
+
     /// The socket address to bind to.
     #[arg(long, env, default_value = "127.0.0.1:7872")]
     pub addr: SocketAddr,
@@ -1,6 +1,6 @@
 use {
     crate::{
-        domain::{eth, solver::baseline},
+        domain::{eth, liquidity, solver::baseline},
         infra::{config::unwrap_or_log, contracts},
         util::serialize,
     },
@@ -8,7 +8,7 @@ use {
     serde::Deserialize,
     serde_with::serde_as,
     shared::price_estimation::gas::SETTLEMENT_OVERHEAD,
-    std::path::Path,
+    std::{collections::HashMap, path::Path},
     tokio::fs,
 };
 
@@ -48,6 +48,76 @@ struct Config {
     /// token
     #[serde_as(as = "serialize::U256")]
     native_token_price_estimation_amount: eth::U256,
+
+    /// Weights of the liquidity sources to prefer when routing. Sources
+    /// without a weight have a weight of 1.
+    #[serde(default)]
+    source_weights: HashMap<LiquiditySource, f64>,
+
+    /// Share of the best route's output (or input, for buy orders) which may
+    /// be given up to route over sources with higher weights, e.g. 0.001 for
+    /// 0.1%.
+    #[serde(default)]
+    source_preference_tolerance: f64,
+
+    /// Minimum number of independent liquidity sources a token pair needs
+    /// before it is routed through. Pairs with fewer sources are considered
+    /// too easy to manipulate and are only tradable via CoWs.
+    #[serde(default)]
+    min_pools_per_pair: Option<usize>,
+
+    /// How to handle multiple pools reported at the same address: either keep
+    /// the one holding the most liquidity or don't use any liquidity at all.
+    #[serde(default)]
+    duplicate_pools: DuplicatePools,
+
+    /// Token pairs which are hard to route and get a longer solving budget.
+    #[serde(default)]
+    hard_pairs: Vec<[eth::H160; 2]>,
+
+    /// The amount of time in milliseconds to finish solving before the
+    /// deadline for auctions with an order on a hard pair.
+    #[serde(default = "default_hard_pair_deadline_slack_ms")]
+    hard_pair_deadline_slack_ms: u32,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+enum DuplicatePools {
+    #[default]
+    KeepDeepest,
+    Reject,
+}
+
+impl From<DuplicatePools> for liquidity::Duplicates {
+    fn from(value: DuplicatePools) -> Self {
+        match value {
+            DuplicatePools::KeepDeepest => Self::KeepDeepest,
+            DuplicatePools::Reject => Self::Reject,
+        }
+    }
+}
+
+#[derive(Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+enum LiquiditySource {
+    ConstantProduct,
+    WeightedProduct,
+    Stable,
+    Concentrated,
+    LimitOrder,
+}
+
+impl From<LiquiditySource> for liquidity::Kind {
+    fn from(value: LiquiditySource) -> Self {
+        match value {
+            LiquiditySource::ConstantProduct => Self::ConstantProduct,
+            LiquiditySource::WeightedProduct => Self::WeightedProduct,
+            LiquiditySource::Stable => Self::Stable,
+            LiquiditySource::Concentrated => Self::Concentrated,
+            LiquiditySource::LimitOrder => Self::LimitOrder,
+        }
+    }
 }
 
 /// Load the driver configuration from a TOML file.
@@ -84,6 +154,29 @@ pub async fn load(path: &Path) -> baseline::Config {
         max_partial_attempts: config.max_partial_attempts,
         solution_gas_offset: config.solution_gas_offset.into(),
         native_token_price_estimation_amount: config.native_token_price_estimation_amount,
+        preferences: baseline::Preferences {
+            weights: config
+                .source_weights
+                .into_iter()
+                .map(|(source, weight)| (source.into(), weight))
+                .collect(),
+            tolerance: config.source_preference_tolerance,
+        },
+        min_pools_per_pair: config.min_pools_per_pair,
+        duplicate_pools: config.duplicate_pools.into(),
+        hard_pairs: baseline::HardPairs {
+            pairs: config
+                .hard_pairs
+                .into_iter()
+                .map(|[a, b]| {
+                    let (a, b) = (eth::TokenAddress(a), eth::TokenAddress(b));
+                    (a.min(b), a.max(b))
+                })
+                .collect(),
+            deadline_slack: chrono::Duration::milliseconds(
+                config.hard_pair_deadline_slack_ms.into(),
+            ),
+        },
     }
 }
 
@@ -92,3 +185,7 @@ pub async fn load(path: &Path) -> baseline::Config {
 fn default_gas_offset() -> i64 {
     SETTLEMENT_OVERHEAD.try_into().unwrap()
 }
+
+fn default_hard_pair_deadline_slack_ms() -> u32 {
+    100
+}
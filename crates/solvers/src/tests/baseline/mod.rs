@@ -3,6 +3,9 @@
 mod bal_liquidity;
 mod buy_order_rounding;
 mod direct_swap;
+mod duplicate_pools;
 mod internalization;
 mod limit_order_quoting;
+mod min_pools_per_pair;
 mod partial_fill;
+mod source_preferences;
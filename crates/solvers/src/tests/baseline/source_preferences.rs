@@ -0,0 +1,141 @@
+//! Test cases verifying that the baseline solver prefers routing over
+//! liquidity sources with higher weights when their output is close to the
+//! best one.
+
+use {crate::tests, serde_json::json};
+
+/// Sells WETH for COW with a Uniswap V2 pool and a slightly shallower Balancer
+/// weighted pool, returning the ID of the liquidity used by the solution.
+async fn solve(config: &str) -> serde_json::Value {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::String(format!(
+            r#"
+                    chain-id = "1"
+                    base-tokens = []
+                    max-hops = 0
+                    max-partial-attempts = 1
+                    native-token-price-estimation-amount = "100000000000000000"
+                    {config}
+                "#
+        )),
+    )
+    .await;
+
+    let solution = engine
+        .solve(json!({
+            "id": "1",
+            "tokens": {
+                "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                    "decimals": 18,
+                    "symbol": "WETH",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                },
+                "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                    "decimals": 18,
+                    "symbol": "COW",
+                    "referencePrice": "100000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                }
+            },
+            "orders": [
+                {
+                    "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a",
+                    "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                    "buyToken": "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB",
+                    "sellAmount": "1000000000000000000",
+                    "fullSellAmount": "1000000000000000000",
+                    "buyAmount": "1",
+                    "fullBuyAmount": "1",
+                    "feePolicies": [],
+                    "validTo": 0,
+                    "kind": "sell",
+                    "owner": "0x5b1e2c2762667331bc91648052f646d1b0d35984",
+                    "partiallyFillable": false,
+                    "preInteractions": [],
+                    "postInteractions": [],
+                    "sellTokenSource": "erc20",
+                    "buyTokenDestination": "erc20",
+                    "class": "market",
+                    "appData": "0x6000000000000000000000000000000000000000000000000000000000000007",
+                    "signingScheme": "presign",
+                    "signature": "0x",
+                }
+            ],
+            "liquidity": [
+                {
+                    "kind": "constantProduct",
+                    "tokens": {
+                        "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                            "balance": "100000000000000000000"
+                        },
+                        "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                            "balance": "1000000000000000000000000"
+                        }
+                    },
+                    "fee": "0.003",
+                    "id": "0",
+                    "address": "0x97b744df0b59d93A866304f97431D8EfAd29a08d",
+                    "router": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+                    "gasEstimate": "110000"
+                },
+                {
+                    "kind": "weightedProduct",
+                    "tokens": {
+                        "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                            "balance": "100000000000000000000",
+                            "scalingFactor": "1",
+                            "weight": "0.5",
+                        },
+                        "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                            "balance": "999000000000000000000000",
+                            "scalingFactor": "1",
+                            "weight": "0.5",
+                        }
+                    },
+                    "fee": "0.003",
+                    "id": "1",
+                    "address": "0x92762b42a06dcdddc5b7362cfb01e631c4d44b40",
+                    "balancerPoolId": "0x5c78d05b8ecf97507d1cf70646082c54faa4da950000000000000000000005ca",
+                    "gasEstimate": "88892",
+                    "version": "v0",
+                }
+            ],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z",
+            "surplusCapturingJitOrderOwners": []
+        }))
+        .await;
+
+    solution["solutions"][0]["interactions"][0]["id"].clone()
+}
+
+#[tokio::test]
+async fn best_output_without_preferences() {
+    assert_eq!(solve("").await, json!("0"));
+}
+
+#[tokio::test]
+async fn preferred_source_within_tolerance() {
+    let config = r#"
+        source-preference-tolerance = 0.01
+        [source-weights]
+        weighted-product = 2
+    "#;
+    assert_eq!(solve(config).await, json!("1"));
+}
+
+#[tokio::test]
+async fn best_output_outside_tolerance() {
+    let config = r#"
+        source-preference-tolerance = 0.0001
+        [source-weights]
+        weighted-product = 2
+    "#;
+    assert_eq!(solve(config).await, json!("0"));
+}
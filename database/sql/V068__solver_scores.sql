@@ -0,0 +1,9 @@
+-- The score of every solver that participated in an auction, not only the
+-- winner's, so that solver performance can be compared per auction.
+CREATE TABLE solver_scores (
+  auction_id bigint NOT NULL,
+  solver bytea NOT NULL,
+  score numeric(78,0) NOT NULL,
+
+  PRIMARY KEY (auction_id, solver)
+);
@@ -0,0 +1,4 @@
+-- The recipient volume based (partner) fees are paid out to as part of the
+-- settlement. Fees with a recipient aren't kept by the protocol.
+ALTER TABLE fee_policies
+    ADD COLUMN volume_fee_recipient bytea;